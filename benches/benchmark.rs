@@ -23,14 +23,17 @@ use locate::perf_of_locate;
 mod locate_with_raw_index;
 use locate_with_raw_index::compare_locate_vs_locate_from_raw_index;
 
-// mod perf_by_vector_size;
-// use perf_by_vector_size::{
-//     build_no_text, locate_no_text,
-// };
+mod perf_by_vector_size;
+use perf_by_vector_size::{
+    perf_of_rank_by_vector_size,
+    perf_of_count_by_vector_size,
+};
 
 criterion_group!(
     benches,
     compare_locate_vs_locate_from_raw_index,
+    perf_of_rank_by_vector_size,
+    perf_of_count_by_vector_size,
 );
 #[cfg(feature = "fastbwt")]
 criterion_group!(
@@ -51,10 +51,9 @@ pub fn perf_of_locate(c: &mut Criterion) {
     let n_patterns = 10; // 1000;
     let pattern_length = [10, 20, 30, 40, 50];
     let patterns_by_length: Vec<Vec<Vec<u8>>> = pattern_length.iter().map(|l| {
-        let patterns = (0..n_patterns).map(|_| {
+        (0..n_patterns).map(|_| {
             gen_rand_pattern(&text, *l, *l)
-        }).collect();
-        patterns
+        }).collect()
     }).collect();   
 
     let ss_list = [1, 2, 4, 8];
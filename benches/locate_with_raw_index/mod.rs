@@ -63,10 +63,9 @@ pub fn compare_locate_vs_locate_from_raw_index(c: &mut Criterion) {
     let n_patterns = 100;
     let pattern_length = [50]; // [10, 20, 30, 40, 50];
     let patterns_by_length: Vec<Vec<Vec<u8>>> = pattern_length.iter().map(|l| {
-        let patterns = (0..n_patterns).map(|_| {
+        (0..n_patterns).map(|_| {
             gen_rand_pattern(&text, *l, *l)
-        }).collect();
-        patterns
+        }).collect()
     }).collect();   
 
     let ss_list = [4];
@@ -1,169 +1,167 @@
 use criterion::{
     black_box, Criterion, BenchmarkId,
-    PlotConfiguration, AxisScale,
 };
-use lt_fm_index::*;
-use lt_fm_index::tests::random_text::{
-    NO_STEMS, gen_rand_text,
-    rand_pattern_of_length,
+use lt_fm_index::{
+    LtFmIndex, Block, BwmBackend,
+    blocks::{Block5, Block6},
+    backends::{Interleaved, Superblock},
 };
+use super::random_data::{
+    gen_rand_chr_list,
+    gen_rand_text,
+    gen_rand_pattern,
+};
+use rand::Rng;
 
-#[inline]
-fn build_32(text: Vec<u8>, ss: u64, lk: u32) -> LtFmIndex<text_encoders::C3B32> {
-    let te = text_encoders::C3B32::new(&[
-        &[b'A', b'a'],
-        &[b'C', b'c'],
-        &[b'G', b'g'],
-    ]);
-    LtFmIndex::new(text, &te, ss, lk)
-}
-fn build_64(text: Vec<u8>, ss: u64, lk: u32) -> LtFmIndex<text_encoders::C3B64> {
-    let te = text_encoders::C3B64::new(&[
-        &[b'A', b'a'],
-        &[b'C', b'c'],
-        &[b'G', b'g'],
-    ]);
-    LtFmIndex::new(text, &te, ss, lk)
-}
-#[inline]
-fn build_128(text: Vec<u8>, ss: u64, lk: u32) -> LtFmIndex<text_encoders::C3B128> {
-    let te = text_encoders::C3B128::new(&[
-        &[b'A', b'a'],
-        &[b'C', b'c'],
-        &[b'G', b'g'],
-    ]);
-    LtFmIndex::new(text, &te, ss, lk)
-}
-#[inline]
-fn build_mul_texts_32(texts: Vec<Vec<u8>>, ss: u64, lk: u32) {
-    for text in texts {
-        _ = build_32(text, ss, lk);
-    }
-}
-#[inline]
-fn build_mul_texts_64(texts: Vec<Vec<u8>>, ss: u64, lk: u32) {
-    for text in texts {
-        _ = build_64(text, ss, lk);
-    }
-}
-#[inline]
-fn build_mul_texts_128(texts: Vec<Vec<u8>>, ss: u64, lk: u32) {
-    for text in texts {
-        _ = build_128(text, ss, lk);
-    }
+/*
+Bench the rank of the both ends of the pos range in the blocks of 5 and 6 vectors,
+counting each end separately (scalar) or the both ends together (dispatched to SIMD)
+*/
+
+struct RankQuery {
+    block_idx: [usize; 2],
+    rems: [u32; 2],
+    chridx: u8,
 }
-#[inline]
-fn locate_mul_patterns_32(lfi: &LtFmIndex<text_encoders::C3B32>, patterns: &Vec<Vec<u8>>) {
-    for pattern in patterns {
-        _ = lfi.locate(pattern);
-    }
+
+fn gen_blocks_and_queries<B: Block<u32>>(chr_count: u32, block_count: usize, query_count: usize) -> (Vec<B>, Vec<RankQuery>) {
+    let mut rng = rand::rng();
+    let mut rank_pre_counts = vec![0_u32; chr_count as usize];
+    let blocks = (0..block_count).map(|_| {
+        let chunk: Vec<u8> = (0..B::BLOCK_LEN).map(|_| rng.random_range(1..=chr_count as u8)).collect();
+        B::vectorize(&chunk, &mut rank_pre_counts)
+    }).collect();
+    let queries = (0..query_count).map(|_| {
+        // Near ends, as the pos range narrowing in the search
+        let start_block_idx = rng.random_range(0..block_count);
+        let end_block_idx = (start_block_idx + rng.random_range(0..2)).min(block_count - 1);
+        RankQuery {
+            block_idx: [start_block_idx, end_block_idx],
+            rems: [rng.random_range(1..B::BLOCK_LEN), rng.random_range(1..B::BLOCK_LEN)],
+            chridx: rng.random_range(0..chr_count as u8),
+        }
+    }).collect();
+    (blocks, queries)
 }
+
 #[inline]
-fn locate_mul_patterns_64(lfi: &LtFmIndex<text_encoders::C3B64>, patterns: &Vec<Vec<u8>>) {
-    for pattern in patterns {
-        _ = lfi.locate(pattern);
-    }
+fn rank_each_end<B: Block<u32>>(blocks: &[B], queries: &[RankQuery]) -> u32 {
+    queries.iter().fold(0, |sum, query| {
+        sum
+        + blocks[query.block_idx[0]].get_remain_count_of(query.rems[0], query.chridx)
+        + blocks[query.block_idx[1]].get_remain_count_of(query.rems[1], query.chridx)
+    })
 }
 #[inline]
-fn locate_mul_patterns_128(lfi: &LtFmIndex<text_encoders::C3B128>, patterns: &Vec<Vec<u8>>) {
-    for pattern in patterns {
-        _ = lfi.locate(pattern);
-    }
+fn rank_both_ends<B: Block<u32>>(blocks: &[B], queries: &[RankQuery]) -> u32 {
+    queries.iter().fold(0, |sum, query| {
+        let [start_count, end_count] = B::get_remain_counts_of(
+            [&blocks[query.block_idx[0]], &blocks[query.block_idx[1]]],
+            query.rems,
+            query.chridx,
+        );
+        sum + start_count + end_count
+    })
 }
 
-pub fn build_no_text(c: &mut Criterion) {
-    let mut group = c.benchmark_group("build");
-
-    let plot_config = PlotConfiguration::default()
-        .summary_scale(AxisScale::Logarithmic);
-    group.plot_config(plot_config.clone());
-
-    let n = 20;
-    let ss = 2;
-    let lk = 4;
+pub fn perf_of_rank_by_vector_size(c: &mut Criterion) {
+    let mut group = c.benchmark_group("rank_of_pos_range");
 
-    let text_lens: Vec<usize> = {
-        (1..=6).map(| v | 10_i32.pow(v) as usize ).collect()
-    };
-
-    for tl in text_lens {
-        let texts: Vec<Vec<u8>> = (0..n).map(|_| gen_rand_text(&NO_STEMS, tl..tl+1)).collect();
-        
-        group.bench_with_input(
-            BenchmarkId::new("32", tl),
-            &tl,
-            |b, _i| b.iter(|| {
-                build_mul_texts_32(black_box(texts.clone()), black_box(ss), black_box(lk));
-            }
-        ));
-
-        group.bench_with_input(
-            BenchmarkId::new("64", tl),
-            &tl,
-            |b, _i| b.iter(|| {
-                build_mul_texts_64(black_box(texts.clone()), black_box(ss), black_box(lk));
-            }
-        ));
+    let block_count = 10_000;
+    let query_count = 10_000;
 
-        group.bench_with_input(
-            BenchmarkId::new("128", tl),
-            &tl,
-            |b, _i| b.iter(|| {
-                build_mul_texts_128(black_box(texts.clone()), black_box(ss), black_box(lk));
+    macro_rules! TestCode {
+        ( $block: ty, $chr_count: expr, $tag: tt ) => {
+            {
+                let (blocks, queries) = gen_blocks_and_queries::<$block>($chr_count, block_count, query_count);
+                assert_eq!(rank_each_end(&blocks, &queries), rank_both_ends(&blocks, &queries));
+                group.bench_with_input(
+                    BenchmarkId::new("each_end", $tag),
+                    &$tag,
+                    |b, _i| b.iter(|| {
+                        rank_each_end(black_box(&blocks), black_box(&queries))
+                    }
+                ));
+                group.bench_with_input(
+                    BenchmarkId::new("both_ends", $tag),
+                    &$tag,
+                    |b, _i| b.iter(|| {
+                        rank_both_ends(black_box(&blocks), black_box(&queries))
+                    }
+                ));
             }
-        ));
+        };
     }
+    TestCode!(Block5<u32>, 31, "b5_v32");
+    TestCode!(Block5<u64>, 31, "b5_v64");
+    TestCode!(Block5<u128>, 31, "b5_v128");
+    TestCode!(Block6<u32>, 63, "b6_v32");
+    TestCode!(Block6<u64>, 63, "b6_v64");
+    TestCode!(Block6<u128>, 63, "b6_v128");
+
     group.finish();
 }
 
-pub fn locate_no_text(c: &mut Criterion) {
-    let mut group = c.benchmark_group("locate");
-    
-    let plot_config = PlotConfiguration::default()
-        .summary_scale(AxisScale::Logarithmic);
-    group.plot_config(plot_config.clone());
-
-    
-    let ss = 2;
-    let lk = 4;
-    let text_len = 10_i32.pow(6) as usize;
-    let text = gen_rand_text(&NO_STEMS, text_len..text_len+1);
+/*
+Bench counting the patterns by the vector size of the blocks of 5 and 6 vectors,
+with the rank checkpoints separated from the blocks, interleaved with them,
+or in the two levels of the superblocks and the intervals of 4 blocks
+*/
 
-    let lt_fm_index_32 = build_32(text.clone(), ss, lk);
-    let lt_fm_index_64 = build_64(text.clone(), ss, lk);
-    let lt_fm_index_128 = build_128(text.clone(), ss, lk);
-
-    let pattern_lens: Vec<usize> = {
-        (1..=4).map(| v | 10_i32.pow(v) as usize ).collect()
-    };
+#[inline]
+fn count_multiple_patterns<B: BwmBackend<u32>>(lfi: &LtFmIndex<u32, B>, patterns: &[Vec<u8>]) -> u32 {
+    patterns.iter().map(|pattern| lfi.count(pattern)).sum()
+}
 
-    let n = 20;
-    for pl in pattern_lens {
-        let patterns: Vec<Vec<u8>> = (0..n).map(|_| rand_pattern_of_length(&text, pl)).collect();
-        
-        group.bench_with_input(
-            BenchmarkId::new("32", pl),
-            &pl,
-            |b, _i| b.iter(|| {
-                locate_mul_patterns_32(black_box(&lt_fm_index_32), black_box(&patterns));
-            }
-        ));
+pub fn perf_of_count_by_vector_size(c: &mut Criterion) {
+    let mut group = c.benchmark_group("count_by_vector_size");
 
-        group.bench_with_input(
-            BenchmarkId::new("64", pl),
-            &pl,
-            |b, _i| b.iter(|| {
-                locate_mul_patterns_64(black_box(&lt_fm_index_64), black_box(&patterns));
-            }
-        ));
+    let text_len = 1_000_000;
+    let n_patterns = 1_000;
+    let pattern_len = 20;
 
-        group.bench_with_input(
-            BenchmarkId::new("128", pl),
-            &pl,
-            |b, _i| b.iter(|| {
-                locate_mul_patterns_128(black_box(&lt_fm_index_128), black_box(&patterns));
+    macro_rules! TestCode {
+        ( $block: ty, $chr_count: expr, $tag: tt ) => {
+            {
+                let chr_list = gen_rand_chr_list($chr_count);
+                let characters_by_index = chr_list.chunks(1).collect::<Vec<_>>();
+                let text = gen_rand_text(&chr_list, text_len, text_len);
+                let patterns: Vec<Vec<u8>> = (0..n_patterns).map(|_| {
+                    gen_rand_pattern(&text, pattern_len, pattern_len)
+                }).collect();
+                let lfi = LtFmIndex::<u32, $block>::build(text.clone(), &characters_by_index, 4, 3).unwrap();
+                let interleaved_lfi = LtFmIndex::<u32, Interleaved<$block>>::build(text.clone(), &characters_by_index, 4, 3).unwrap();
+                let superblock_lfi = LtFmIndex::<u32, Superblock<$block, 4>>::build(text, &characters_by_index, 4, 3).unwrap();
+                group.bench_with_input(
+                    BenchmarkId::new("count", $tag),
+                    &$tag,
+                    |b, _i| b.iter(|| {
+                        count_multiple_patterns(black_box(&lfi), black_box(&patterns))
+                    }
+                ));
+                group.bench_with_input(
+                    BenchmarkId::new("count_interleaved", $tag),
+                    &$tag,
+                    |b, _i| b.iter(|| {
+                        count_multiple_patterns(black_box(&interleaved_lfi), black_box(&patterns))
+                    }
+                ));
+                group.bench_with_input(
+                    BenchmarkId::new("count_superblock", $tag),
+                    &$tag,
+                    |b, _i| b.iter(|| {
+                        count_multiple_patterns(black_box(&superblock_lfi), black_box(&patterns))
+                    }
+                ));
             }
-        ));
+        };
     }
+    TestCode!(Block5<u32>, 31, "b5_v32");
+    TestCode!(Block5<u64>, 31, "b5_v64");
+    TestCode!(Block5<u128>, 31, "b5_v128");
+    TestCode!(Block6<u32>, 63, "b6_v32");
+    TestCode!(Block6<u64>, 63, "b6_v64");
+    TestCode!(Block6<u128>, 63, "b6_v128");
+
     group.finish();
 }
@@ -0,0 +1,10 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed=test_capi.c");
+    println!("cargo:rerun-if-changed=../include/lt_fm_index.h");
+    cc::Build::new()
+        .file("test_capi.c")
+        .include("../include")
+        .warnings_into_errors(true)
+        .compile("lt_fm_index_capi_test");
+}
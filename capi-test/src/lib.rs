@@ -0,0 +1,26 @@
+/*!
+C test of the `capi` feature, built with the header in `include`.
+
+The C file is compiled by the build script of this crate, so it is not a part of the `lt-fm-index` library.
+*/
+#[cfg(test)]
+mod tests {
+    use std::ffi::{CString, c_char, c_int};
+
+    // Links the `ltfm_*` functions called from C
+    use lt_fm_index as _;
+
+    unsafe extern "C" {
+        // In `test_capi.c`, compiled by the build script
+        fn capi_test_main(path: *const c_char) -> c_int;
+    }
+
+    #[test]
+    fn c_test_passes() {
+        let path = std::env::temp_dir().join(format!("lt-fm-index-capi-test-{}", std::process::id()));
+        let c_path = CString::new(path.to_str().unwrap()).unwrap();
+        let result = unsafe { capi_test_main(c_path.as_ptr()) };
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(result, 0);
+    }
+}
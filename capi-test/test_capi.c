@@ -0,0 +1,79 @@
+// Test of the C API, called by `capi_test_main` in `src/lib.rs`.
+#include <stdio.h>
+#include <string.h>
+
+#include "lt_fm_index.h"
+
+#define CHECK(condition) do { \
+    if (!(condition)) { \
+        fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, #condition); \
+        return 1; \
+    } \
+} while (0)
+
+static int test_index(uint32_t position, uint32_t backend, const char *path) {
+    const char *text = "CTCCGTACACCTGTTTCGTATCGGAXXYYZZ";
+    const uint8_t *characters[] = { (const uint8_t *)"Aa", (const uint8_t *)"Cc", (const uint8_t *)"Gg" };
+    const size_t characters_lens[] = { 2, 2, 2 };
+    LtFmIndexHandle *index = NULL;
+    CHECK(ltfm_build((const uint8_t *)text, strlen(text), characters, characters_lens, 3,
+                     position, backend, 2, 4, &index) == LT_FM_STATUS_OK);
+    CHECK(index != NULL);
+
+    uint64_t text_len = 0;
+    CHECK(ltfm_len_text(index, &text_len) == LT_FM_STATUS_OK);
+    CHECK(text_len == strlen(text));
+
+    uint64_t count = 0;
+    CHECK(ltfm_count(index, (const uint8_t *)"TA", 2, &count) == LT_FM_STATUS_OK);
+    CHECK(count == 2);
+
+    uint64_t locations[2] = { 0 };
+    size_t located_count = 0;
+    CHECK(ltfm_locate(index, (const uint8_t *)"TA", 2, locations, 2, &located_count) == LT_FM_STATUS_OK);
+    CHECK(located_count == 2 && locations[0] == 5 && locations[1] == 18);
+    // Buffer shorter than the locations
+    CHECK(ltfm_locate(index, (const uint8_t *)"TA", 2, locations, 1, &located_count) == LT_FM_STATUS_BUFFER_TOO_SMALL);
+    CHECK(located_count == 2 && locations[0] == 5);
+    CHECK(ltfm_locate(index, (const uint8_t *)"TA", 2, NULL, 0, &located_count) == LT_FM_STATUS_BUFFER_TOO_SMALL);
+    CHECK(located_count == 2);
+
+    // Save and load
+    CHECK(ltfm_save(index, path) == LT_FM_STATUS_OK);
+    LtFmIndexHandle *loaded = NULL;
+    CHECK(ltfm_load(path, &loaded) == LT_FM_STATUS_OK);
+    CHECK(ltfm_count(loaded, (const uint8_t *)"UNDEF", 5, &count) == LT_FM_STATUS_OK);
+    CHECK(count == 2);
+    ltfm_free(loaded);
+    ltfm_free(index);
+    return 0;
+}
+
+int capi_test_main(const char *path) {
+    const uint32_t positions[] = { LTFM_POSITION_U32, LTFM_POSITION_U64 };
+    const uint32_t backends[] = { LTFM_BLOCK2, LTFM_BLOCK3, LTFM_BLOCK4, LTFM_BLOCK5, LTFM_BLOCK6, LTFM_WAVELET_TREE };
+    for (size_t i = 0; i < 2; i++) {
+        for (size_t j = 0; j < 6; j++) {
+            if (test_index(positions[i], backends[j], path) != 0) {
+                return 1;
+            }
+        }
+    }
+
+    // Errors
+    LtFmIndexHandle *index = NULL;
+    const uint8_t *characters[] = { (const uint8_t *)"A" };
+    const size_t characters_lens[] = { 1 };
+    CHECK(ltfm_build((const uint8_t *)"AAA", 3, characters, characters_lens, 1,
+                     16, LTFM_BLOCK2, 2, 4, &index) == LT_FM_STATUS_INVALID_TYPE);
+    CHECK(ltfm_build((const uint8_t *)"AAA", 3, characters, characters_lens, 1,
+                     LTFM_POSITION_U32, LTFM_BLOCK2, 0, 4, &index) == LT_FM_STATUS_BUILD_FAILED);
+    CHECK(ltfm_build(NULL, 3, characters, characters_lens, 1,
+                     LTFM_POSITION_U32, LTFM_BLOCK2, 2, 4, &index) == LT_FM_STATUS_NULL_POINTER);
+    CHECK(index == NULL);
+    CHECK(ltfm_load("/nonexistent/lt_fm_index", &index) == LT_FM_STATUS_IO_FAILED);
+    uint64_t count = 0;
+    CHECK(ltfm_count(NULL, (const uint8_t *)"A", 1, &count) == LT_FM_STATUS_NULL_POINTER);
+    ltfm_free(NULL);
+    return 0;
+}
@@ -0,0 +1,136 @@
+#ifndef LT_FM_INDEX_H
+#define LT_FM_INDEX_H
+
+/* Generated by cbindgen from src/algorithm/capi. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Position of `u32`.
+#define LTFM_POSITION_U32 32
+
+// Position of `u64`.
+#define LTFM_POSITION_U64 64
+
+// Backend of [BackendType::Block2], indexing 3 characters.
+#define LTFM_BLOCK2 2
+
+// Backend of [BackendType::Block3], indexing 7 characters.
+#define LTFM_BLOCK3 3
+
+// Backend of [BackendType::Block4], indexing 15 characters.
+#define LTFM_BLOCK4 4
+
+// Backend of [BackendType::Block5], indexing 31 characters.
+#define LTFM_BLOCK5 5
+
+// Backend of [BackendType::Block6], indexing 63 characters.
+#define LTFM_BLOCK6 6
+
+// Backend of [BackendType::WaveletTree], indexing 255 characters.
+#define LTFM_WAVELET_TREE 0
+
+// Result of the functions.
+typedef enum LtFmStatus {
+  LT_FM_STATUS_OK = 0,
+  // A required pointer is null.
+  LT_FM_STATUS_NULL_POINTER = 1,
+  // The combination of the position and backend is not supported.
+  LT_FM_STATUS_INVALID_TYPE = 2,
+  // The arguments of the build are invalid.
+  LT_FM_STATUS_BUILD_FAILED = 3,
+  // Saving or loading failed.
+  LT_FM_STATUS_IO_FAILED = 4,
+  // The buffer is shorter than the count of the locations.
+  LT_FM_STATUS_BUFFER_TOO_SMALL = 5,
+  // The function panicked.
+  LT_FM_STATUS_PANICKED = 6,
+} LtFmStatus;
+
+// Opaque handle of the index.
+typedef struct LtFmIndexHandle LtFmIndexHandle;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Builds the index, and writes the handle to `out_index`.
+//
+// The characters of each index are given as `characters_count` arrays
+// (`characters[i]` of the length `characters_lens[i]`), as `characters_by_index` of [LtFmIndex::build].
+// The handle must be freed by [ltfm_free].
+//
+// # Safety
+// The pointers must be valid for the given lengths, and `out_index` must be valid for writing.
+enum LtFmStatus ltfm_build(const uint8_t *text,
+                           size_t text_len,
+                           const uint8_t *const *characters,
+                           const size_t *characters_lens,
+                           size_t characters_count,
+                           uint32_t position,
+                           uint32_t backend,
+                           uint64_t suffix_array_sampling_ratio,
+                           uint32_t lookup_table_kmer_size,
+                           struct LtFmIndexHandle **out_index);
+
+// Saves the index to the file of the path, as [AnyLtFmIndex::save_to].
+//
+// # Safety
+// `index` must be a handle from this API, and `path` must be a nul-terminated UTF-8 string.
+enum LtFmStatus ltfm_save(const struct LtFmIndexHandle *index, const char *path);
+
+// Loads the index saved by [ltfm_save] (or [AnyLtFmIndex::save_to]), and writes the handle to `out_index`.
+//
+// The handle must be freed by [ltfm_free].
+//
+// # Safety
+// `path` must be a nul-terminated UTF-8 string, and `out_index` must be valid for writing.
+enum LtFmStatus ltfm_load(const char *path,
+                          struct LtFmIndexHandle **out_index);
+
+// Frees the index. Null is ignored.
+//
+// # Safety
+// `index` must be a handle from this API, not freed before.
+void ltfm_free(struct LtFmIndexHandle *index);
+
+// Writes the length of the indexed text to `out_len`.
+//
+// # Safety
+// `index` must be a handle from this API, and `out_len` must be valid for writing.
+enum LtFmStatus ltfm_len_text(const struct LtFmIndexHandle *index, uint64_t *out_len);
+
+// Counts the occurrences of the pattern, and writes it to `out_count`.
+//
+// # Safety
+// `index` must be a handle from this API, `pattern` must be valid for `pattern_len` bytes,
+// and `out_count` must be valid for writing.
+enum LtFmStatus ltfm_count(const struct LtFmIndexHandle *index,
+                           const uint8_t *pattern,
+                           size_t pattern_len,
+                           uint64_t *out_count);
+
+// Locates the occurrences of the pattern into the caller-provided buffer, in ascending order.
+//
+// The count of the occurrences is written to `out_count`.
+// If it is larger than `locations_capacity`, the buffer is filled with the first locations
+// and [LtFmStatus::BufferTooSmall] is returned.
+//
+// # Safety
+// `index` must be a handle from this API, `pattern` must be valid for `pattern_len` bytes,
+// `locations` must be valid for writing `locations_capacity` values, and `out_count` must be valid for writing.
+enum LtFmStatus ltfm_locate(const struct LtFmIndexHandle *index,
+                            const uint8_t *pattern,
+                            size_t pattern_len,
+                            uint64_t *locations,
+                            size_t locations_capacity,
+                            size_t *out_count);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* LT_FM_INDEX_H */
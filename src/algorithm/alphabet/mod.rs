@@ -0,0 +1,127 @@
+use std::ops::Deref;
+
+use crate::core::errors::BuildError;
+use super::ChrIdxTable;
+
+/// Groups of the characters indexed as the same character, in order of their indices.
+///
+/// The characters of each group are sorted, so the [representative](Self::representative) is the smallest one
+/// (e.g. `A` of `Aa`).
+/// The alphabet is accepted by [crate::LtFmIndex::build] in place of `characters_by_index`,
+/// and recovered from the index by [crate::LtFmIndex::alphabet].
+///
+/// # Example
+/// ```rust
+/// use lt_fm_index::{LtFmIndex, Alphabet};
+/// use lt_fm_index::blocks::Block3;
+///
+/// let alphabet = Alphabet::dna();
+/// let lt_fm_index = LtFmIndex::<u32, Block3<u64>>::build(
+///     b"ACGTacgtNN".to_vec(), &alphabet, 2, 3,
+/// ).unwrap();
+/// assert_eq!(lt_fm_index.count(b"ACGT"), 2);
+/// assert_eq!(lt_fm_index.alphabet(), alphabet);
+/// assert_eq!(lt_fm_index.alphabet().representative(2), Some(b'G'));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Alphabet {
+    groups: Vec<Vec<u8>>,
+}
+
+impl Alphabet {
+    /// Makes an alphabet from the groups of the characters, in order of their indices.
+    pub fn new<T: AsRef<[u8]>>(characters_by_index: &[T]) -> Result<Self, BuildError> {
+        ChrIdxTable::check_characters(characters_by_index)?;
+        let groups = characters_by_index.iter().map(|chrs| {
+            let mut group = chrs.as_ref().to_vec();
+            group.sort_unstable();
+            group
+        }).collect();
+        Ok(Self { groups })
+    }
+    /// Nucleotides `ACGT`.
+    pub fn dna() -> Self {
+        Self::case_insensitive(&[b"A", b"C", b"G", b"T"])
+    }
+    /// Nucleotides `ACGT` and the unknown base `N`.
+    pub fn dna_n() -> Self {
+        Self::case_insensitive(&[b"A", b"C", b"G", b"T", b"N"])
+    }
+    /// Nucleotides `ACGU`.
+    pub fn rna() -> Self {
+        Self::case_insensitive(&[b"A", b"C", b"G", b"U"])
+    }
+    /// Nucleotides and the IUPAC degenerate codes, with `T` and `U` as the same.
+    pub fn iupac() -> Self {
+        Self::case_insensitive(&[
+            b"A", b"C", b"G", b"TU", b"R", b"Y", b"S", b"W", b"K", b"M", b"B", b"D", b"H", b"V", b"N",
+        ])
+    }
+    /// The 20 standard amino acids.
+    pub fn protein() -> Self {
+        Self::case_insensitive(&[
+            b"A", b"C", b"D", b"E", b"F", b"G", b"H", b"I", b"K", b"L",
+            b"M", b"N", b"P", b"Q", b"R", b"S", b"T", b"V", b"W", b"Y",
+        ])
+    }
+    /// The amino acids reduced to 10 groups of the similar ones (Murphy et al., 2000).
+    pub fn reduced_protein() -> Self {
+        Self::case_insensitive(&[
+            b"LVIM", b"C", b"A", b"G", b"ST", b"P", b"FYW", b"EDNQ", b"KR", b"H",
+        ])
+    }
+    /// Names of the presets, accepted by [Self::preset].
+    pub const PRESET_NAMES: [&'static str; 6] = ["dna", "dna-n", "rna", "iupac", "protein", "reduced-protein"];
+
+    /// Preset of the name: `dna`, `dna-n`, `rna`, `iupac`, `protein`, or `reduced-protein`, case-insensitive.
+    pub fn preset(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "dna" => Some(Self::dna()),
+            "dna-n" => Some(Self::dna_n()),
+            "rna" => Some(Self::rna()),
+            "iupac" => Some(Self::iupac()),
+            "protein" => Some(Self::protein()),
+            "reduced-protein" => Some(Self::reduced_protein()),
+            _ => None,
+        }
+    }
+    fn case_insensitive(uppercase_groups: &[&[u8]]) -> Self {
+        let groups = uppercase_groups.iter().map(|chrs| {
+            chrs.iter().flat_map(|&chr| [chr, chr.to_ascii_lowercase()]).collect::<Vec<u8>>()
+        }).collect::<Vec<_>>();
+        Self::new(&groups).unwrap()
+    }
+
+    /// Groups of the characters, in order of their indices.
+    pub fn groups(&self) -> &[Vec<u8>] {
+        &self.groups
+    }
+    /// The first character of the group of the index.
+    ///
+    /// `None` for the index of the unindexed characters.
+    pub fn representative(&self, chridx: u8) -> Option<u8> {
+        self.groups.get(chridx as usize).and_then(|group| group.first().copied())
+    }
+    /// Count of the characters of the index: the groups, and one for the unindexed characters.
+    pub fn chr_count(&self) -> u32 {
+        self.groups.len() as u32 + 1
+    }
+    // The groups of the index of `chr_count` characters
+    pub(crate) fn from_chr_idx_table(chr_idx_table: &ChrIdxTable, chr_count: u32) -> Self {
+        let mut groups = vec![Vec::new(); chr_count as usize - 1];
+        (0..=u8::MAX).for_each(|chr| {
+            if let Some(group) = groups.get_mut(chr_idx_table.idx_of(chr) as usize) {
+                group.push(chr);
+            }
+        });
+        Self { groups }
+    }
+}
+
+impl Deref for Alphabet {
+    type Target = [Vec<u8>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.groups
+    }
+}
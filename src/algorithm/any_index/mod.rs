@@ -0,0 +1,324 @@
+/*!
+Index with the position and backend chosen at runtime.
+
+[AnyLtFmIndex] is one of the [LtFmIndex] of the position ([PositionType]) and the backend ([BackendType]).
+It is saved with a header of the types, so it is loaded without knowing them in advance.
+The command-line tool and the bindings of the `capi`, `python`, and `wasm` features share it and its file.
+*/
+use std::io::{Read, Write};
+
+use crate::core::{Position, errors::BuildError};
+use super::{LtFmIndex, BwmBackend, BuildOptions, UnindexedCharacters, ComponentSizes};
+use super::bwm::blocks::{Block2, Block3, Block4, Block5, Block6};
+use super::bwm::backends::WaveletTree;
+
+// Header of the saved index, followed by the codes of the position and backend
+const MAGIC: &[u8; 8] = b"LTFMIDX1";
+const HEADER_LEN: usize = 10; // magic(8) + codes(2)
+
+/// Position of [AnyLtFmIndex].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PositionType {
+    U32,
+    U64,
+}
+
+/// Backend of [AnyLtFmIndex]: the blocks of `u64` vectors, or [WaveletTree].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackendType {
+    /// [Block2], indexing 3 characters
+    Block2,
+    /// [Block3], indexing 7 characters
+    Block3,
+    /// [Block4], indexing 15 characters
+    Block4,
+    /// [Block5], indexing 31 characters
+    Block5,
+    /// [Block6], indexing 63 characters
+    Block6,
+    /// [WaveletTree], indexing 255 characters
+    WaveletTree,
+}
+
+macro_rules! define_any_index {
+    ($($variant:ident($position:ty, $backend:ty) = ($position_type:ident, $backend_type:ident)),* $(,)?) => {
+        /// [LtFmIndex] of the position and backend chosen at runtime.
+        ///
+        /// # Example
+        /// ```rust
+        /// use lt_fm_index::{AnyLtFmIndex, PositionType, BackendType, BuildOptions};
+        ///
+        /// let index = AnyLtFmIndex::build(
+        ///     b"ACGTACGT".to_vec(), &[b"A", b"C", b"G", b"T"],
+        ///     PositionType::U64, BackendType::Block3, 2, 3, &BuildOptions::default(),
+        /// ).unwrap();
+        /// let mut buffer = Vec::new();
+        /// index.save_to(&mut buffer).unwrap();
+        /// let loaded = AnyLtFmIndex::load_from(&buffer[..]).unwrap();
+        /// assert_eq!(loaded.backend_type(), BackendType::Block3);
+        /// assert_eq!(loaded.count(b"ACGT"), 2);
+        /// ```
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum AnyLtFmIndex {
+            $($variant(LtFmIndex<$position, $backend>),)*
+        }
+
+        impl AnyLtFmIndex {
+            /// Builds the index of the types.
+            ///
+            /// The other arguments are the same as [LtFmIndex::build_with_options].
+            /// The suffix array sampling ratio must fit in the position.
+            pub fn build<T>(
+                text: Vec<u8>,
+                characters_by_index: &[T],
+                position: PositionType,
+                backend: BackendType,
+                suffix_array_sampling_ratio: u64,
+                lookup_table_kmer_size: u32,
+                options: &BuildOptions,
+            ) -> Result<Self, BuildError>
+            where
+                T: AsRef<[u8]>,
+            {
+                match (position, backend) {
+                    $((PositionType::$position_type, BackendType::$backend_type) => Ok(Self::$variant(
+                        LtFmIndex::build_with_options(
+                            text,
+                            characters_by_index,
+                            <$position>::try_from(suffix_array_sampling_ratio)
+                                .map_err(|_| BuildError::SuffixArraySamplingRatio)?,
+                            lookup_table_kmer_size,
+                            options,
+                        )?,
+                    )),)*
+                }
+            }
+            /// Position of the index.
+            pub fn position_type(&self) -> PositionType {
+                match self {
+                    $(Self::$variant(_) => PositionType::$position_type,)*
+                }
+            }
+            /// Backend of the index.
+            pub fn backend_type(&self) -> BackendType {
+                match self {
+                    $(Self::$variant(_) => BackendType::$backend_type,)*
+                }
+            }
+            fn load_index_from<R: Read>(
+                position: PositionType,
+                backend: BackendType,
+                reader: R,
+            ) -> Result<Self, std::io::Error> {
+                match (position, backend) {
+                    $((PositionType::$position_type, BackendType::$backend_type) => Ok(Self::$variant(
+                        LtFmIndex::load_from(reader)?,
+                    )),)*
+                }
+            }
+            fn erased(&self) -> &dyn ErasedIndex {
+                match self {
+                    $(Self::$variant(index) => index,)*
+                }
+            }
+        }
+
+        $(impl From<LtFmIndex<$position, $backend>> for AnyLtFmIndex {
+            fn from(index: LtFmIndex<$position, $backend>) -> Self {
+                Self::$variant(index)
+            }
+        })*
+    };
+}
+
+define_any_index! {
+    U32Block2(u32, Block2<u64>) = (U32, Block2),
+    U32Block3(u32, Block3<u64>) = (U32, Block3),
+    U32Block4(u32, Block4<u64>) = (U32, Block4),
+    U32Block5(u32, Block5<u64>) = (U32, Block5),
+    U32Block6(u32, Block6<u64>) = (U32, Block6),
+    U32WaveletTree(u32, WaveletTree) = (U32, WaveletTree),
+    U64Block2(u64, Block2<u64>) = (U64, Block2),
+    U64Block3(u64, Block3<u64>) = (U64, Block3),
+    U64Block4(u64, Block4<u64>) = (U64, Block4),
+    U64Block5(u64, Block5<u64>) = (U64, Block5),
+    U64Block6(u64, Block6<u64>) = (U64, Block6),
+    U64WaveletTree(u64, WaveletTree) = (U64, WaveletTree),
+}
+
+impl AnyLtFmIndex {
+    /// The largest k-mer size of the lookup table with at most 2^20 entries,
+    /// for the count of the groups in `characters_by_index`.
+    pub fn default_kmer_size(group_count: usize) -> u32 {
+        let base = group_count as u64 + 1; // with the unindexed characters
+        let mut kmer_size = 1;
+        while base.pow(kmer_size + 1) <= 1 << 20 {
+            kmer_size += 1;
+        }
+        kmer_size
+    }
+    /// Counts the number of occurrences of a pattern, as [LtFmIndex::count].
+    pub fn count(&self, pattern: &[u8]) -> u64 {
+        self.erased().count(pattern)
+    }
+    /// Locates all occurrences of a pattern, as [LtFmIndex::locate]. The locations may not be in order.
+    pub fn locate(&self, pattern: &[u8]) -> Vec<u64> {
+        self.erased().locate(pattern)
+    }
+    pub fn len_text(&self) -> u64 {
+        self.erased().len_text()
+    }
+    pub fn index_count(&self) -> u32 {
+        self.erased().index_count()
+    }
+    pub fn unindexed_characters(&self) -> UnindexedCharacters {
+        self.erased().unindexed_characters()
+    }
+    /// The decoding table, as [LtFmIndex::decoding_table].
+    pub fn decoding_table(&self) -> &[u8; 256] {
+        self.erased().decoding_table()
+    }
+    /// Encoded sizes of the components of the index in bytes.
+    pub fn component_sizes(&self) -> ComponentSizes {
+        self.erased().component_sizes()
+    }
+
+    /// Saves the header of the types, followed by the index saved by [LtFmIndex::save_to].
+    pub fn save_to<W: Write>(&self, mut writer: W) -> Result<(), std::io::Error> {
+        // header
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[self.position_type().code(), self.backend_type().code()])?;
+        // index
+        self.erased().save_to(&mut writer)
+    }
+    /// Loads the index saved by [Self::save_to], of the types in the header.
+    pub fn load_from<R: Read>(mut reader: R) -> Result<Self, std::io::Error> {
+        let invalid_data = |message: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, message);
+        let mut header = [0; HEADER_LEN];
+        reader.read_exact(&mut header).map_err(|_| invalid_data("Not an index file"))?;
+        if &header[..MAGIC.len()] != MAGIC {
+            return Err(invalid_data("Not an index file"));
+        }
+        let position = PositionType::from_code(header[8]).ok_or_else(|| invalid_data("Unknown position type"))?;
+        let backend = BackendType::from_code(header[9]).ok_or_else(|| invalid_data("Unknown backend"))?;
+        Self::load_index_from(position, backend, reader)
+    }
+    pub fn encoded_len(&self) -> usize {
+        HEADER_LEN // header
+        + self.erased().encoded_len() // index
+    }
+}
+
+// Queries of the index with the types erased
+trait ErasedIndex {
+    fn count(&self, pattern: &[u8]) -> u64;
+    fn locate(&self, pattern: &[u8]) -> Vec<u64>;
+    fn len_text(&self) -> u64;
+    fn index_count(&self) -> u32;
+    fn unindexed_characters(&self) -> UnindexedCharacters;
+    fn decoding_table(&self) -> &[u8; 256];
+    fn component_sizes(&self) -> ComponentSizes;
+    fn save_to(&self, writer: &mut dyn Write) -> Result<(), std::io::Error>;
+    fn encoded_len(&self) -> usize;
+}
+
+impl<P: Position, B: BwmBackend<P>> ErasedIndex for LtFmIndex<P, B> {
+    fn count(&self, pattern: &[u8]) -> u64 {
+        LtFmIndex::count(self, pattern).as_u64()
+    }
+    fn locate(&self, pattern: &[u8]) -> Vec<u64> {
+        LtFmIndex::locate(self, pattern).into_iter().map(Position::as_u64).collect()
+    }
+    fn len_text(&self) -> u64 {
+        LtFmIndex::len_text(self).as_u64()
+    }
+    fn index_count(&self) -> u32 {
+        LtFmIndex::index_count(self)
+    }
+    fn unindexed_characters(&self) -> UnindexedCharacters {
+        LtFmIndex::unindexed_characters(self)
+    }
+    fn decoding_table(&self) -> &[u8; 256] {
+        LtFmIndex::decoding_table(self)
+    }
+    fn component_sizes(&self) -> ComponentSizes {
+        LtFmIndex::component_sizes(self)
+    }
+    fn save_to(&self, writer: &mut dyn Write) -> Result<(), std::io::Error> {
+        LtFmIndex::save_to(self, writer)
+    }
+    fn encoded_len(&self) -> usize {
+        LtFmIndex::encoded_len(self)
+    }
+}
+
+impl PositionType {
+    /// The type of the name (`"u32"` or `"u64"`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Self::U32, Self::U64].into_iter().find(|position| position.name() == name)
+    }
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+        }
+    }
+    /// Code of the type in the header (32 or 64).
+    pub fn code(self) -> u8 {
+        match self {
+            Self::U32 => 32,
+            Self::U64 => 64,
+        }
+    }
+    pub fn from_code(code: u8) -> Option<Self> {
+        [Self::U32, Self::U64].into_iter().find(|position| position.code() == code)
+    }
+    /// The smaller type that can index the text of the length.
+    pub fn fitting(text_len: u64) -> Self {
+        if text_len <= u32::MAX as u64 { Self::U32 } else { Self::U64 }
+    }
+}
+
+impl BackendType {
+    const ALL: [Self; 6] = [Self::Block2, Self::Block3, Self::Block4, Self::Block5, Self::Block6, Self::WaveletTree];
+
+    /// The type of the name (`"block2"`-`"block6"` or `"wavelet"`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|backend| backend.name() == name)
+    }
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Block2 => "block2",
+            Self::Block3 => "block3",
+            Self::Block4 => "block4",
+            Self::Block5 => "block5",
+            Self::Block6 => "block6",
+            Self::WaveletTree => "wavelet",
+        }
+    }
+    /// Code of the type in the header (2-6 for the blocks, and 0 for the wavelet tree).
+    pub fn code(self) -> u8 {
+        match self {
+            Self::Block2 => 2,
+            Self::Block3 => 3,
+            Self::Block4 => 4,
+            Self::Block5 => 5,
+            Self::Block6 => 6,
+            Self::WaveletTree => 0,
+        }
+    }
+    pub fn from_code(code: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|backend| backend.code() == code)
+    }
+    /// The smallest block that can index the count of characters, or the wavelet tree.
+    pub fn fitting(chr_count: usize) -> Self {
+        match chr_count {
+            0..=3 => Self::Block2,
+            4..=7 => Self::Block3,
+            8..=15 => Self::Block4,
+            16..=31 => Self::Block5,
+            32..=63 => Self::Block6,
+            _ => Self::WaveletTree,
+        }
+    }
+}
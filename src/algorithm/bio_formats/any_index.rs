@@ -0,0 +1,150 @@
+use crate::core::errors::BuildError;
+use super::{
+    AnyLtFmIndex, PositionType, BackendType, BuildOptions,
+    ContigMap, Contig, Coordinate, FastaReader, FastaRecord,
+};
+
+/// [MultiSequenceIndex](super::MultiSequenceIndex) over [AnyLtFmIndex], of the position and backend chosen at runtime.
+///
+/// It is saved as [AnyLtFmIndex] followed by the names and lengths of the sequences,
+/// so [AnyLtFmIndex::load_from] also loads the index of the concatenated sequences from its file.
+#[derive(Clone, PartialEq, Eq)]
+pub struct AnyMultiSequenceIndex {
+    index: AnyLtFmIndex,
+    contig_map: ContigMap,
+}
+
+impl AnyMultiSequenceIndex {
+    /// Builds the index of the types from the FASTA file.
+    ///
+    /// The arguments other than the types are the same as [MultiSequenceIndex::build_from_fasta](super::MultiSequenceIndex::build_from_fasta).
+    #[allow(clippy::too_many_arguments)]
+    pub fn build_from_fasta<R, T>(
+        reader: R,
+        characters_by_index: &[T],
+        position: PositionType,
+        backend: BackendType,
+        suffix_array_sampling_ratio: u64,
+        lookup_table_kmer_size: u32,
+        options: &BuildOptions,
+    ) -> Result<Self, BuildError>
+    where
+        R: std::io::BufRead,
+        T: AsRef<[u8]>,
+    {
+        Self::build_from_fallible_records(
+            FastaReader::new(reader),
+            characters_by_index,
+            position,
+            backend,
+            suffix_array_sampling_ratio,
+            lookup_table_kmer_size,
+            options,
+        )
+    }
+    /// Builds the index of the types from the sequences.
+    ///
+    /// The arguments other than the types are the same as [MultiSequenceIndex::build_from_records](super::MultiSequenceIndex::build_from_records).
+    #[allow(clippy::too_many_arguments)]
+    pub fn build_from_records<I, T>(
+        records: I,
+        characters_by_index: &[T],
+        position: PositionType,
+        backend: BackendType,
+        suffix_array_sampling_ratio: u64,
+        lookup_table_kmer_size: u32,
+        options: &BuildOptions,
+    ) -> Result<Self, BuildError>
+    where
+        I: IntoIterator<Item = FastaRecord>,
+        T: AsRef<[u8]>,
+    {
+        Self::build_from_fallible_records(
+            records.into_iter().map(Ok),
+            characters_by_index,
+            position,
+            backend,
+            suffix_array_sampling_ratio,
+            lookup_table_kmer_size,
+            options,
+        )
+    }
+    fn build_from_fallible_records<I, T>(
+        records: I,
+        characters_by_index: &[T],
+        position: PositionType,
+        backend: BackendType,
+        suffix_array_sampling_ratio: u64,
+        lookup_table_kmer_size: u32,
+        options: &BuildOptions,
+    ) -> Result<Self, BuildError>
+    where
+        I: Iterator<Item = Result<FastaRecord, std::io::Error>>,
+        T: AsRef<[u8]>,
+    {
+        let (text, contigs) = ContigMap::concatenate(records, characters_by_index, options.unindexed_characters)?;
+        let index = AnyLtFmIndex::build(
+            text,
+            characters_by_index,
+            position,
+            backend,
+            suffix_array_sampling_ratio,
+            lookup_table_kmer_size,
+            options,
+        )?;
+        let contig_map = ContigMap::new(contigs, index.len_text());
+        Ok(Self { index, contig_map })
+    }
+
+    /// Counts the number of occurrences of a pattern in the sequences, as [MultiSequenceIndex::count](super::MultiSequenceIndex::count).
+    pub fn count(&self, pattern: &[u8]) -> u64 {
+        self.contig_map.count(
+            pattern.len(),
+            || self.index.count(pattern),
+            || self.index.locate(pattern),
+        )
+    }
+    /// Locates all occurrences of a pattern, as the name of the sequence and the offset in it.
+    ///
+    /// The locations are not in order.
+    pub fn locate_in_contigs(&self, pattern: &[u8], coordinate: Coordinate) -> Vec<(&str, u64)> {
+        self.contig_map.offsets_in_contigs(self.index.locate(pattern), pattern.len(), coordinate)
+    }
+    /// Names and lengths of the sequences, in the order of the FASTA file.
+    pub fn contigs(&self) -> &[Contig] {
+        self.contig_map.contigs()
+    }
+    /// The index of the concatenated sequences.
+    pub fn index(&self) -> &AnyLtFmIndex {
+        &self.index
+    }
+
+    pub fn save_to<W: std::io::Write>(&self, mut writer: W) -> Result<(), std::io::Error> {
+        // index
+        self.index.save_to(&mut writer)?;
+        // contigs
+        self.contig_map.save_to(&mut writer)
+    }
+    pub fn load_from<R: std::io::Read>(mut reader: R) -> Result<Self, std::io::Error> {
+        let index = AnyLtFmIndex::load_from(&mut reader)?;
+        Self::load_contigs_from(index, reader)
+    }
+    // Loads the contigs following the index, which is already loaded from the reader
+    pub(crate) fn load_contigs_from<R: std::io::Read>(index: AnyLtFmIndex, reader: R) -> Result<Self, std::io::Error> {
+        let contig_map = ContigMap::load_from(reader, index.len_text())?;
+        Ok(Self { index, contig_map })
+    }
+    pub fn encoded_len(&self) -> usize {
+        self.index.encoded_len() // index
+        + self.contig_map.encoded_len() // contigs
+    }
+}
+
+impl std::fmt::Debug for AnyMultiSequenceIndex {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AnyMultiSequenceIndex")
+            .field("contig_count", &self.contigs().len())
+            .field("index", &self.index)
+            .finish()
+    }
+}
@@ -0,0 +1,36 @@
+use std::pin::Pin;
+
+use crate::core::Position;
+use super::{MultiSequenceIndex, ContigMap, LtFmIndex, BwmBackend};
+use capwriter::{AsyncSave, AsyncLoad};
+
+impl<P: Position, B: BwmBackend<P>> MultiSequenceIndex<P, B> {
+    pub fn async_save_to<W>(&self, mut writer: Pin<&mut W>) -> impl Future<Output = Result<(), std::io::Error>> + Send where
+        W: tokio::io::AsyncWrite + Send,
+    {
+        async move {
+            // index
+            self.index.async_save_to(writer.as_mut()).await?;
+            // contigs
+            let (name_lens, names, lens) = self.contig_map.encode();
+            name_lens.save_as_ne(writer.as_mut()).await?;
+            names.save_as_ne(writer.as_mut()).await?;
+            lens.save_as_ne(writer.as_mut()).await?;
+            Ok(())
+        }
+    }
+    pub fn async_load_from<R>(mut reader: Pin<&mut R>) -> impl Future<Output = Result<Self, std::io::Error>> + Send where
+        R: tokio::io::AsyncRead + Send,
+        Self: Sized
+    {
+        async move {
+            let index = LtFmIndex::<P, B>::async_load_from(reader.as_mut()).await?;
+            let name_lens = Vec::<u64>::load_as_ne(reader.as_mut()).await?;
+            let names = Vec::<u8>::load_as_ne(reader.as_mut()).await?;
+            let lens = Vec::<u64>::load_as_ne(reader.as_mut()).await?;
+            let contigs = ContigMap::decode(name_lens, names, lens)?;
+            let contig_map = ContigMap::new(contigs, index.len_text().as_u64());
+            Ok(Self { index, contig_map })
+        }
+    }
+}
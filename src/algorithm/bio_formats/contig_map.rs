@@ -0,0 +1,154 @@
+use capwriter::{Save, Load};
+
+use crate::core::errors::BuildError;
+use super::{Contig, Coordinate, FastaRecord, UnindexedCharacters};
+
+// Contigs in the concatenated text of the index, shared by the indexes of any types
+#[derive(Clone, PartialEq, Eq)]
+pub(super) struct ContigMap {
+    contigs: Vec<Contig>,
+    // End of each contig in the concatenated text
+    contig_ends: Vec<u64>,
+    // No occurrence is across the contigs: they are separated by the separators, or there is only one
+    is_separated: bool,
+}
+
+impl ContigMap {
+    // Concatenates the sequences into the text to index, with the separators between them
+    pub(super) fn concatenate<I, T>(
+        records: I,
+        characters_by_index: &[T],
+        unindexed_characters: UnindexedCharacters,
+    ) -> Result<(Vec<u8>, Vec<Contig>), BuildError>
+    where
+        I: Iterator<Item = Result<FastaRecord, std::io::Error>>,
+        T: AsRef<[u8]>,
+    {
+        // A character out of `characters_by_index`, separating the contigs
+        let separator = match unindexed_characters {
+            UnindexedCharacters::Separator => (0..=u8::MAX).find(|chr| {
+                characters_by_index.iter().all(|characters| !characters.as_ref().contains(chr))
+            }),
+            UnindexedCharacters::Same => None,
+        };
+        let mut text = Vec::new();
+        let mut contigs = Vec::new();
+        for record in records {
+            let record = record?;
+            if let (Some(separator), false) = (separator, contigs.is_empty()) {
+                text.push(separator);
+            }
+            text.extend_from_slice(&record.sequence);
+            contigs.push(Contig {
+                name: record.name,
+                len: record.sequence.len() as u64,
+            });
+        }
+        Ok((text, contigs))
+    }
+    pub(super) fn new(contigs: Vec<Contig>, text_len: u64) -> Self {
+        // The text is longer than the contigs, by the separators between them
+        let has_separators = text_len > contigs.iter().map(|contig| contig.len).sum::<u64>();
+        let separator_len = if has_separators { 1 } else { 0 };
+        let contig_ends = contigs.iter().enumerate().scan(0, |end, (contig_idx, contig)| {
+            if contig_idx != 0 {
+                *end += separator_len;
+            }
+            *end += contig.len;
+            Some(*end)
+        }).collect();
+        let contigs_len = contigs.len();
+        Self {
+            contigs,
+            contig_ends,
+            is_separated: has_separators || contigs_len <= 1,
+        }
+    }
+    pub(super) fn contigs(&self) -> &[Contig] {
+        &self.contigs
+    }
+
+    // Count in the contigs, from the count or the locations in the index
+    pub(super) fn count(
+        &self,
+        pattern_len: usize,
+        count_in_index: impl FnOnce() -> u64,
+        locate_in_index: impl FnOnce() -> Vec<u64>,
+    ) -> u64 {
+        if pattern_len == 0 {
+            self.contigs.iter().map(|contig| contig.len).sum()
+        } else if self.is_separated {
+            count_in_index()
+        } else {
+            self.locate_in_text(locate_in_index(), pattern_len).count() as u64
+        }
+    }
+    pub(super) fn offsets_in_contigs(
+        &self,
+        locations: Vec<u64>,
+        pattern_len: usize,
+        coordinate: Coordinate,
+    ) -> Vec<(&str, u64)> {
+        let base = match coordinate {
+            Coordinate::ZeroBased => 0,
+            Coordinate::OneBased => 1,
+        };
+        self.locate_in_text(locations, pattern_len).map(|(contig_idx, location)| {
+            let contig_start = self.contig_ends[contig_idx] - self.contigs[contig_idx].len;
+            (self.contigs[contig_idx].name.as_str(), location - contig_start + base)
+        }).collect()
+    }
+    // Locations in the concatenated text in a contig, with the index of the contig
+    fn locate_in_text(&self, locations: Vec<u64>, pattern_len: usize) -> impl Iterator<Item = (usize, u64)> + '_ {
+        locations.into_iter().filter_map(move |location| {
+            let contig_idx = self.contig_ends.partition_point(|&end| end <= location);
+            // Not at the separator before the contig, and ending in the contig
+            let is_in_contig = self.contig_ends.get(contig_idx).is_some_and(|&end| {
+                end - self.contigs[contig_idx].len <= location && location + pattern_len as u64 <= end
+            });
+            is_in_contig.then_some((contig_idx, location))
+        })
+    }
+
+    pub(super) fn save_to<W: std::io::Write>(&self, mut writer: W) -> Result<(), std::io::Error> {
+        let (name_lens, names, lens) = self.encode();
+        name_lens.save_as_ne(&mut writer)?;
+        names.save_as_ne(&mut writer)?;
+        lens.save_as_ne(&mut writer)?;
+        Ok(())
+    }
+    pub(super) fn load_from<R: std::io::Read>(mut reader: R, text_len: u64) -> Result<Self, std::io::Error> {
+        let name_lens = Vec::<u64>::load_as_ne(&mut reader)?;
+        let names = Vec::<u8>::load_as_ne(&mut reader)?;
+        let lens = Vec::<u64>::load_as_ne(&mut reader)?;
+        Ok(Self::new(Self::decode(name_lens, names, lens)?, text_len))
+    }
+    pub(super) fn encoded_len(&self) -> usize {
+        let (name_lens, names, lens) = self.encode();
+        name_lens.encoded_len() + names.encoded_len() + lens.encoded_len()
+    }
+    // Lengths of the names, concatenated names, and lengths of the sequences
+    pub(super) fn encode(&self) -> (Vec<u64>, Vec<u8>, Vec<u64>) {
+        let name_lens = self.contigs.iter().map(|contig| contig.name.len() as u64).collect();
+        let names = self.contigs.iter().flat_map(|contig| contig.name.bytes()).collect();
+        let lens = self.contigs.iter().map(|contig| contig.len).collect();
+        (name_lens, names, lens)
+    }
+    pub(super) fn decode(
+        name_lens: Vec<u64>,
+        names: Vec<u8>,
+        lens: Vec<u64>,
+    ) -> Result<Vec<Contig>, std::io::Error> {
+        let invalid_data = || std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid contigs");
+        if name_lens.len() != lens.len() || name_lens.iter().sum::<u64>() != names.len() as u64 {
+            return Err(invalid_data());
+        }
+        let mut start = 0;
+        name_lens.into_iter().zip(lens).map(|(name_len, len)| {
+            let end = start + name_len as usize;
+            let name = String::from_utf8(names[start..end].to_vec()).map_err(|_| invalid_data())?;
+            start = end;
+            Ok(Contig { name, len })
+        }).collect()
+    }
+}
@@ -0,0 +1,86 @@
+use std::io::{BufRead, Error, ErrorKind};
+
+/// A sequence of the FASTA file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FastaRecord {
+    /// The first word of the header
+    pub name: String,
+    pub sequence: Vec<u8>,
+}
+
+/// Iterator over the records of the FASTA file.
+///
+/// The lines of each sequence are joined, and the empty lines are skipped.
+/// The text before the first header is [ErrorKind::InvalidData].
+pub struct FastaReader<R: BufRead> {
+    reader: R,
+    line: Vec<u8>,
+    // Name of the record whose header has been read
+    next_name: Option<String>,
+    is_started: bool,
+}
+
+impl<R: BufRead> FastaReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: Vec::new(),
+            next_name: None,
+            is_started: false,
+        }
+    }
+    fn read_line(&mut self) -> Result<bool, Error> {
+        read_line(&mut self.reader, &mut self.line)
+    }
+    fn read_record(&mut self) -> Result<Option<FastaRecord>, Error> {
+        if !self.is_started {
+            self.is_started = true;
+            while self.read_line()? {
+                if let Some(name) = name_of_header(&self.line, b'>') {
+                    self.next_name = Some(name);
+                    break;
+                } else if !self.line.iter().all(u8::is_ascii_whitespace) {
+                    return Err(Error::new(ErrorKind::InvalidData, "FASTA sequence without header"));
+                }
+            }
+        }
+        let Some(name) = self.next_name.take() else {
+            return Ok(None);
+        };
+        let mut sequence = Vec::new();
+        while self.read_line()? {
+            if let Some(next_name) = name_of_header(&self.line, b'>') {
+                self.next_name = Some(next_name);
+                break;
+            }
+            sequence.extend(self.line.iter().filter(|chr| !chr.is_ascii_whitespace()));
+        }
+        Ok(Some(FastaRecord { name, sequence }))
+    }
+}
+
+impl<R: BufRead> Iterator for FastaReader<R> {
+    type Item = Result<FastaRecord, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
+
+// First word after the marker of the header
+pub(super) fn name_of_header(line: &[u8], marker: u8) -> Option<String> {
+    let header = line.strip_prefix(&[marker])?;
+    let name = header.split(u8::is_ascii_whitespace).next().unwrap_or_default();
+    Some(String::from_utf8_lossy(name).into_owned())
+}
+// Reads the next line without the line ending. `false` at the end of the file.
+pub(super) fn read_line<R: BufRead>(reader: &mut R, line: &mut Vec<u8>) -> Result<bool, Error> {
+    line.clear();
+    if reader.read_until(b'\n', line)? == 0 {
+        return Ok(false);
+    }
+    while matches!(line.last(), Some(b'\n' | b'\r')) {
+        line.pop();
+    }
+    Ok(true)
+}
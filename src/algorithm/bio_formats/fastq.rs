@@ -0,0 +1,73 @@
+use std::io::{BufRead, Error, ErrorKind};
+
+use super::fasta::{name_of_header, read_line};
+
+/// A read of the FASTQ file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FastqRecord {
+    /// The first word of the header
+    pub name: String,
+    pub sequence: Vec<u8>,
+    pub quality: Vec<u8>,
+}
+
+/// Iterator over the reads of the FASTQ file, for streaming the query reads.
+///
+/// Each read is four lines: the header, the sequence, the separator (`+`), and the quality.
+/// The malformed read is [ErrorKind::InvalidData].
+pub struct FastqReader<R: BufRead> {
+    reader: R,
+    line: Vec<u8>,
+}
+
+impl<R: BufRead> FastqReader<R> {
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: Vec::new(),
+        }
+    }
+    fn read_line(&mut self) -> Result<bool, Error> {
+        read_line(&mut self.reader, &mut self.line)
+    }
+    fn read_required_line(&mut self) -> Result<(), Error> {
+        if self.read_line()? {
+            Ok(())
+        } else {
+            Err(Error::new(ErrorKind::InvalidData, "FASTQ read is truncated"))
+        }
+    }
+    fn read_record(&mut self) -> Result<Option<FastqRecord>, Error> {
+        // Header, skipping the empty lines
+        loop {
+            if !self.read_line()? {
+                return Ok(None);
+            } else if !self.line.is_empty() {
+                break;
+            }
+        }
+        let name = name_of_header(&self.line, b'@').ok_or_else(|| {
+            Error::new(ErrorKind::InvalidData, "FASTQ header must start with '@'")
+        })?;
+        self.read_required_line()?;
+        let sequence = self.line.clone();
+        self.read_required_line()?;
+        if !self.line.starts_with(b"+") {
+            return Err(Error::new(ErrorKind::InvalidData, "FASTQ separator must start with '+'"));
+        }
+        self.read_required_line()?;
+        if self.line.len() != sequence.len() {
+            return Err(Error::new(ErrorKind::InvalidData, "FASTQ quality must be as long as the sequence"));
+        }
+        let quality = self.line.clone();
+        Ok(Some(FastqRecord { name, sequence, quality }))
+    }
+}
+
+impl<R: BufRead> Iterator for FastqReader<R> {
+    type Item = Result<FastqRecord, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_record().transpose()
+    }
+}
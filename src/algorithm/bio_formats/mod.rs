@@ -0,0 +1,228 @@
+/*!
+Indexing the FASTA files and reading the FASTQ files (`bio-formats` feature).
+
+[MultiSequenceIndex] indexes the sequences of a FASTA file, and locates the pattern
+with the name of the sequence (contig) and the offset in it.
+[AnyMultiSequenceIndex] is the same over [AnyLtFmIndex], of the position and backend chosen at runtime.
+[FastqReader] streams the query reads from a FASTQ file.
+*/
+use crate::core::{
+    Position,
+    errors::{BuildError, QueryError},
+};
+use super::{
+    LtFmIndex, AnyLtFmIndex, PositionType, BackendType, BwmBackend,
+    BuildOptions, EncodedPattern, UnindexedCharacters,
+};
+
+mod fasta;
+pub use fasta::{FastaReader, FastaRecord};
+mod fastq;
+pub use fastq::{FastqReader, FastqRecord};
+
+/// FM-index of the multiple sequences, locating the pattern with the name of the sequence.
+///
+/// The sequences are concatenated into one [LtFmIndex],
+/// and the occurrences across the boundaries of the sequences are excluded.
+/// With [UnindexedCharacters::Separator], an unindexed character is inserted between the sequences,
+/// so no pattern matches across them and [Self::count] is the count of the index.
+/// Otherwise, [Self::count] locates the occurrences to exclude the ones across the boundaries.
+/// The names and lengths of the sequences are saved with the index.
+///
+/// # Example
+/// ```rust
+/// use lt_fm_index::BuildOptions;
+/// use lt_fm_index::bio_formats::{MultiSequenceIndex, Coordinate};
+/// use lt_fm_index::blocks::Block3;
+///
+/// let fasta = b">chr1 first\nACGTAC\nGTAC\n>chr2\nTTACGT\n";
+/// let index = MultiSequenceIndex::<u32, Block3<u64>>::build_from_fasta(
+///     &fasta[..], &[b"A", b"C", b"G", b"T"], 2, 3, &BuildOptions::default(),
+/// ).unwrap();
+/// let mut locations = index.locate_in_contigs(b"ACGT", Coordinate::OneBased);
+/// locations.sort();
+/// assert_eq!(locations, vec![("chr1", 1), ("chr1", 5), ("chr2", 3)]);
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct MultiSequenceIndex<P: Position, B: BwmBackend<P>> {
+    index: LtFmIndex<P, B>,
+    contig_map: ContigMap,
+}
+
+/// Name and length of a sequence in [MultiSequenceIndex].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contig {
+    pub name: String,
+    pub len: u64,
+}
+
+/// Base of the offset in the sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Coordinate {
+    /// The first character is at 0.
+    ZeroBased,
+    /// The first character is at 1 (e.g. SAM and VCF).
+    OneBased,
+}
+
+impl<P: Position, B: BwmBackend<P>> MultiSequenceIndex<P, B> {
+    /// Builds the index from the FASTA file.
+    ///
+    /// The name of each sequence is the first word of its header.
+    /// The arguments other than `reader` are the same as [LtFmIndex::build_with_options].
+    pub fn build_from_fasta<R, T>(
+        reader: R,
+        characters_by_index: &[T],
+        suffix_array_sampling_ratio: P,
+        lookup_table_kmer_size: u32,
+        options: &BuildOptions,
+    ) -> Result<Self, BuildError>
+    where
+        R: std::io::BufRead,
+        T: AsRef<[u8]>,
+    {
+        Self::build_from_fallible_records(
+            FastaReader::new(reader),
+            characters_by_index,
+            suffix_array_sampling_ratio,
+            lookup_table_kmer_size,
+            options,
+        )
+    }
+    /// Builds the index from the sequences.
+    ///
+    /// The arguments other than `records` are the same as [LtFmIndex::build_with_options].
+    pub fn build_from_records<I, T>(
+        records: I,
+        characters_by_index: &[T],
+        suffix_array_sampling_ratio: P,
+        lookup_table_kmer_size: u32,
+        options: &BuildOptions,
+    ) -> Result<Self, BuildError>
+    where
+        I: IntoIterator<Item = FastaRecord>,
+        T: AsRef<[u8]>,
+    {
+        Self::build_from_fallible_records(
+            records.into_iter().map(Ok),
+            characters_by_index,
+            suffix_array_sampling_ratio,
+            lookup_table_kmer_size,
+            options,
+        )
+    }
+    fn build_from_fallible_records<I, T>(
+        records: I,
+        characters_by_index: &[T],
+        suffix_array_sampling_ratio: P,
+        lookup_table_kmer_size: u32,
+        options: &BuildOptions,
+    ) -> Result<Self, BuildError>
+    where
+        I: Iterator<Item = Result<FastaRecord, std::io::Error>>,
+        T: AsRef<[u8]>,
+    {
+        let (text, contigs) = ContigMap::concatenate(records, characters_by_index, options.unindexed_characters)?;
+        let index = LtFmIndex::build_with_options(
+            text,
+            characters_by_index,
+            suffix_array_sampling_ratio,
+            lookup_table_kmer_size,
+            options,
+        )?;
+        let contig_map = ContigMap::new(contigs, index.len_text().as_u64());
+        Ok(Self { index, contig_map })
+    }
+
+    /// Counts the number of occurrences of a pattern in the sequences.
+    ///
+    /// If the sequences are not separated (see [MultiSequenceIndex]),
+    /// the occurrences are located to exclude the ones across the sequences, taking the time of [LtFmIndex::locate].
+    pub fn count(&self, pattern: &[u8]) -> P {
+        P::from_u64(self.contig_map.count(
+            pattern.len(),
+            || self.index.count(pattern).as_u64(),
+            || as_u64_vec(self.index.locate(pattern)),
+        ))
+    }
+    /// Locates all occurrences of a pattern, as the name of the sequence and the offset in it.
+    ///
+    /// The locations are not in order.
+    pub fn locate_in_contigs(&self, pattern: &[u8], coordinate: Coordinate) -> Vec<(&str, P)> {
+        self.offsets_in_contigs(self.index.locate(pattern), pattern.len(), coordinate)
+    }
+    /// Counts the number of occurrences of an [EncodedPattern] in the sequences.
+    ///
+    /// The pattern must be encoded with the decoding table of [Self::index],
+    /// otherwise this may panic or return the wrong count.
+    /// [Self::try_count_encoded_pattern] checks the decoding table.
+    pub fn count_encoded_pattern(&self, pattern: &EncodedPattern) -> P {
+        P::from_u64(self.contig_map.count(
+            pattern.len(),
+            || self.index.count_encoded_pattern(pattern).as_u64(),
+            || as_u64_vec(self.index.locate_encoded_pattern(pattern)),
+        ))
+    }
+    /// Locates all occurrences of an [EncodedPattern], as the name of the sequence and the offset in it.
+    ///
+    /// The locations are not in order.
+    /// The pattern must be encoded as [Self::count_encoded_pattern].
+    pub fn locate_encoded_pattern_in_contigs(&self, pattern: &EncodedPattern, coordinate: Coordinate) -> Vec<(&str, P)> {
+        self.offsets_in_contigs(self.index.locate_encoded_pattern(pattern), pattern.len(), coordinate)
+    }
+    /// Checked version of [Self::count_encoded_pattern].
+    ///
+    /// Returns [QueryError::IncompatibleAlphabet] if the pattern is not encoded with the decoding table of [Self::index].
+    pub fn try_count_encoded_pattern(&self, pattern: &EncodedPattern) -> Result<P, QueryError> {
+        pattern.check_encoded_with(self.index.decoding_table())?;
+        Ok(self.count_encoded_pattern(pattern))
+    }
+    /// Checked version of [Self::locate_encoded_pattern_in_contigs].
+    ///
+    /// Returns [QueryError::IncompatibleAlphabet] if the pattern is not encoded with the decoding table of [Self::index].
+    pub fn try_locate_encoded_pattern_in_contigs(
+        &self,
+        pattern: &EncodedPattern,
+        coordinate: Coordinate,
+    ) -> Result<Vec<(&str, P)>, QueryError> {
+        pattern.check_encoded_with(self.index.decoding_table())?;
+        Ok(self.locate_encoded_pattern_in_contigs(pattern, coordinate))
+    }
+    fn offsets_in_contigs(&self, locations: Vec<P>, pattern_len: usize, coordinate: Coordinate) -> Vec<(&str, P)> {
+        self.contig_map.offsets_in_contigs(as_u64_vec(locations), pattern_len, coordinate)
+            .into_iter()
+            .map(|(name, offset)| (name, P::from_u64(offset)))
+            .collect()
+    }
+
+    /// Names and lengths of the sequences, in the order of the FASTA file.
+    pub fn contigs(&self) -> &[Contig] {
+        self.contig_map.contigs()
+    }
+    /// The index of the concatenated sequences.
+    pub fn index(&self) -> &LtFmIndex<P, B> {
+        &self.index
+    }
+}
+
+impl<P: Position, B: BwmBackend<P>> std::fmt::Debug for MultiSequenceIndex<P, B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MultiSequenceIndex")
+            .field("contig_count", &self.contigs().len())
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+fn as_u64_vec<P: Position>(locations: Vec<P>) -> Vec<u64> {
+    locations.into_iter().map(Position::as_u64).collect()
+}
+
+mod contig_map;
+use contig_map::ContigMap;
+mod any_index;
+pub use any_index::AnyMultiSequenceIndex;
+mod serialize;
+#[cfg(feature = "async-io")]
+#[cfg_attr(docsrs, doc(cfg(feature = "async-io")))]
+mod async_serialize;
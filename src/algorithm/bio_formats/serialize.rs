@@ -0,0 +1,26 @@
+use crate::core::Position;
+use super::{MultiSequenceIndex, ContigMap, LtFmIndex, BwmBackend};
+
+impl<P: Position, B: BwmBackend<P>> MultiSequenceIndex<P, B> {
+    pub fn save_to<W>(&self, mut writer: W) -> Result<(), std::io::Error> where
+        W: std::io::Write
+    {
+        // index
+        self.index.save_to(&mut writer)?;
+        // contigs
+        self.contig_map.save_to(&mut writer)?;
+        Ok(())
+    }
+    pub fn load_from<R>(mut reader: R) -> Result<Self, std::io::Error> where
+        R: std::io::Read,
+        Self: Sized
+    {
+        let index = LtFmIndex::<P, B>::load_from(&mut reader)?;
+        let contig_map = ContigMap::load_from(&mut reader, index.len_text().as_u64())?;
+        Ok(Self { index, contig_map })
+    }
+    pub fn encoded_len(&self) -> usize {
+        self.index.encoded_len() // index
+        + self.contig_map.encoded_len() // contigs
+    }
+}
@@ -0,0 +1,32 @@
+use std::pin::Pin;
+
+use crate::core::AsyncSerialize;
+use super::BitVector;
+use capwriter::{AsyncSave, AsyncLoad};
+
+impl AsyncSerialize for BitVector {
+    fn async_save_to<W>(&self, mut writer: Pin<&mut W>) -> impl Future<Output = Result<(), std::io::Error>> + Send where
+        W: tokio::io::AsyncWrite + Send,
+    {
+        async move {
+            // len
+            self.len.save_as_ne(writer.as_mut()).await?;
+            // words
+            self.words.save_as_ne(writer.as_mut()).await?;
+            Ok(())
+        }
+    }
+    fn async_load_from<R>(mut reader: Pin<&mut R>) -> impl Future<Output = Result<Self, std::io::Error>> + Send where
+        R: tokio::io::AsyncRead + Send,
+        Self: Sized,
+    {
+        async move {
+            // len
+            let len = u64::load_as_ne(reader.as_mut()).await?;
+            // words
+            let words = Vec::<u64>::load_as_ne(reader.as_mut()).await?;
+            // rank_samples are not saved
+            Ok(Self::from_words(len, words))
+        }
+    }
+}
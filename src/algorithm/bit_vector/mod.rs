@@ -0,0 +1,102 @@
+// Bit vector supporting constant time rank queries
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct BitVector {
+    len: u64,
+    words: Vec<u64>,
+    // Count of set bits before each group of `WORDS_PER_RANK_SAMPLE` words
+    rank_samples: Vec<u64>,
+}
+
+const WORD_LEN: u64 = 64;
+const WORDS_PER_RANK_SAMPLE: usize = 8;
+
+impl BitVector {
+    // Build
+    pub fn from_bits<I: IntoIterator<Item = bool>>(bits: I) -> Self {
+        let mut len = 0;
+        let mut words: Vec<u64> = Vec::new();
+        bits.into_iter().for_each(|bit| {
+            let offset = len % WORD_LEN;
+            if offset == 0 {
+                words.push(0);
+            }
+            if bit {
+                *words.last_mut().unwrap() |= 1 << offset;
+            }
+            len += 1;
+        });
+        Self::from_words(len, words)
+    }
+    fn from_words(len: u64, words: Vec<u64>) -> Self {
+        let mut rank_samples = Vec::with_capacity(words.len() / WORDS_PER_RANK_SAMPLE + 1);
+        let mut accumed_count = 0;
+        words.chunks(WORDS_PER_RANK_SAMPLE).for_each(|chunk| {
+            rank_samples.push(accumed_count);
+            accumed_count += chunk.iter().map(|word| word.count_ones() as u64).sum::<u64>();
+        });
+        rank_samples.push(accumed_count);
+        Self {
+            len,
+            words,
+            rank_samples,
+        }
+    }
+
+    // Locate
+    #[inline]
+    pub fn get(&self, idx: u64) -> bool {
+        let word = self.words[(idx / WORD_LEN) as usize];
+        (word >> (idx % WORD_LEN)) & 1 == 1
+    }
+    /// Count of set bits in `[0, idx)`.
+    #[inline]
+    pub fn rank1(&self, idx: u64) -> u64 {
+        let word_idx = (idx / WORD_LEN) as usize;
+        let sample_idx = word_idx / WORDS_PER_RANK_SAMPLE;
+        let mut count = self.rank_samples[sample_idx];
+        self.words[sample_idx * WORDS_PER_RANK_SAMPLE..word_idx].iter().for_each(|word| {
+            count += word.count_ones() as u64;
+        });
+        let offset = idx % WORD_LEN;
+        if offset != 0 {
+            let masked = self.words[word_idx] & ((1 << offset) - 1);
+            count += masked.count_ones() as u64;
+        }
+        count
+    }
+    /// Count of unset bits in `[0, idx)`.
+    #[inline]
+    pub fn rank0(&self, idx: u64) -> u64 {
+        idx - self.rank1(idx)
+    }
+}
+
+mod serialize;
+#[cfg(feature = "async-io")]
+mod async_serialize;
+
+#[cfg(test)]
+mod tests {
+    use super::BitVector;
+    use rand::Rng;
+
+    #[test]
+    fn rank_is_same_as_counting() {
+        let mut rng = rand::rng();
+        for len in [0, 1, 63, 64, 65, 511, 512, 513, 2000] {
+            let bits: Vec<bool> = (0..len).map(|_| rng.random_bool(0.3)).collect();
+            let bit_vector = BitVector::from_bits(bits.iter().copied());
+            let mut count = 0;
+            for (idx, &bit) in bits.iter().enumerate() {
+                assert_eq!(bit_vector.rank1(idx as u64), count);
+                assert_eq!(bit_vector.get(idx as u64), bit);
+                if bit {
+                    count += 1;
+                }
+            }
+            assert_eq!(bit_vector.rank1(len), count);
+        }
+    }
+}
@@ -0,0 +1,30 @@
+use crate::core::Serialize;
+use super::BitVector;
+use capwriter::{Save, Load};
+
+impl Serialize for BitVector {
+    fn save_to<W>(&self, writer: &mut W) -> Result<(), std::io::Error> where
+        W: std::io::Write,
+    {
+        // len
+        self.len.save_as_ne(writer)?;
+        // words
+        self.words.save_as_ne(writer)?;
+        Ok(())
+    }
+    fn load_from<R>(reader: &mut R) -> Result<Self, std::io::Error> where
+        R: std::io::Read,
+        Self: Sized,
+    {
+        // len
+        let len = u64::load_as_ne(reader)?;
+        // words
+        let words = Vec::<u64>::load_as_ne(reader)?;
+        // rank_samples are not saved
+        Ok(Self::from_words(len, words))
+    }
+    fn encoded_len(&self) -> usize {
+        8 // len
+        + self.words.encoded_len() // words
+    }
+}
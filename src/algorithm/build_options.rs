@@ -0,0 +1,85 @@
+/// Options for building the index, other than the required arguments of `build`.
+///
+/// # Example
+/// ```rust
+/// use lt_fm_index::{LtFmIndex, BuildOptions, LookupTable};
+/// use lt_fm_index::blocks::Block2;
+///
+/// let options = BuildOptions::new().lookup_table(LookupTable::Sparse);
+/// let lt_fm_index = LtFmIndex::<u32, Block2<u64>>::build_with_options(
+///     b"ACGTACGTAC".to_vec(),
+///     &[b"A", b"C", b"G"],
+///     2,
+///     12,
+///     &options,
+/// ).unwrap();
+/// assert_eq!(lt_fm_index.count(b"GTAC"), 2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildOptions {
+    pub(crate) lookup_table: LookupTable,
+    pub(crate) unindexed_characters: UnindexedCharacters,
+    pub(crate) suffix_array_sampling: SuffixArraySampling,
+}
+
+/// Representation of the k-mer lookup table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LookupTable {
+    /// Stores the count of every possible k-mer.
+    /// The size is `(count of characters + 1)^k`, but the lookup takes constant time.
+    #[default]
+    Dense,
+    /// Stores only the k-mers that occur in the text.
+    /// The size is bounded by the length of the text, so large k can be used
+    /// (e.g. k=12+ for nucleotide, k=4+ for protein). The lookup takes `O(log n)` time.
+    Sparse,
+}
+
+/// Semantics of the characters not in `characters_by_index`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnindexedCharacters {
+    /// All unindexed characters are treated as the same character.
+    /// e.g. `N` in the pattern matches `X`, `Y`, or `!` in the text.
+    #[default]
+    Same,
+    /// Unindexed characters are separators, never matched by any pattern.
+    /// e.g. patterns can not match across the `N` gaps of a genome.
+    Separator,
+}
+
+/// Which suffix array entries are sampled, at the ratio of `suffix_array_sampling_ratio`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SuffixArraySampling {
+    /// Samples every n-th row of the suffix array.
+    /// The samples are found without additional structure, but the count of LF mappings
+    /// to locate an occurrence is not bounded.
+    #[default]
+    Row,
+    /// Samples every n-th position of the text, marking the sampled rows with a bit vector.
+    /// Locating an occurrence takes fewer than n LF mappings.
+    TextPosition,
+}
+
+impl BuildOptions {
+    /// Options with the default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Sets the representation of the k-mer lookup table (default: [LookupTable::Dense]).
+    pub fn lookup_table(mut self, lookup_table: LookupTable) -> Self {
+        self.lookup_table = lookup_table;
+        self
+    }
+    /// Sets the semantics of the unindexed characters (default: [UnindexedCharacters::Same]).
+    pub fn unindexed_characters(mut self, unindexed_characters: UnindexedCharacters) -> Self {
+        self.unindexed_characters = unindexed_characters;
+        self
+    }
+    /// Sets the sampling of the suffix array (default: [SuffixArraySampling::Row]).
+    ///
+    /// Not used by [crate::RlFmIndex], which samples the boundaries of the runs.
+    pub fn suffix_array_sampling(mut self, suffix_array_sampling: SuffixArraySampling) -> Self {
+        self.suffix_array_sampling = suffix_array_sampling;
+        self
+    }
+}
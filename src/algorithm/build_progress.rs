@@ -0,0 +1,128 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use crate::core::errors::BuildError;
+
+/// Phase of the build, in the order of the progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BuildPhase {
+    /// Encoding the text and counting the k-mers of the lookup table.
+    Encoding,
+    /// Sorting the suffixes of the text.
+    SuffixSorting,
+    /// Transforming the text to the BWT, and sampling the suffix array.
+    Bwt,
+    /// Vectorizing the BWT to the structure of the backend.
+    Vectorization,
+}
+
+/// Observer of the progress of [crate::LtFmIndex::build_with_progress].
+///
+/// Both methods do nothing by default.
+pub trait BuildObserver {
+    /// Called with the fraction (from 0.0 to 1.0) of the phase done.
+    fn on_progress(&mut self, phase: BuildPhase, fraction: f64) {
+        let _ = (phase, fraction);
+    }
+    /// Called when the phase is finished, with the time it took.
+    fn on_phase_finished(&mut self, phase: BuildPhase, elapsed: Duration) {
+        let _ = (phase, elapsed);
+    }
+}
+
+impl BuildObserver for () {}
+
+/// Token to cancel the build from the other threads.
+///
+/// The clones share the state, so the build is cancelled by calling [Self::cancel] on any clone.
+/// The build returns [BuildError::Cancelled] at the next check.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// New token, not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+    /// Cancels the builds using this token.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+    /// Whether [Self::cancel] is called.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+}
+
+// The progress is reported at every 1/REPORT_COUNT of the phase
+const REPORT_COUNT: usize = 100;
+
+// Reports the progress to the observer, and checks the cancellation during the build.
+// The monitor without the observer and token only costs a comparison per step.
+pub struct BuildMonitor<'a> {
+    observer: Option<&'a mut dyn BuildObserver>,
+    cancellation: Option<&'a CancellationToken>,
+    phase_started_at: Option<Instant>,
+    // The step of the next report of the phase
+    next_report: usize,
+}
+
+impl<'a> BuildMonitor<'a> {
+    pub fn none() -> Self {
+        Self {
+            observer: None,
+            cancellation: None,
+            phase_started_at: None,
+            next_report: usize::MAX,
+        }
+    }
+    pub fn new(observer: &'a mut dyn BuildObserver, cancellation: &'a CancellationToken) -> Self {
+        Self {
+            observer: Some(observer),
+            cancellation: Some(cancellation),
+            phase_started_at: None,
+            next_report: usize::MAX,
+        }
+    }
+    pub fn start_phase(&mut self, phase: BuildPhase) -> Result<(), BuildError> {
+        self.check_cancelled()?;
+        if let Some(observer) = self.observer.as_mut() {
+            self.phase_started_at = Some(Instant::now());
+            self.next_report = 0;
+            observer.on_progress(phase, 0.0);
+        }
+        Ok(())
+    }
+    // Called with the count of the steps done, in the long loops
+    #[inline]
+    pub fn progress(&mut self, phase: BuildPhase, done: usize, total: usize) -> Result<(), BuildError> {
+        if done < self.next_report {
+            return Ok(());
+        }
+        self.next_report = done + (total / REPORT_COUNT).max(1);
+        self.check_cancelled()?;
+        if let Some(observer) = self.observer.as_mut() {
+            observer.on_progress(phase, done as f64 / total as f64);
+        }
+        Ok(())
+    }
+    pub fn finish_phase(&mut self, phase: BuildPhase) -> Result<(), BuildError> {
+        self.check_cancelled()?;
+        if let Some(observer) = self.observer.as_mut() {
+            observer.on_progress(phase, 1.0);
+            let elapsed = self.phase_started_at.take().map(|started_at| started_at.elapsed()).unwrap_or_default();
+            observer.on_phase_finished(phase, elapsed);
+            self.next_report = usize::MAX;
+        }
+        Ok(())
+    }
+    fn check_cancelled(&self) -> Result<(), BuildError> {
+        match self.cancellation {
+            Some(cancellation) if cancellation.is_cancelled() => Err(BuildError::Cancelled),
+            _ => Ok(()),
+        }
+    }
+}
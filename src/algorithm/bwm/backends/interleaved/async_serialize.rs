@@ -0,0 +1,49 @@
+use std::marker::PhantomData;
+use std::pin::Pin;
+
+use crate::core::{Position, AsyncSerialize};
+use crate::algorithm::Block;
+use super::{InterleavedBwm, CacheLine};
+use bytemuck::Zeroable;
+use capwriter::{AsyncSave, AsyncLoad};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+impl<P: Position, B: Block<P>> AsyncSerialize for InterleavedBwm<P, B> {
+    fn async_save_to<W>(&self, mut writer: Pin<&mut W>) -> impl Future<Output = Result<(), std::io::Error>> + Send where
+        W: tokio::io::AsyncWrite + Send,
+    {
+        async move {
+            // primary_index
+            self.primary_index.as_u64().save_as_ne(writer.as_mut()).await?;
+            // chr_count
+            self.chr_count.save_as_ne(writer.as_mut()).await?;
+            // lines
+            (self.lines.len() as u64).save_as_ne(writer.as_mut()).await?;
+            writer.write_all(bytemuck::cast_slice(&self.lines)).await?;
+            Ok(())
+        }
+    }
+    fn async_load_from<R>(mut reader: Pin<&mut R>) -> impl Future<Output = Result<Self, std::io::Error>> + Send where
+        R: tokio::io::AsyncRead + Send,
+    {
+        async move {
+            // primary_index
+            let primary_index = P::from_u64(u64::load_as_ne(reader.as_mut()).await?);
+            // chr_count
+            let chr_count = u32::load_as_ne(reader.as_mut()).await?;
+            let (checkpoints_offset, record_lines) = Self::record_layout(chr_count);
+            // lines
+            let lines_len = u64::load_as_ne(reader.as_mut()).await? as usize;
+            let mut lines = vec![CacheLine::zeroed(); lines_len];
+            reader.as_mut().read_exact(bytemuck::cast_slice_mut(&mut lines)).await?;
+            Ok(Self {
+                primary_index,
+                chr_count,
+                checkpoints_offset,
+                record_lines,
+                lines,
+                _block: PhantomData,
+            })
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use std::marker::PhantomData;
+
+use bytemuck::{Pod, Zeroable};
+
+use crate::core::{Position, errors::BuildError};
+use crate::algorithm::{Block, BuildMonitor, BuildPhase};
+use super::{BwmBackend, BurrowsWheelerMatrix};
+
+/// Backend storing each block of `B` next to its rank checkpoints in a cache-line-aligned record.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Interleaved<B>(PhantomData<B>);
+
+impl<P: Position, B: Block<P>> BwmBackend<P> for Interleaved<B> {
+    type Bwm = InterleavedBwm<P, B>;
+}
+
+// Burrows-Wheeler Matrix in the records of a block and its rank checkpoints
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct InterleavedBwm<P: Position, B: Block<P>> {
+    primary_index: P,
+    chr_count: u32,
+    // Layout of the record, derived from the chr_count
+    checkpoints_offset: u32,
+    record_lines: u32,
+    lines: Vec<CacheLine>,
+    _block: PhantomData<B>,
+}
+
+const CACHE_LINE_SIZE: usize = 64;
+
+#[repr(C, align(64))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Pod, Zeroable)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+struct CacheLine([u64; 8]);
+
+impl<P: Position, B: Block<P>> BurrowsWheelerMatrix<P> for InterleavedBwm<P, B> {
+    const MAX_CHR: u32 = B::MAX_CHR;
+
+    // Build
+    fn new(bwt_text: Vec<u8>, pidx: P, chr_count: u32, monitor: &mut BuildMonitor) -> Result<Self, BuildError> {
+        monitor.start_phase(BuildPhase::Vectorization)?;
+        let (checkpoints_offset, record_lines) = Self::record_layout(chr_count);
+        let block_len = B::BLOCK_LEN as usize;
+        // The last record has the empty block, if the text is fit to the blocks
+        let record_count = bwt_text.len() / block_len + 1;
+
+        let mut bwm = Self {
+            primary_index: pidx,
+            chr_count,
+            checkpoints_offset,
+            record_lines,
+            lines: vec![CacheLine::zeroed(); record_count * record_lines as usize],
+            _block: PhantomData,
+        };
+        let mut rank_pre_counts = vec![P::ZERO; chr_count as usize];
+        let mut chunks = bwt_text.chunks(block_len);
+
+        for record_idx in 0..record_count {
+            monitor.progress(BuildPhase::Vectorization, record_idx * block_len, bwt_text.len())?;
+            let checkpoints_offset = checkpoints_offset as usize;
+            let record = bwm.record_mut(record_idx);
+            record[checkpoints_offset..checkpoints_offset + rank_pre_counts.len() * size_of::<P>()]
+                .copy_from_slice(bytemuck::cast_slice(&rank_pre_counts));
+            let block = match chunks.next() {
+                Some(text_chunk) => {
+                    let mut block = B::vectorize(text_chunk, &mut rank_pre_counts);
+                    if text_chunk.len() < block_len {
+                        block.shift_last_offset((block_len - text_chunk.len()) as u32);
+                    }
+                    block
+                },
+                None => B::empty(),
+            };
+            record[..size_of::<B>()].copy_from_slice(bytemuck::bytes_of(&block));
+        }
+
+        monitor.finish_phase(BuildPhase::Vectorization)?;
+        Ok(bwm)
+    }
+    // Locate
+    #[inline]
+    fn get_next_rank(&self, mut pos: P, chridx: u8) -> P {
+        if pos < self.primary_index {
+            pos += P::ONE;
+        }
+        let (quot, rem) = B::split_position(pos);
+
+        let record = self.record(quot);
+        let rank_precount = self.checkpoint_of(record, chridx);
+        if rem == 0 {
+            rank_precount
+        } else {
+            let rem_count = Self::block_of(record).get_remain_count_of(rem, chridx);
+            rank_precount + P::from_u32(rem_count)
+        }
+    }
+    #[inline]
+    fn get_next_ranks(&self, pos_range: (P, P), chridx: u8) -> (P, P) {
+        let [(start_quot, start_rem), (end_quot, end_rem)] = [pos_range.0, pos_range.1].map(|mut pos| {
+            if pos < self.primary_index {
+                pos += P::ONE;
+            }
+            B::split_position(pos)
+        });
+        let [start_record, end_record] = [start_quot, end_quot].map(|quot| self.record(quot));
+        let [start_count, end_count] = B::get_remain_counts_of(
+            [Self::block_of(start_record), Self::block_of(end_record)],
+            [start_rem, end_rem],
+            chridx,
+        );
+        (
+            self.checkpoint_of(start_record, chridx) + P::from_u32(start_count),
+            self.checkpoint_of(end_record, chridx) + P::from_u32(end_count),
+        )
+    }
+    #[inline]
+    fn get_pre_rank_and_chridx(&self, mut pos: P) -> Option<(P, u8)> {
+        if pos == self.primary_index - P::ONE {
+            return None;
+        } else if pos < self.primary_index {
+            pos += P::ONE;
+        }
+        let (quot, rem) = B::split_position(pos);
+
+        let record = self.record(quot);
+        let block = Self::block_of(record);
+        let chridx = block.get_chridx_of(rem);
+
+        let rank_precount = self.checkpoint_of(record, chridx);
+        if rem == 0 {
+            Some((rank_precount, chridx))
+        } else {
+            let rem_count = block.get_remain_count_of(rem, chridx);
+            Some((rank_precount + P::from_u32(rem_count), chridx))
+        }
+    }
+
+    fn chr_count(&self) -> u32 {
+        self.chr_count
+    }
+    fn primary_index(&self) -> P {
+        self.primary_index
+    }
+}
+
+impl<P: Position, B: Block<P>> InterleavedBwm<P, B> {
+    // The block is at the start of the record, and the checkpoints follow it.
+    // Returns the offset of the checkpoints and the count of cache lines of a record.
+    fn record_layout(chr_count: u32) -> (u32, u32) {
+        let checkpoints_offset = size_of::<B>().next_multiple_of(align_of::<P>());
+        let record_len = checkpoints_offset + chr_count as usize * size_of::<P>();
+        (checkpoints_offset as u32, record_len.div_ceil(CACHE_LINE_SIZE) as u32)
+    }
+    #[inline]
+    fn record(&self, record_idx: usize) -> &[u8] {
+        let record_lines = self.record_lines as usize;
+        let start = record_idx * record_lines;
+        bytemuck::cast_slice(&self.lines[start..start + record_lines])
+    }
+    fn record_mut(&mut self, record_idx: usize) -> &mut [u8] {
+        let record_lines = self.record_lines as usize;
+        let start = record_idx * record_lines;
+        bytemuck::cast_slice_mut(&mut self.lines[start..start + record_lines])
+    }
+    #[inline]
+    fn block_of(record: &[u8]) -> &B {
+        bytemuck::from_bytes(&record[..size_of::<B>()])
+    }
+    #[inline]
+    fn checkpoint_of(&self, record: &[u8], chridx: u8) -> P {
+        let offset = self.checkpoints_offset as usize + chridx as usize * size_of::<P>();
+        *bytemuck::from_bytes(&record[offset..offset + size_of::<P>()])
+    }
+}
+
+mod serialize;
+#[cfg(feature = "async-io")]
+mod async_serialize;
@@ -0,0 +1,49 @@
+use std::marker::PhantomData;
+
+use crate::core::{Position, Serialize};
+use crate::algorithm::Block;
+use super::{InterleavedBwm, CacheLine};
+use bytemuck::Zeroable;
+use capwriter::{Save, Load};
+
+impl<P: Position, B: Block<P>> Serialize for InterleavedBwm<P, B> {
+    fn save_to<W>(&self, writer: &mut W) -> Result<(), std::io::Error> where
+        W: std::io::Write,
+    {
+        // primary_index
+        self.primary_index.as_u64().save_as_ne(writer)?;
+        // chr_count
+        self.chr_count.save_as_ne(writer)?;
+        // lines
+        (self.lines.len() as u64).save_as_ne(writer)?;
+        writer.write_all(bytemuck::cast_slice(&self.lines))?;
+        Ok(())
+    }
+    fn load_from<R>(reader: &mut R) -> Result<Self, std::io::Error> where
+        R: std::io::Read,
+        Self: Sized,
+    {
+        // primary_index
+        let primary_index = P::from_u64(u64::load_as_ne(reader)?);
+        // chr_count
+        let chr_count = u32::load_as_ne(reader)?;
+        let (checkpoints_offset, record_lines) = Self::record_layout(chr_count);
+        // lines
+        let lines_len = u64::load_as_ne(reader)? as usize;
+        let mut lines = vec![CacheLine::zeroed(); lines_len];
+        reader.read_exact(bytemuck::cast_slice_mut(&mut lines))?;
+        Ok(Self {
+            primary_index,
+            chr_count,
+            checkpoints_offset,
+            record_lines,
+            lines,
+            _block: PhantomData,
+        })
+    }
+    fn encoded_len(&self) -> usize {
+        12 // primary_index(8) + chr_count(4)
+        + 8 // lines_len
+        + size_of_val(self.lines.as_slice()) // lines
+    }
+}
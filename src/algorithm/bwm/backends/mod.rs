@@ -0,0 +1,25 @@
+/*!
+Alternative backends of trait [BwmBackend].
+Every [super::Block] is also a backend, storing the text in bit-sliced blocks with
+a rank checkpoint of every character for each block.
+The backends in this module trade that layout for other strengths.
+
+- [WaveletTree]: Huffman-shaped wavelet tree. The cost of the rank query and the size
+  are proportional to the entropy of the text, instead of the count of the characters.
+  Can index up to 255 characters.
+- [Interleaved]: The same blocks of [super::Block], each stored next to its rank checkpoints
+  in a cache-line-aligned record, so that a rank query touches adjacent cache lines.
+  Records are padded to the cache line, which costs the space for small blocks.
+- [Superblock]: The same blocks of [super::Block], with the absolute rank checkpoints per superblock
+  and the small relative ones every `INTERVAL` blocks. The longer interval takes less memory,
+  but counts more blocks in a rank query.
+*/
+use super::{BwmBackend, BurrowsWheelerMatrix};
+
+mod wavelet_tree;
+mod interleaved;
+mod superblock;
+
+pub use wavelet_tree::WaveletTree;
+pub use interleaved::Interleaved;
+pub use superblock::Superblock;
@@ -0,0 +1,55 @@
+use std::pin::Pin;
+
+use crate::core::{Position, AsyncSerialize};
+use crate::algorithm::Block;
+use super::SuperblockBwm;
+use capwriter::{AsyncSave, AsyncLoad};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+impl<P: Position, B: Block<P>, const INTERVAL: u32> AsyncSerialize for SuperblockBwm<P, B, INTERVAL> {
+    fn async_save_to<W>(&self, mut writer: Pin<&mut W>) -> impl Future<Output = Result<(), std::io::Error>> + Send where
+        W: tokio::io::AsyncWrite + Send,
+    {
+        async move {
+            // primary_index
+            self.primary_index.as_u64().save_as_ne(writer.as_mut()).await?;
+            // chr_count
+            self.chr_count.save_as_ne(writer.as_mut()).await?;
+            // interval
+            INTERVAL.save_as_ne(writer.as_mut()).await?;
+            // checkpoints
+            self.superblock_checkpoints.save_as_ne(writer.as_mut()).await?;
+            self.interval_checkpoints.save_as_ne(writer.as_mut()).await?;
+            // blocks
+            (self.blocks.len() as u64).save_as_ne(writer.as_mut()).await?;
+            writer.write_all(bytemuck::cast_slice(&self.blocks)).await?;
+            Ok(())
+        }
+    }
+    fn async_load_from<R>(mut reader: Pin<&mut R>) -> impl Future<Output = Result<Self, std::io::Error>> + Send where
+        R: tokio::io::AsyncRead + Send,
+    {
+        async move {
+            // primary_index
+            let primary_index = P::from_u64(u64::load_as_ne(reader.as_mut()).await?);
+            // chr_count
+            let chr_count = u32::load_as_ne(reader.as_mut()).await?;
+            // interval
+            Self::check_interval(u32::load_as_ne(reader.as_mut()).await?)?;
+            // checkpoints
+            let superblock_checkpoints = Vec::<P>::load_as_ne(reader.as_mut()).await?;
+            let interval_checkpoints = Vec::<u16>::load_as_ne(reader.as_mut()).await?;
+            // blocks
+            let blocks_len = u64::load_as_ne(reader.as_mut()).await? as usize;
+            let mut blocks = vec![B::zeroed(); blocks_len];
+            reader.as_mut().read_exact(bytemuck::cast_slice_mut(&mut blocks)).await?;
+            Ok(Self {
+                primary_index,
+                chr_count,
+                superblock_checkpoints,
+                interval_checkpoints,
+                blocks,
+            })
+        }
+    }
+}
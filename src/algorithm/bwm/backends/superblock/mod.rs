@@ -0,0 +1,176 @@
+use std::marker::PhantomData;
+
+use crate::core::{Position, errors::BuildError};
+use crate::algorithm::{Block, BuildMonitor, BuildPhase};
+use super::{BwmBackend, BurrowsWheelerMatrix};
+
+/**
+Backend of the blocks of `B` with two-level rank checkpoints.
+The relative checkpoint is taken every `INTERVAL` blocks, and the absolute checkpoint every superblock of up to $2^{16}$ characters.
+*/
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Superblock<B, const INTERVAL: u32>(PhantomData<B>);
+
+impl<P: Position, B: Block<P>, const INTERVAL: u32> BwmBackend<P> for Superblock<B, INTERVAL> {
+    type Bwm = SuperblockBwm<P, B, INTERVAL>;
+}
+
+// Burrows-Wheeler Matrix with the rank checkpoints of the superblocks and the intervals
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct SuperblockBwm<P: Position, B: Block<P>, const INTERVAL: u32> {
+    primary_index: P,
+    chr_count: u32,
+    // Ranks at the start of each superblock
+    superblock_checkpoints: Vec<P>,
+    // Ranks at the start of each interval, relative to its superblock
+    interval_checkpoints: Vec<u16>,
+    blocks: Vec<B>,
+}
+
+impl<P: Position, B: Block<P>, const INTERVAL: u32> BurrowsWheelerMatrix<P> for SuperblockBwm<P, B, INTERVAL> {
+    const MAX_CHR: u32 = B::MAX_CHR;
+
+    // Build
+    fn new(bwt_text: Vec<u8>, pidx: P, chr_count: u32, monitor: &mut BuildMonitor) -> Result<Self, BuildError> {
+        monitor.start_phase(BuildPhase::Vectorization)?;
+        let block_len = B::BLOCK_LEN as usize;
+        // The last block is empty, if the text is fit to the blocks
+        let block_count = bwt_text.len() / block_len + 1;
+        let interval_count = block_count.div_ceil(INTERVAL as usize);
+        let superblock_count = interval_count.div_ceil(Self::SUPERBLOCK_INTERVALS as usize);
+
+        let mut superblock_checkpoints = Vec::with_capacity(superblock_count * chr_count as usize);
+        let mut interval_checkpoints = Vec::with_capacity(interval_count * chr_count as usize);
+        let mut blocks = Vec::with_capacity(block_count);
+        let mut rank_pre_counts = vec![P::ZERO; chr_count as usize];
+        let mut chunks = bwt_text.chunks(block_len);
+
+        for block_idx in 0..block_count {
+            monitor.progress(BuildPhase::Vectorization, block_idx * block_len, bwt_text.len())?;
+            if block_idx % INTERVAL as usize == 0 {
+                if block_idx % Self::SUPERBLOCK_BLOCKS == 0 {
+                    superblock_checkpoints.extend_from_slice(&rank_pre_counts);
+                }
+                let superblock_ranks = &superblock_checkpoints[superblock_checkpoints.len() - chr_count as usize..];
+                interval_checkpoints.extend(rank_pre_counts.iter().zip(superblock_ranks).map(|(&rank, &superblock_rank)| {
+                    (rank - superblock_rank).as_u32() as u16
+                }));
+            }
+            let block = match chunks.next() {
+                Some(text_chunk) => {
+                    let mut block = B::vectorize(text_chunk, &mut rank_pre_counts);
+                    if text_chunk.len() < block_len {
+                        block.shift_last_offset((block_len - text_chunk.len()) as u32);
+                    }
+                    block
+                },
+                None => B::empty(),
+            };
+            blocks.push(block);
+        }
+
+        monitor.finish_phase(BuildPhase::Vectorization)?;
+        Ok(Self {
+            primary_index: pidx,
+            chr_count,
+            superblock_checkpoints,
+            interval_checkpoints,
+            blocks,
+        })
+    }
+    // Locate
+    #[inline]
+    fn get_next_rank(&self, mut pos: P, chridx: u8) -> P {
+        if pos < self.primary_index {
+            pos += P::ONE;
+        }
+        let (quot, rem) = B::split_position(pos);
+
+        let rank_precount = self.rank_precount_of(quot, chridx);
+        if rem == 0 {
+            rank_precount
+        } else {
+            let rem_count = self.blocks[quot].get_remain_count_of(rem, chridx);
+            rank_precount + P::from_u32(rem_count)
+        }
+    }
+    #[inline]
+    fn get_next_ranks(&self, pos_range: (P, P), chridx: u8) -> (P, P) {
+        let [(start_quot, start_rem), (end_quot, end_rem)] = [pos_range.0, pos_range.1].map(|mut pos| {
+            if pos < self.primary_index {
+                pos += P::ONE;
+            }
+            B::split_position(pos)
+        });
+        let [start_count, end_count] = B::get_remain_counts_of(
+            [&self.blocks[start_quot], &self.blocks[end_quot]],
+            [start_rem, end_rem],
+            chridx,
+        );
+        (
+            self.rank_precount_of(start_quot, chridx) + P::from_u32(start_count),
+            self.rank_precount_of(end_quot, chridx) + P::from_u32(end_count),
+        )
+    }
+    #[inline]
+    fn get_pre_rank_and_chridx(&self, mut pos: P) -> Option<(P, u8)> {
+        if pos == self.primary_index - P::ONE {
+            return None;
+        } else if pos < self.primary_index {
+            pos += P::ONE;
+        }
+        let (quot, rem) = B::split_position(pos);
+
+        let block = &self.blocks[quot];
+        let chridx = block.get_chridx_of(rem);
+
+        let rank_precount = self.rank_precount_of(quot, chridx);
+        if rem == 0 {
+            Some((rank_precount, chridx))
+        } else {
+            let rem_count = block.get_remain_count_of(rem, chridx);
+            Some((rank_precount + P::from_u32(rem_count), chridx))
+        }
+    }
+
+    fn chr_count(&self) -> u32 {
+        self.chr_count
+    }
+    fn primary_index(&self) -> P {
+        self.primary_index
+    }
+}
+
+impl<P: Position, B: Block<P>, const INTERVAL: u32> SuperblockBwm<P, B, INTERVAL> {
+    // Count of the intervals in a superblock, keeping the relative ranks in u16
+    const SUPERBLOCK_INTERVALS: u32 = {
+        assert!(
+            INTERVAL != 0 && INTERVAL <= (1 << 16) / B::BLOCK_LEN,
+            "The interval must span from 1 block to 2^16 characters",
+        );
+        (1 << 16) / (INTERVAL * B::BLOCK_LEN)
+    };
+    const SUPERBLOCK_BLOCKS: usize = (Self::SUPERBLOCK_INTERVALS * INTERVAL) as usize;
+
+    // Rank at the start of the block
+    #[inline]
+    fn rank_precount_of(&self, block_idx: usize, chridx: u8) -> P {
+        let chr_count = self.chr_count as usize;
+        let chridx = chridx as usize;
+        let interval_idx = block_idx / INTERVAL as usize;
+        let superblock_idx = interval_idx / Self::SUPERBLOCK_INTERVALS as usize;
+
+        // The blocks between the start of the interval and the block are counted
+        let relative_rank = self.blocks[interval_idx * INTERVAL as usize..block_idx].iter().fold(
+            self.interval_checkpoints[interval_idx * chr_count + chridx] as u32,
+            |count, block| count + block.get_remain_count_of(B::BLOCK_LEN, chridx as u8),
+        );
+        self.superblock_checkpoints[superblock_idx * chr_count + chridx] + P::from_u32(relative_rank)
+    }
+}
+
+mod serialize;
+#[cfg(feature = "async-io")]
+mod async_serialize;
@@ -0,0 +1,70 @@
+use crate::core::{Position, Serialize};
+use crate::algorithm::Block;
+use super::SuperblockBwm;
+use capwriter::{Save, Load};
+
+impl<P: Position, B: Block<P>, const INTERVAL: u32> Serialize for SuperblockBwm<P, B, INTERVAL> {
+    fn save_to<W>(&self, writer: &mut W) -> Result<(), std::io::Error> where
+        W: std::io::Write,
+    {
+        // primary_index
+        self.primary_index.as_u64().save_as_ne(writer)?;
+        // chr_count
+        self.chr_count.save_as_ne(writer)?;
+        // interval
+        INTERVAL.save_as_ne(writer)?;
+        // checkpoints
+        self.superblock_checkpoints.save_as_ne(writer)?;
+        self.interval_checkpoints.save_as_ne(writer)?;
+        // blocks
+        (self.blocks.len() as u64).save_as_ne(writer)?;
+        writer.write_all(bytemuck::cast_slice(&self.blocks))?;
+        Ok(())
+    }
+    fn load_from<R>(reader: &mut R) -> Result<Self, std::io::Error> where
+        R: std::io::Read,
+        Self: Sized,
+    {
+        // primary_index
+        let primary_index = P::from_u64(u64::load_as_ne(reader)?);
+        // chr_count
+        let chr_count = u32::load_as_ne(reader)?;
+        // interval
+        Self::check_interval(u32::load_as_ne(reader)?)?;
+        // checkpoints
+        let superblock_checkpoints = Vec::<P>::load_as_ne(reader)?;
+        let interval_checkpoints = Vec::<u16>::load_as_ne(reader)?;
+        // blocks
+        let blocks_len = u64::load_as_ne(reader)? as usize;
+        let mut blocks = vec![B::zeroed(); blocks_len];
+        reader.read_exact(bytemuck::cast_slice_mut(&mut blocks))?;
+        Ok(Self {
+            primary_index,
+            chr_count,
+            superblock_checkpoints,
+            interval_checkpoints,
+            blocks,
+        })
+    }
+    fn encoded_len(&self) -> usize {
+        16 // primary_index(8) + chr_count(4) + interval(4)
+        + self.superblock_checkpoints.encoded_len() // superblock_checkpoints
+        + self.interval_checkpoints.encoded_len() // interval_checkpoints
+        + 8 // blocks_len
+        + size_of_val(self.blocks.as_slice()) // blocks
+    }
+}
+
+impl<P: Position, B: Block<P>, const INTERVAL: u32> SuperblockBwm<P, B, INTERVAL> {
+    // The layout of the checkpoints depends on the interval
+    pub(super) fn check_interval(interval: u32) -> Result<(), std::io::Error> {
+        if interval == INTERVAL {
+            Ok(())
+        } else {
+            Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("Checkpoint interval of the index is {}, not {}", interval, INTERVAL),
+            ))
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use std::pin::Pin;
+
+use crate::core::{Position, AsyncSerialize};
+use crate::algorithm::bit_vector::BitVector;
+use super::{WaveletTreeBwm, WaveletNode};
+use capwriter::{AsyncSave, AsyncLoad};
+
+impl<P: Position> AsyncSerialize for WaveletTreeBwm<P> {
+    fn async_save_to<W>(&self, mut writer: Pin<&mut W>) -> impl Future<Output = Result<(), std::io::Error>> + Send where
+        W: tokio::io::AsyncWrite + Send,
+    {
+        async move {
+            // primary_index
+            self.primary_index.as_u64().save_as_ne(writer.as_mut()).await?;
+            // chr_count
+            self.chr_count.save_as_ne(writer.as_mut()).await?;
+            // root
+            self.root.save_as_ne(writer.as_mut()).await?;
+            // nodes
+            (self.nodes.len() as u64).save_as_ne(writer.as_mut()).await?;
+            for node in &self.nodes {
+                node.children.save_as_ne(writer.as_mut()).await?;
+                node.bits.async_save_to(writer.as_mut()).await?;
+            }
+            // codes
+            self.codes.save_as_ne(writer.as_mut()).await?;
+            self.code_lens.save_as_ne(writer.as_mut()).await?;
+            Ok(())
+        }
+    }
+    fn async_load_from<R>(mut reader: Pin<&mut R>) -> impl Future<Output = Result<Self, std::io::Error>> + Send where
+        R: tokio::io::AsyncRead + Send,
+        Self: Sized,
+    {
+        async move {
+            // primary_index
+            let primary_index = P::from_u64(u64::load_as_ne(reader.as_mut()).await?);
+            // chr_count
+            let chr_count = u32::load_as_ne(reader.as_mut()).await?;
+            // root
+            let root = u32::load_as_ne(reader.as_mut()).await?;
+            // nodes
+            let nodes_len = u64::load_as_ne(reader.as_mut()).await? as usize;
+            let mut nodes = Vec::with_capacity(nodes_len);
+            for _ in 0..nodes_len {
+                let children = <[u32; 2]>::load_as_ne(reader.as_mut()).await?;
+                let bits = BitVector::async_load_from(reader.as_mut()).await?;
+                nodes.push(WaveletNode { bits, children });
+            }
+            // codes
+            let codes = Vec::<u128>::load_as_ne(reader.as_mut()).await?;
+            let code_lens = Vec::<u8>::load_as_ne(reader.as_mut()).await?;
+            Ok(Self {
+                primary_index,
+                chr_count,
+                root,
+                nodes,
+                codes,
+                code_lens,
+            })
+        }
+    }
+}
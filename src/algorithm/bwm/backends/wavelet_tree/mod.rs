@@ -0,0 +1,241 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use crate::core::{Position, errors::BuildError};
+use crate::algorithm::bit_vector::BitVector;
+use crate::algorithm::{BuildMonitor, BuildPhase};
+use super::{BwmBackend, BurrowsWheelerMatrix};
+
+/// Backend using a Huffman-shaped wavelet tree. Can index 255 characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WaveletTree;
+
+impl<P: Position> BwmBackend<P> for WaveletTree {
+    type Bwm = WaveletTreeBwm<P>;
+}
+
+// Burrows-Wheeler Matrix in Huffman-shaped wavelet tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct WaveletTreeBwm<P: Position> {
+    primary_index: P,
+    chr_count: u32,
+    root: u32,
+    nodes: Vec<WaveletNode>,
+    // Huffman code of each chridx (from the root, MSB first)
+    codes: Vec<u128>,
+    code_lens: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+struct WaveletNode {
+    bits: BitVector,
+    children: [u32; 2],
+}
+
+// If the flag is set, the reference to the node is a leaf of chridx.
+const LEAF_FLAG: u32 = 1 << 31;
+
+enum HuffmanNode {
+    Leaf(u8),
+    Internal([usize; 2]),
+}
+
+impl<P: Position> BurrowsWheelerMatrix<P> for WaveletTreeBwm<P> {
+    const MAX_CHR: u32 = 254;
+
+    // Build
+    fn new(bwt_text: Vec<u8>, pidx: P, chr_count: u32, monitor: &mut BuildMonitor) -> Result<Self, BuildError> {
+        monitor.start_phase(BuildPhase::Vectorization)?;
+        let mut chridx_text = bwt_text;
+        let mut frequencies = vec![0_u64; chr_count as usize];
+        chridx_text.iter_mut().for_each(|chridxwp| {
+            *chridxwp -= 1;
+            frequencies[*chridxwp as usize] += 1;
+        });
+
+        let (huffman_nodes, huffman_root) = Self::huffman_tree(&frequencies);
+        let mut codes = vec![0; chr_count as usize];
+        let mut code_lens = vec![0; chr_count as usize];
+        let mut stack = vec![(huffman_root, 0_u128, 0_u8)];
+        while let Some((node_idx, code, code_len)) = stack.pop() {
+            match huffman_nodes[node_idx] {
+                HuffmanNode::Leaf(chridx) => {
+                    codes[chridx as usize] = code;
+                    code_lens[chridx as usize] = code_len;
+                },
+                HuffmanNode::Internal([left, right]) => {
+                    stack.push((left, code << 1, code_len + 1));
+                    stack.push((right, (code << 1) | 1, code_len + 1));
+                },
+            }
+        }
+
+        // Each character is in the bits of the nodes on the path to its leaf
+        let bit_count = frequencies.iter().zip(&code_lens).map(|(&frequency, &code_len)| {
+            frequency as usize * code_len as usize
+        }).sum();
+        let mut nodes = Vec::new();
+        let root = Self::build_node(
+            &mut nodes,
+            &huffman_nodes,
+            huffman_root,
+            0,
+            chridx_text,
+            &codes,
+            &code_lens,
+            monitor,
+            &mut (0, bit_count),
+        )?;
+
+        monitor.finish_phase(BuildPhase::Vectorization)?;
+        Ok(Self {
+            primary_index: pidx,
+            chr_count,
+            root,
+            nodes,
+            codes,
+            code_lens,
+        })
+    }
+    // Locate
+    #[inline]
+    fn get_next_rank(&self, mut pos: P, chridx: u8) -> P {
+        if pos < self.primary_index {
+            pos += P::ONE;
+        }
+        P::from_u64(self.rank(pos.as_u64(), chridx))
+    }
+    #[inline]
+    fn get_pre_rank_and_chridx(&self, mut pos: P) -> Option<(P, u8)> {
+        if pos == self.primary_index - P::ONE {
+            return None;
+        } else if pos < self.primary_index {
+            pos += P::ONE;
+        }
+        let (chridx, rank) = self.chridx_and_rank(pos.as_u64());
+        Some((P::from_u64(rank), chridx))
+    }
+    fn chr_count(&self) -> u32 {
+        self.chr_count
+    }
+    fn primary_index(&self) -> P {
+        self.primary_index
+    }
+}
+
+impl<P: Position> WaveletTreeBwm<P> {
+    fn huffman_tree(frequencies: &[u64]) -> (Vec<HuffmanNode>, usize) {
+        let mut huffman_nodes = Vec::new();
+        let mut heap = BinaryHeap::new();
+        frequencies.iter().enumerate().for_each(|(chridx, &frequency)| {
+            if frequency != 0 {
+                heap.push(Reverse((frequency, huffman_nodes.len())));
+                huffman_nodes.push(HuffmanNode::Leaf(chridx as u8));
+            }
+        });
+        if huffman_nodes.is_empty() {
+            // Empty text
+            huffman_nodes.push(HuffmanNode::Leaf(0));
+            return (huffman_nodes, 0);
+        }
+        while heap.len() > 1 {
+            let Reverse((frequency_1, left)) = heap.pop().unwrap();
+            let Reverse((frequency_2, right)) = heap.pop().unwrap();
+            heap.push(Reverse((frequency_1 + frequency_2, huffman_nodes.len())));
+            huffman_nodes.push(HuffmanNode::Internal([left, right]));
+        }
+        let Reverse((_, root)) = heap.pop().unwrap();
+        (huffman_nodes, root)
+    }
+    // `progress` is the count of the bits built, and of all bits
+    #[allow(clippy::too_many_arguments)]
+    fn build_node(
+        nodes: &mut Vec<WaveletNode>,
+        huffman_nodes: &[HuffmanNode],
+        huffman_node_idx: usize,
+        depth: u8,
+        chridx_text: Vec<u8>,
+        codes: &[u128],
+        code_lens: &[u8],
+        monitor: &mut BuildMonitor,
+        progress: &mut (usize, usize),
+    ) -> Result<u32, BuildError> {
+        let children = match huffman_nodes[huffman_node_idx] {
+            HuffmanNode::Leaf(chridx) => return Ok(LEAF_FLAG | chridx as u32),
+            HuffmanNode::Internal(children) => children,
+        };
+        let code_bit = |chridx: u8| {
+            let shift = code_lens[chridx as usize] - 1 - depth;
+            (codes[chridx as usize] >> shift) & 1 == 1
+        };
+        let bits = BitVector::from_bits(chridx_text.iter().map(|&chridx| code_bit(chridx)));
+        let (right_text, left_text): (Vec<u8>, Vec<u8>) = chridx_text.into_iter().partition(|&chridx| code_bit(chridx));
+
+        progress.0 += right_text.len() + left_text.len();
+        monitor.progress(BuildPhase::Vectorization, progress.0, progress.1)?;
+
+        let node_idx = nodes.len();
+        nodes.push(WaveletNode { bits, children: [0, 0] });
+        let left = Self::build_node(
+            nodes, huffman_nodes, children[0], depth + 1, left_text, codes, code_lens, monitor, progress,
+        )?;
+        let right = Self::build_node(
+            nodes, huffman_nodes, children[1], depth + 1, right_text, codes, code_lens, monitor, progress,
+        )?;
+        nodes[node_idx].children = [left, right];
+        Ok(node_idx as u32)
+    }
+
+    // Count of chridx in `[0, idx)` of the BWT without the primary index.
+    #[inline]
+    fn rank(&self, mut idx: u64, chridx: u8) -> u64 {
+        let code = self.codes[chridx as usize];
+        let code_len = self.code_lens[chridx as usize];
+        let mut depth = 0;
+        let mut node_ref = self.root;
+        while node_ref & LEAF_FLAG == 0 {
+            if depth >= code_len {
+                // Character not in text
+                return 0;
+            }
+            let node = &self.nodes[node_ref as usize];
+            let bit = ((code >> (code_len - 1 - depth)) & 1) as usize;
+            idx = if bit == 1 {
+                node.bits.rank1(idx)
+            } else {
+                node.bits.rank0(idx)
+            };
+            node_ref = node.children[bit];
+            depth += 1;
+        }
+        if node_ref & !LEAF_FLAG == chridx as u32 {
+            idx
+        } else {
+            0
+        }
+    }
+    // The chridx at idx and its count in `[0, idx)`.
+    #[inline]
+    fn chridx_and_rank(&self, mut idx: u64) -> (u8, u64) {
+        let mut node_ref = self.root;
+        while node_ref & LEAF_FLAG == 0 {
+            let node = &self.nodes[node_ref as usize];
+            let bit = node.bits.get(idx);
+            idx = if bit {
+                node.bits.rank1(idx)
+            } else {
+                node.bits.rank0(idx)
+            };
+            node_ref = node.children[bit as usize];
+        }
+        ((node_ref & !LEAF_FLAG) as u8, idx)
+    }
+}
+
+mod serialize;
+#[cfg(feature = "async-io")]
+mod async_serialize;
@@ -0,0 +1,66 @@
+use crate::core::{Position, Serialize};
+use crate::algorithm::bit_vector::BitVector;
+use super::{WaveletTreeBwm, WaveletNode};
+use capwriter::{Save, Load};
+
+impl<P: Position> Serialize for WaveletTreeBwm<P> {
+    fn save_to<W>(&self, writer: &mut W) -> Result<(), std::io::Error> where
+        W: std::io::Write,
+    {
+        // primary_index
+        self.primary_index.as_u64().save_as_ne(writer)?;
+        // chr_count
+        self.chr_count.save_as_ne(writer)?;
+        // root
+        self.root.save_as_ne(writer)?;
+        // nodes
+        (self.nodes.len() as u64).save_as_ne(writer)?;
+        for node in &self.nodes {
+            node.children.save_as_ne(writer)?;
+            node.bits.save_to(writer)?;
+        }
+        // codes
+        self.codes.save_as_ne(writer)?;
+        self.code_lens.save_as_ne(writer)?;
+        Ok(())
+    }
+    fn load_from<R>(reader: &mut R) -> Result<Self, std::io::Error> where
+        R: std::io::Read,
+        Self: Sized,
+    {
+        // primary_index
+        let primary_index = P::from_u64(u64::load_as_ne(reader)?);
+        // chr_count
+        let chr_count = u32::load_as_ne(reader)?;
+        // root
+        let root = u32::load_as_ne(reader)?;
+        // nodes
+        let nodes_len = u64::load_as_ne(reader)? as usize;
+        let mut nodes = Vec::with_capacity(nodes_len);
+        for _ in 0..nodes_len {
+            let children = <[u32; 2]>::load_as_ne(reader)?;
+            let bits = BitVector::load_from(reader)?;
+            nodes.push(WaveletNode { bits, children });
+        }
+        // codes
+        let codes = Vec::<u128>::load_as_ne(reader)?;
+        let code_lens = Vec::<u8>::load_as_ne(reader)?;
+        Ok(Self {
+            primary_index,
+            chr_count,
+            root,
+            nodes,
+            codes,
+            code_lens,
+        })
+    }
+    fn encoded_len(&self) -> usize {
+        16 // primary_index(8) + chr_count(4) + root(4)
+        + 8 // nodes_len
+        + self.nodes.iter().map(|node| {
+            node.children.encoded_len() + node.bits.encoded_len()
+        }).sum::<usize>() // nodes
+        + self.codes.encoded_len() // codes
+        + self.code_lens.encoded_len() // code_lens
+    }
+}
@@ -1,11 +1,17 @@
 use crate::core::Position;
-use super::{Block, Vector};
+use super::{Block, BwmBackend, Bwm, Vector};
 use bytemuck::{Pod, Zeroable};
 
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 pub struct Block3<V: Vector>([V; 3]);
 
+impl<P: Position, V: Vector> BwmBackend<P> for Block3<V> {
+    type Bwm = Bwm<P, Self>;
+}
+
 impl<P: Position, V: Vector> Block<P> for Block3<V> {
     const BLOCK_LEN: u32 = V::BLOCK_LEN;
     const MAX_CHR: u32 = 7;
@@ -1,11 +1,17 @@
 use crate::core::Position;
-use super::{Block, Vector};
+use super::{Block, BwmBackend, Bwm, Vector};
 use bytemuck::{Pod, Zeroable};
 
 #[repr(C)]
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 pub struct Block5<V: Vector>([V; 5]);
 
+impl<P: Position, V: Vector> BwmBackend<P> for Block5<V> {
+    type Bwm = Bwm<P, Self>;
+}
+
 impl<P: Position, V: Vector> Block<P> for Block5<V> {
     const BLOCK_LEN: u32 = V::BLOCK_LEN;
     const MAX_CHR: u32 = 31;
@@ -85,6 +91,10 @@ impl<P: Position, V: Vector> Block<P> for Block5<V> {
         count_bits.count_ones()
     }
     #[inline]
+    fn get_remain_counts_of(blocks: [&Self; 2], rems: [u32; 2], chridx: u8) -> [u32; 2] {
+        V::count_chridx_pair([&blocks[0].0, &blocks[1].0], rems, chridx)
+    }
+    #[inline]
     fn get_chridx_of(&self, rem: u32) -> u8 {
         let mov = V::BLOCK_LEN - rem - 1;
         let v1 = (self.0[0] >> V::from_u32(mov)).as_u8() & 1;
@@ -8,9 +8,10 @@ But the maximum count of the index is restrict to the $2^{v}-1$ ($v$ the vector
 Currently, [Vector] is implemented for: u32, u64, u128.
 The shorter the vector, the faster the algorithm, but the larger the struct.
 */
-use super::Block;
+use super::{Block, BwmBackend, Bwm};
 
 mod vector;
+pub(crate) mod simd_rank;
 mod block2;
 mod block3;
 mod block4;
@@ -0,0 +1,283 @@
+/*!
+Counting the bits of a chridx in the bit-sliced vectors of two blocks at once,
+for the both ends of the pos range.
+
+The kernel is selected at runtime by the features of the CPU:
+AVX-512 (`VPOPCNTQ`), AVX2, or SSE4.2 with `POPCNT` on x86_64, NEON on aarch64, and scalar otherwise.
+Each kernel matches the chridx by XOR with the inverting masks and AND of the vectors,
+and counts the bits in the first `rem` bits by AND with the masks of the remains instead of the shift.
+*/
+use std::sync::OnceLock;
+
+use super::Vector;
+
+/// Instructions counting the bits of the chridx.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kernel {
+    Scalar,
+    #[cfg(target_arch = "x86_64")]
+    Sse42,
+    #[cfg(target_arch = "x86_64")]
+    Avx2,
+    #[cfg(target_arch = "x86_64")]
+    Avx512,
+    #[cfg(target_arch = "aarch64")]
+    Neon,
+}
+
+static KERNEL: OnceLock<Kernel> = OnceLock::new();
+
+impl Kernel {
+    // The fastest kernel supported by the CPU
+    #[inline]
+    pub fn detected() -> Self {
+        *KERNEL.get_or_init(|| {
+            Self::supported().into_iter().last().unwrap_or(Self::Scalar)
+        })
+    }
+    // Kernels supported by the CPU, from the slowest
+    pub fn supported() -> Vec<Self> {
+        let mut kernels = vec![Self::Scalar];
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("sse4.2") && is_x86_feature_detected!("popcnt") {
+                kernels.push(Self::Sse42);
+            }
+            if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("popcnt") {
+                kernels.push(Self::Avx2);
+            }
+            if is_x86_feature_detected!("avx512vpopcntdq") && is_x86_feature_detected!("avx512vl") {
+                kernels.push(Self::Avx512);
+            }
+        }
+        #[cfg(target_arch = "aarch64")]
+        {
+            if std::arch::is_aarch64_feature_detected!("neon") {
+                kernels.push(Self::Neon);
+            }
+        }
+        kernels
+    }
+}
+
+#[inline]
+pub fn count_pair_scalar<V: Vector, const N: usize>(vectors: [&[V; N]; 2], rems: [u32; 2], chridx: u8) -> [u32; 2] {
+    [0, 1].map(|side| {
+        if rems[side] == 0 {
+            return 0;
+        }
+        let matched = vectors[side].iter().enumerate().fold(!V::ZERO, |matched, (bit, &vector)| {
+            if (chridx >> bit) & 1 == 1 { matched & vector } else { matched & !vector }
+        });
+        (matched >> V::from_u32(V::BLOCK_LEN - rems[side])).count_ones()
+    })
+}
+
+#[inline]
+pub fn count_pair_u64<const N: usize>(vectors: [&[u64; N]; 2], rems: [u32; 2], chridx: u8) -> [u32; 2] {
+    count_pair_u64_with(Kernel::detected(), vectors, rems, chridx)
+}
+#[inline]
+pub fn count_pair_u64_with<const N: usize>(kernel: Kernel, vectors: [&[u64; N]; 2], rems: [u32; 2], chridx: u8) -> [u32; 2] {
+    // SAFETY: the kernels are used only if the CPU supports them
+    match kernel {
+        #[cfg(target_arch = "x86_64")]
+        Kernel::Avx512 => unsafe { x86::count_pair_u64_avx512(vectors, rems, chridx) },
+        #[cfg(target_arch = "x86_64")]
+        Kernel::Avx2 | Kernel::Sse42 => unsafe { x86::count_pair_u64_sse42(vectors, rems, chridx) },
+        #[cfg(target_arch = "aarch64")]
+        Kernel::Neon => unsafe { neon::count_pair_u64(vectors, rems, chridx) },
+        _ => count_pair_scalar(vectors, rems, chridx),
+    }
+}
+
+#[inline]
+pub fn count_pair_u128<const N: usize>(vectors: [&[u128; N]; 2], rems: [u32; 2], chridx: u8) -> [u32; 2] {
+    count_pair_u128_with(Kernel::detected(), vectors, rems, chridx)
+}
+#[inline]
+pub fn count_pair_u128_with<const N: usize>(kernel: Kernel, vectors: [&[u128; N]; 2], rems: [u32; 2], chridx: u8) -> [u32; 2] {
+    // SAFETY: the kernels are used only if the CPU supports them
+    match kernel {
+        #[cfg(target_arch = "x86_64")]
+        Kernel::Avx512 => unsafe { x86::count_pair_u128_avx512(vectors, rems, chridx) },
+        #[cfg(target_arch = "x86_64")]
+        Kernel::Avx2 => unsafe { x86::count_pair_u128_avx2(vectors, rems, chridx) },
+        #[cfg(target_arch = "x86_64")]
+        Kernel::Sse42 => unsafe { x86::count_pair_u128_sse42(vectors, rems, chridx) },
+        #[cfg(target_arch = "aarch64")]
+        Kernel::Neon => unsafe { neon::count_pair_u128(vectors, rems, chridx) },
+        _ => count_pair_scalar(vectors, rems, chridx),
+    }
+}
+
+// All ones for the bit of the chridx of 0, to invert the vector by XOR
+#[inline(always)]
+fn inverting_mask(chridx: u8, bit: usize) -> u64 {
+    (((chridx >> bit) & 1) as u64).wrapping_sub(1)
+}
+// The first (upper) `rem` bits
+#[inline(always)]
+fn remain_mask_u64(rem: u32) -> u64 {
+    if rem == 0 { 0 } else { u64::MAX << (64 - rem) }
+}
+// The first (upper) `rem` bits, as the lower and upper words
+#[inline(always)]
+fn remain_mask_u128(rem: u32) -> [u64; 2] {
+    let mask = if rem == 0 { 0 } else { u128::MAX << (128 - rem) };
+    [mask as u64, (mask >> 64) as u64]
+}
+#[inline(always)]
+fn words_of(vector: u128) -> [u64; 2] {
+    [vector as u64, (vector >> 64) as u64]
+}
+
+// The kernels are `unsafe fn` of the target features, as the MSRV requires.
+// The intrinsics are unsafe before Rust 1.87, and safe in the target features after it,
+// so the calls are not wrapped in the `unsafe` blocks.
+#[cfg(target_arch = "x86_64")]
+#[allow(unsafe_op_in_unsafe_fn)]
+mod x86 {
+    use std::arch::asm;
+    use std::arch::x86_64::*;
+    use super::{inverting_mask, remain_mask_u64, remain_mask_u128, words_of};
+
+    // Lanes: the vectors of the first and second blocks
+    #[inline]
+    #[target_feature(enable = "sse4.2")]
+    unsafe fn matched_u64x2<const N: usize>(vectors: [&[u64; N]; 2], rems: [u32; 2], chridx: u8) -> __m128i {
+        let mut matched = _mm_set_epi64x(remain_mask_u64(rems[1]) as i64, remain_mask_u64(rems[0]) as i64);
+        for (bit, (&vector_0, &vector_1)) in vectors[0].iter().zip(vectors[1]).enumerate() {
+            let inverting = _mm_set1_epi64x(inverting_mask(chridx, bit) as i64);
+            let vector = _mm_set_epi64x(vector_1 as i64, vector_0 as i64);
+            matched = _mm_and_si128(matched, _mm_xor_si128(vector, inverting));
+        }
+        matched
+    }
+    // Lanes: the lower and upper words of the vector of a block
+    #[inline]
+    #[target_feature(enable = "sse4.2")]
+    unsafe fn matched_u64x2_of_u128<const N: usize>(vectors: &[u128; N], rem: u32, chridx: u8) -> __m128i {
+        let [mask_lo, mask_hi] = remain_mask_u128(rem);
+        let mut matched = _mm_set_epi64x(mask_hi as i64, mask_lo as i64);
+        for (bit, &vector) in vectors.iter().enumerate() {
+            let [vector_lo, vector_hi] = words_of(vector);
+            let inverting = _mm_set1_epi64x(inverting_mask(chridx, bit) as i64);
+            let vector = _mm_set_epi64x(vector_hi as i64, vector_lo as i64);
+            matched = _mm_and_si128(matched, _mm_xor_si128(vector, inverting));
+        }
+        matched
+    }
+    // Lanes: the lower and upper words of the vectors of the first and second blocks
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    unsafe fn matched_u64x4<const N: usize>(vectors: [&[u128; N]; 2], rems: [u32; 2], chridx: u8) -> __m256i {
+        let [mask_0_lo, mask_0_hi] = remain_mask_u128(rems[0]);
+        let [mask_1_lo, mask_1_hi] = remain_mask_u128(rems[1]);
+        let mut matched = _mm256_set_epi64x(mask_1_hi as i64, mask_1_lo as i64, mask_0_hi as i64, mask_0_lo as i64);
+        for (bit, (&vector_0, &vector_1)) in vectors[0].iter().zip(vectors[1]).enumerate() {
+            let [vector_0_lo, vector_0_hi] = words_of(vector_0);
+            let [vector_1_lo, vector_1_hi] = words_of(vector_1);
+            let inverting = _mm256_set1_epi64x(inverting_mask(chridx, bit) as i64);
+            let vector = _mm256_set_epi64x(vector_1_hi as i64, vector_1_lo as i64, vector_0_hi as i64, vector_0_lo as i64);
+            matched = _mm256_and_si256(matched, _mm256_xor_si256(vector, inverting));
+        }
+        matched
+    }
+
+    #[target_feature(enable = "sse4.2,popcnt")]
+    pub unsafe fn count_pair_u64_sse42<const N: usize>(vectors: [&[u64; N]; 2], rems: [u32; 2], chridx: u8) -> [u32; 2] {
+        let matched = matched_u64x2(vectors, rems, chridx);
+        [
+            _popcnt64(_mm_extract_epi64::<0>(matched)) as u32,
+            _popcnt64(_mm_extract_epi64::<1>(matched)) as u32,
+        ]
+    }
+    // `VPOPCNTQ` is written in the assembly, since the intrinsics of AVX-512 are stable after the MSRV
+    #[target_feature(enable = "sse4.2")]
+    pub unsafe fn count_pair_u64_avx512<const N: usize>(vectors: [&[u64; N]; 2], rems: [u32; 2], chridx: u8) -> [u32; 2] {
+        let matched = matched_u64x2(vectors, rems, chridx);
+        let counts: __m128i;
+        asm!(
+            "vpopcntq {counts}, {matched}",
+            matched = in(xmm_reg) matched,
+            counts = lateout(xmm_reg) counts,
+            options(pure, nomem, nostack),
+        );
+        [_mm_extract_epi64::<0>(counts) as u32, _mm_extract_epi64::<1>(counts) as u32]
+    }
+    #[target_feature(enable = "sse4.2,popcnt")]
+    pub unsafe fn count_pair_u128_sse42<const N: usize>(vectors: [&[u128; N]; 2], rems: [u32; 2], chridx: u8) -> [u32; 2] {
+        let matched_0 = matched_u64x2_of_u128(vectors[0], rems[0], chridx);
+        let matched_1 = matched_u64x2_of_u128(vectors[1], rems[1], chridx);
+        [
+            (_popcnt64(_mm_extract_epi64::<0>(matched_0)) + _popcnt64(_mm_extract_epi64::<1>(matched_0))) as u32,
+            (_popcnt64(_mm_extract_epi64::<0>(matched_1)) + _popcnt64(_mm_extract_epi64::<1>(matched_1))) as u32,
+        ]
+    }
+    #[target_feature(enable = "avx2,popcnt")]
+    pub unsafe fn count_pair_u128_avx2<const N: usize>(vectors: [&[u128; N]; 2], rems: [u32; 2], chridx: u8) -> [u32; 2] {
+        let matched = matched_u64x4(vectors, rems, chridx);
+        [
+            (_popcnt64(_mm256_extract_epi64::<0>(matched)) + _popcnt64(_mm256_extract_epi64::<1>(matched))) as u32,
+            (_popcnt64(_mm256_extract_epi64::<2>(matched)) + _popcnt64(_mm256_extract_epi64::<3>(matched))) as u32,
+        ]
+    }
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn count_pair_u128_avx512<const N: usize>(vectors: [&[u128; N]; 2], rems: [u32; 2], chridx: u8) -> [u32; 2] {
+        let matched = matched_u64x4(vectors, rems, chridx);
+        let counts: __m256i;
+        asm!(
+            "vpopcntq {counts}, {matched}",
+            matched = in(ymm_reg) matched,
+            counts = lateout(ymm_reg) counts,
+            options(pure, nomem, nostack),
+        );
+        [
+            (_mm256_extract_epi64::<0>(counts) + _mm256_extract_epi64::<1>(counts)) as u32,
+            (_mm256_extract_epi64::<2>(counts) + _mm256_extract_epi64::<3>(counts)) as u32,
+        ]
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+#[allow(unsafe_op_in_unsafe_fn)]
+mod neon {
+    use std::arch::aarch64::*;
+    use super::{inverting_mask, remain_mask_u64, remain_mask_u128, words_of};
+
+    #[inline]
+    #[target_feature(enable = "neon")]
+    unsafe fn matched_u64x2_of_u128<const N: usize>(vectors: &[u128; N], rem: u32, chridx: u8) -> uint64x2_t {
+        let [mask_lo, mask_hi] = remain_mask_u128(rem);
+        let mut matched = vcombine_u64(vcreate_u64(mask_lo), vcreate_u64(mask_hi));
+        for (bit, &vector) in vectors.iter().enumerate() {
+            let [vector_lo, vector_hi] = words_of(vector);
+            let inverting = vdupq_n_u64(inverting_mask(chridx, bit));
+            let vector = vcombine_u64(vcreate_u64(vector_lo), vcreate_u64(vector_hi));
+            matched = vandq_u64(matched, veorq_u64(vector, inverting));
+        }
+        matched
+    }
+
+    #[target_feature(enable = "neon")]
+    pub unsafe fn count_pair_u64<const N: usize>(vectors: [&[u64; N]; 2], rems: [u32; 2], chridx: u8) -> [u32; 2] {
+        let mut matched = vcombine_u64(vcreate_u64(remain_mask_u64(rems[0])), vcreate_u64(remain_mask_u64(rems[1])));
+        for (bit, (&vector_0, &vector_1)) in vectors[0].iter().zip(vectors[1]).enumerate() {
+            let inverting = vdupq_n_u64(inverting_mask(chridx, bit));
+            let vector = vcombine_u64(vcreate_u64(vector_0), vcreate_u64(vector_1));
+            matched = vandq_u64(matched, veorq_u64(vector, inverting));
+        }
+        let counts = vpaddlq_u32(vpaddlq_u16(vpaddlq_u8(vcntq_u8(vreinterpretq_u8_u64(matched)))));
+        [vgetq_lane_u64::<0>(counts) as u32, vgetq_lane_u64::<1>(counts) as u32]
+    }
+    #[target_feature(enable = "neon")]
+    pub unsafe fn count_pair_u128<const N: usize>(vectors: [&[u128; N]; 2], rems: [u32; 2], chridx: u8) -> [u32; 2] {
+        let matched_0 = matched_u64x2_of_u128(vectors[0], rems[0], chridx);
+        let matched_1 = matched_u64x2_of_u128(vectors[1], rems[1], chridx);
+        [
+            vaddlvq_u8(vcntq_u8(vreinterpretq_u8_u64(matched_0))) as u32,
+            vaddlvq_u8(vcntq_u8(vreinterpretq_u8_u64(matched_1))) as u32,
+        ]
+    }
+}
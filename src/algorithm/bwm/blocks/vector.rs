@@ -7,9 +7,12 @@ Three types are supported:
 
 Using the more small bits makes the algorithm faster, but the size of the struct is the larger than of long bits.
 */
+use super::simd_rank;
+
 pub trait Vector:
     Sized
     + Send + Sync
+    + std::fmt::Debug
     + std::ops::Not<Output = Self>
     + std::ops::BitAnd<Output = Self>
     + std::ops::Shr<Output = Self>
@@ -26,6 +29,11 @@ pub trait Vector:
     const ONE: Self;
     fn as_u8(self) -> u8;
     fn from_u32(value: u32) -> Self;
+    // Counts of the chridx in the first `rems` bits of the bit-sliced vectors of the two blocks
+    #[inline]
+    fn count_chridx_pair<const N: usize>(vectors: [&[Self; N]; 2], rems: [u32; 2], chridx: u8) -> [u32; 2] {
+        simd_rank::count_pair_scalar(vectors, rems, chridx)
+    }
 }
 
 impl Vector for u32 {
@@ -53,6 +61,10 @@ impl Vector for u64 {
     fn from_u32(value: u32) -> Self {
         value as Self
     }
+    #[inline]
+    fn count_chridx_pair<const N: usize>(vectors: [&[Self; N]; 2], rems: [u32; 2], chridx: u8) -> [u32; 2] {
+        simd_rank::count_pair_u64(vectors, rems, chridx)
+    }
 }
 impl Vector for u128 {
     const BLOCK_LEN: u32 = 128;
@@ -66,4 +78,8 @@ impl Vector for u128 {
     fn from_u32(value: u32) -> Self {
         value as Self
     }
+    #[inline]
+    fn count_chridx_pair<const N: usize>(vectors: [&[Self; N]; 2], rems: [u32; 2], chridx: u8) -> [u32; 2] {
+        simd_rank::count_pair_u128(vectors, rems, chridx)
+    }
 }
\ No newline at end of file
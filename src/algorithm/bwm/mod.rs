@@ -1,9 +1,13 @@
-use crate::core::Position;
+use crate::core::{Position, Serialize, errors::BuildError};
+use super::{BuildMonitor, BuildPhase};
 
 pub mod blocks;
+pub mod backends;
 
 // Burrows-Wheeler Matrix
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 pub struct Bwm<P: Position, B: Block<P>> {
     primary_index: P,
     chr_count: u32,
@@ -14,7 +18,7 @@ pub struct Bwm<P: Position, B: Block<P>> {
 Type of the block of compressed Burrow-Wheeler transformed text.
 The implementations are in [blocks] module.
 */
-pub trait Block<P: Position>: Sized + bytemuck::Pod + Send + Sync {
+pub trait Block<P: Position>: BwmBackend<P> + Sized + bytemuck::Pod + Send + Sync + Eq + std::fmt::Debug {
     const BLOCK_LEN: u32;
     const MAX_CHR: u32;
     // Build
@@ -23,34 +27,113 @@ pub trait Block<P: Position>: Sized + bytemuck::Pod + Send + Sync {
     fn shift_last_offset(&mut self, offset: u32);
     // Locate
     fn get_remain_count_of(&self, rem: u32, chridx: u8) -> u32;
+    // Counts of `get_remain_count_of` of the two blocks for the both ends of the pos range (0 for the rem of 0)
+    #[inline]
+    fn get_remain_counts_of(blocks: [&Self; 2], rems: [u32; 2], chridx: u8) -> [u32; 2] {
+        [0, 1].map(|side| match rems[side] {
+            0 => 0,
+            rem => blocks[side].get_remain_count_of(rem, chridx),
+        })
+    }
     fn get_chridx_of(&self, rem: u32) -> u8;
+    // Index of the block having the position, and the remainder in the block
+    #[inline]
+    fn split_position(pos: P) -> (usize, u32) {
+        let block_len = Self::BLOCK_LEN as usize;
+        (pos.as_usize() / block_len, (pos.as_usize() % block_len) as u32)
+    }
+}
+
+/**
+Type that selects the structure storing the Burrow-Wheeler transformed text of [crate::LtFmIndex].
+Every [Block] is a backend using rank checkpoints with bit-sliced blocks.
+The alternatives are in [backends] module.
+*/
+pub trait BwmBackend<P: Position>: Sized + Clone {
+    type Bwm: BurrowsWheelerMatrix<P>;
+}
+
+/// Operations that the structure of a [BwmBackend] provides for the search.
+#[cfg(feature = "async-io")]
+pub trait BurrowsWheelerMatrix<P: Position>:
+    Sized
+    + Clone
+    + PartialEq + Eq
+    + Send + Sync
+    + std::fmt::Debug
+    + Serialize
+    + crate::core::AsyncSerialize
+{
+    const MAX_CHR: u32;
+    // Build, reporting the progress of the vectorization to the monitor
+    fn new(bwt_text: Vec<u8>, pidx: P, chr_count: u32, monitor: &mut BuildMonitor) -> Result<Self, BuildError>;
+    // Locate
+    fn get_next_rank(&self, pos: P, chridx: u8) -> P;
+    // Next ranks of the both ends of the pos range
+    #[inline]
+    fn get_next_ranks(&self, pos_range: (P, P), chridx: u8) -> (P, P) {
+        (self.get_next_rank(pos_range.0, chridx), self.get_next_rank(pos_range.1, chridx))
+    }
+    fn get_pre_rank_and_chridx(&self, pos: P) -> Option<(P, u8)>;
+    fn chr_count(&self) -> u32;
+    // One more than the row of the suffix at the start of the text
+    fn primary_index(&self) -> P;
+}
+/// Operations that the structure of a [BwmBackend] provides for the search.
+#[cfg(not(feature = "async-io"))]
+pub trait BurrowsWheelerMatrix<P: Position>:
+    Sized
+    + Clone
+    + PartialEq + Eq
+    + Send + Sync
+    + std::fmt::Debug
+    + Serialize
+{
+    const MAX_CHR: u32;
+    // Build, reporting the progress of the vectorization to the monitor
+    fn new(bwt_text: Vec<u8>, pidx: P, chr_count: u32, monitor: &mut BuildMonitor) -> Result<Self, BuildError>;
+    // Locate
+    fn get_next_rank(&self, pos: P, chridx: u8) -> P;
+    // Next ranks of the both ends of the pos range
+    #[inline]
+    fn get_next_ranks(&self, pos_range: (P, P), chridx: u8) -> (P, P) {
+        (self.get_next_rank(pos_range.0, chridx), self.get_next_rank(pos_range.1, chridx))
+    }
+    fn get_pre_rank_and_chridx(&self, pos: P) -> Option<(P, u8)>;
+    fn chr_count(&self) -> u32;
+    // One more than the row of the suffix at the start of the text
+    fn primary_index(&self) -> P;
 }
 
 // Bwm Implementations
-impl<P: Position, B: Block<P>> Bwm<P, B> {
+impl<P: Position, B: Block<P>> BurrowsWheelerMatrix<P> for Bwm<P, B> {
+    const MAX_CHR: u32 = B::MAX_CHR;
+
     // Build
     #[inline]
-    pub fn new(bwt_text: Vec<u8>, pidx: P, chr_count: u32) -> Self {
-        let block_len = B::BLOCK_LEN;
-        let mut chunk_count = bwt_text.len() as u32 / block_len.as_u32();
-        let rem = bwt_text.len() as u32 % block_len.as_u32();
+    fn new(bwt_text: Vec<u8>, pidx: P, chr_count: u32, monitor: &mut BuildMonitor) -> Result<Self, BuildError> {
+        monitor.start_phase(BuildPhase::Vectorization)?;
+        let block_len = B::BLOCK_LEN as usize;
+        let mut chunk_count = bwt_text.len() / block_len;
+        let rem = bwt_text.len() % block_len;
         
         let last_offset = if rem == 0 {
             chunk_count += 1;
-            rem
+            0
         } else {
-            block_len.as_u32() - rem
+            (block_len - rem) as u32
         };
 
-        let mut rank_checkpoints = Vec::with_capacity((chunk_count * chr_count) as usize);
+        let mut rank_checkpoints = Vec::with_capacity(chunk_count * chr_count as usize);
         let mut rank_pre_counts = vec![P::ZERO; chr_count as usize];
-        let mut blocks: Vec<B> = Vec::with_capacity(chunk_count as usize);
+        let mut blocks: Vec<B> = Vec::with_capacity(chunk_count);
 
-        bwt_text.chunks(block_len.as_usize()).for_each(|text_chunk| {
+        for (chunk_idx, text_chunk) in bwt_text.chunks(block_len).enumerate() {
+            monitor.progress(BuildPhase::Vectorization, chunk_idx * block_len, bwt_text.len())?;
             rank_checkpoints.extend_from_slice(&rank_pre_counts);
             let block = B::vectorize(text_chunk, &mut rank_pre_counts);
             blocks.push(block);
-        });
+        }
 
         if last_offset == 0 {
             rank_checkpoints.extend_from_slice(&rank_pre_counts);
@@ -60,46 +143,66 @@ impl<P: Position, B: Block<P>> Bwm<P, B> {
             last_block.shift_last_offset(last_offset);
         }
 
-        Self {
+        monitor.finish_phase(BuildPhase::Vectorization)?;
+        Ok(Self {
             primary_index: pidx,
             chr_count,
             rank_checkpoints,
-            blocks: blocks,
-        }
+            blocks,
+        })
     }
     // Locate
     #[inline]
-    pub fn get_next_rank(&self, mut pos: P, chridx: u8) -> P {
+    fn get_next_rank(&self, mut pos: P, chridx: u8) -> P {
         if pos < self.primary_index {
             pos += P::ONE;
         }
-        let quot = pos.as_u32() / B::BLOCK_LEN;
-        let rem = pos.as_u32() % B::BLOCK_LEN;
+        let (quot, rem) = B::split_position(pos);
 
-        let rank_idx = quot.as_u32() * self.chr_count + chridx as u32;
-        let rank_precount = self.rank_checkpoints[rank_idx as usize];
+        let rank_idx = quot * self.chr_count as usize + chridx as usize;
+        let rank_precount = self.rank_checkpoints[rank_idx];
         if rem == 0 {
             rank_precount
         } else {
-            let rem_count = self.blocks[quot.as_usize()].get_remain_count_of(rem, chridx);
+            let rem_count = self.blocks[quot].get_remain_count_of(rem, chridx);
             rank_precount + P::from_u32(rem_count)
         }
     }
     #[inline]
-    pub fn get_pre_rank_and_chridx(&self, mut pos: P) -> Option<(P, u8)> {
+    fn get_next_ranks(&self, pos_range: (P, P), chridx: u8) -> (P, P) {
+        let [(start_quot, start_rem), (end_quot, end_rem)] = [pos_range.0, pos_range.1].map(|mut pos| {
+            if pos < self.primary_index {
+                pos += P::ONE;
+            }
+            B::split_position(pos)
+        });
+        // The block of the rem of 0 is not counted, and can be after the last block
+        let block_of = |quot: usize| &self.blocks[quot.min(self.blocks.len() - 1)];
+        let [start_count, end_count] = B::get_remain_counts_of(
+            [block_of(start_quot), block_of(end_quot)],
+            [start_rem, end_rem],
+            chridx,
+        );
+        let rank_precount_of = |quot: usize| self.rank_checkpoints[quot * self.chr_count as usize + chridx as usize];
+        (
+            rank_precount_of(start_quot) + P::from_u32(start_count),
+            rank_precount_of(end_quot) + P::from_u32(end_count),
+        )
+    }
+    #[inline]
+    fn get_pre_rank_and_chridx(&self, mut pos: P) -> Option<(P, u8)> {
         if pos == self.primary_index - P::ONE {
             return None;
         } else if pos < self.primary_index {
             pos += P::ONE;
         }
-        let quot = pos.as_u32() / B::BLOCK_LEN;
-        let rem = pos.as_u32() % B::BLOCK_LEN;
+        let (quot, rem) = B::split_position(pos);
         
-        let block = &self.blocks[quot.as_usize()];
+        let block = &self.blocks[quot];
         let chridx = block.get_chridx_of(rem);
 
-        let rank_idx = quot.as_u32() * self.chr_count + chridx as u32;
-        let rank_precount = self.rank_checkpoints[rank_idx as usize];
+        let rank_idx = quot * self.chr_count as usize + chridx as usize;
+        let rank_precount = self.rank_checkpoints[rank_idx];
         if rem == 0 {
             Some((rank_precount, chridx))
         } else {
@@ -108,9 +211,12 @@ impl<P: Position, B: Block<P>> Bwm<P, B> {
         }
     }
 
-    pub fn chr_count(&self) -> u32 {
+    fn chr_count(&self) -> u32 {
         self.chr_count
     }
+    fn primary_index(&self) -> P {
+        self.primary_index
+    }
 }
 
 mod serialize;
@@ -0,0 +1,266 @@
+/*!
+C API (`capi` feature).
+
+The functions build, save, load, free, and query the index through an opaque [LtFmIndexHandle].
+The header is `include/lt_fm_index.h` in the repository, generated by `cbindgen`:
+```sh
+cbindgen --config cbindgen.toml --output include/lt_fm_index.h
+```
+The library for C is built with `cargo rustc --release --features capi --crate-type staticlib`
+(or `cdylib`).
+
+The index is [AnyLtFmIndex] of the position ([LTFM_POSITION_U32], [LTFM_POSITION_U64])
+and the backend ([LTFM_BLOCK2]..[LTFM_BLOCK6] of `u64` vectors, or [LTFM_WAVELET_TREE]),
+and the saved file is the same as [AnyLtFmIndex::save_to].
+Every function returns [LtFmStatus], and the panic is caught as [LtFmStatus::Panicked].
+*/
+use std::ffi::{CStr, c_char};
+use std::io::{Write, BufReader, BufWriter};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use super::{AnyLtFmIndex, PositionType, BackendType, BuildOptions};
+
+/// Position of `u32`.
+pub const LTFM_POSITION_U32: u32 = 32;
+/// Position of `u64`.
+pub const LTFM_POSITION_U64: u32 = 64;
+/// Backend of [BackendType::Block2], indexing 3 characters.
+pub const LTFM_BLOCK2: u32 = 2;
+/// Backend of [BackendType::Block3], indexing 7 characters.
+pub const LTFM_BLOCK3: u32 = 3;
+/// Backend of [BackendType::Block4], indexing 15 characters.
+pub const LTFM_BLOCK4: u32 = 4;
+/// Backend of [BackendType::Block5], indexing 31 characters.
+pub const LTFM_BLOCK5: u32 = 5;
+/// Backend of [BackendType::Block6], indexing 63 characters.
+pub const LTFM_BLOCK6: u32 = 6;
+/// Backend of [BackendType::WaveletTree], indexing 255 characters.
+pub const LTFM_WAVELET_TREE: u32 = 0;
+
+/// Result of the functions.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LtFmStatus {
+    Ok = 0,
+    /// A required pointer is null.
+    NullPointer = 1,
+    /// The combination of the position and backend is not supported.
+    InvalidType = 2,
+    /// The arguments of the build are invalid.
+    BuildFailed = 3,
+    /// Saving or loading failed.
+    IoFailed = 4,
+    /// The buffer is shorter than the count of the locations.
+    BufferTooSmall = 5,
+    /// The function panicked.
+    Panicked = 6,
+}
+
+/// Opaque handle of the index.
+pub struct LtFmIndexHandle {
+    index: AnyLtFmIndex,
+}
+
+/// Builds the index, and writes the handle to `out_index`.
+///
+/// The characters of each index are given as `characters_count` arrays
+/// (`characters[i]` of the length `characters_lens[i]`), as `characters_by_index` of [LtFmIndex::build].
+/// The handle must be freed by [ltfm_free].
+///
+/// # Safety
+/// The pointers must be valid for the given lengths, and `out_index` must be valid for writing.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ltfm_build(
+    text: *const u8,
+    text_len: usize,
+    characters: *const *const u8,
+    characters_lens: *const usize,
+    characters_count: usize,
+    position: u32,
+    backend: u32,
+    suffix_array_sampling_ratio: u64,
+    lookup_table_kmer_size: u32,
+    out_index: *mut *mut LtFmIndexHandle,
+) -> LtFmStatus {
+    guard(|| {
+        let text = unsafe { slice_from(text, text_len)? };
+        let characters = unsafe { slice_from(characters, characters_count)? };
+        let characters_lens = unsafe { slice_from(characters_lens, characters_count)? };
+        let characters_by_index = characters.iter().zip(characters_lens).map(|(&chrs, &len)| {
+            unsafe { slice_from(chrs, len) }
+        }).collect::<Result<Vec<_>, _>>()?;
+        let out_index = non_null(out_index)?;
+        let position = u8::try_from(position).ok().and_then(PositionType::from_code);
+        let backend = u8::try_from(backend).ok().and_then(BackendType::from_code);
+        let (Some(position), Some(backend)) = (position, backend) else {
+            return Err(LtFmStatus::InvalidType);
+        };
+        let index = AnyLtFmIndex::build(
+            text.to_vec(),
+            &characters_by_index,
+            position,
+            backend,
+            suffix_array_sampling_ratio,
+            lookup_table_kmer_size,
+            &BuildOptions::default(),
+        ).map_err(|_| LtFmStatus::BuildFailed)?;
+        unsafe { *out_index = Box::into_raw(Box::new(LtFmIndexHandle { index })) };
+        Ok(())
+    })
+}
+
+/// Saves the index to the file of the path, as [AnyLtFmIndex::save_to].
+///
+/// # Safety
+/// `index` must be a handle from this API, and `path` must be a nul-terminated UTF-8 string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ltfm_save(index: *const LtFmIndexHandle, path: *const c_char) -> LtFmStatus {
+    guard(|| {
+        let index = &unsafe { handle(index)? }.index;
+        let path = unsafe { path_from(path)? };
+        let save = || -> Result<(), std::io::Error> {
+            let mut writer = BufWriter::new(std::fs::File::create(path)?);
+            index.save_to(&mut writer)?;
+            writer.flush()
+        };
+        save().map_err(|_| LtFmStatus::IoFailed)
+    })
+}
+
+/// Loads the index saved by [ltfm_save] (or [AnyLtFmIndex::save_to]), and writes the handle to `out_index`.
+///
+/// The handle must be freed by [ltfm_free].
+///
+/// # Safety
+/// `path` must be a nul-terminated UTF-8 string, and `out_index` must be valid for writing.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ltfm_load(path: *const c_char, out_index: *mut *mut LtFmIndexHandle) -> LtFmStatus {
+    guard(|| {
+        let path = unsafe { path_from(path)? };
+        let out_index = non_null(out_index)?;
+        let reader = BufReader::new(std::fs::File::open(path).map_err(|_| LtFmStatus::IoFailed)?);
+        let index = AnyLtFmIndex::load_from(reader).map_err(|_| LtFmStatus::IoFailed)?;
+        unsafe { *out_index = Box::into_raw(Box::new(LtFmIndexHandle { index })) };
+        Ok(())
+    })
+}
+
+/// Frees the index. Null is ignored.
+///
+/// # Safety
+/// `index` must be a handle from this API, not freed before.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ltfm_free(index: *mut LtFmIndexHandle) {
+    if !index.is_null() {
+        drop(unsafe { Box::from_raw(index) });
+    }
+}
+
+/// Writes the length of the indexed text to `out_len`.
+///
+/// # Safety
+/// `index` must be a handle from this API, and `out_len` must be valid for writing.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ltfm_len_text(index: *const LtFmIndexHandle, out_len: *mut u64) -> LtFmStatus {
+    guard(|| {
+        let index = &unsafe { handle(index)? }.index;
+        let out_len = non_null(out_len)?;
+        unsafe { *out_len = index.len_text() };
+        Ok(())
+    })
+}
+
+/// Counts the occurrences of the pattern, and writes it to `out_count`.
+///
+/// # Safety
+/// `index` must be a handle from this API, `pattern` must be valid for `pattern_len` bytes,
+/// and `out_count` must be valid for writing.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ltfm_count(
+    index: *const LtFmIndexHandle,
+    pattern: *const u8,
+    pattern_len: usize,
+    out_count: *mut u64,
+) -> LtFmStatus {
+    guard(|| {
+        let index = &unsafe { handle(index)? }.index;
+        let pattern = unsafe { slice_from(pattern, pattern_len)? };
+        let out_count = non_null(out_count)?;
+        unsafe { *out_count = index.count(pattern) };
+        Ok(())
+    })
+}
+
+/// Locates the occurrences of the pattern into the caller-provided buffer, in ascending order.
+///
+/// The count of the occurrences is written to `out_count`.
+/// If it is larger than `locations_capacity`, the buffer is filled with the first locations
+/// and [LtFmStatus::BufferTooSmall] is returned.
+///
+/// # Safety
+/// `index` must be a handle from this API, `pattern` must be valid for `pattern_len` bytes,
+/// `locations` must be valid for writing `locations_capacity` values, and `out_count` must be valid for writing.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ltfm_locate(
+    index: *const LtFmIndexHandle,
+    pattern: *const u8,
+    pattern_len: usize,
+    locations: *mut u64,
+    locations_capacity: usize,
+    out_count: *mut usize,
+) -> LtFmStatus {
+    guard(|| {
+        let index = &unsafe { handle(index)? }.index;
+        let pattern = unsafe { slice_from(pattern, pattern_len)? };
+        let out_count = non_null(out_count)?;
+        if locations.is_null() && locations_capacity != 0 {
+            return Err(LtFmStatus::NullPointer);
+        }
+        let mut found = index.locate(pattern);
+        found.sort_unstable();
+        let written_count = found.len().min(locations_capacity);
+        if written_count != 0 {
+            unsafe { std::ptr::copy_nonoverlapping(found.as_ptr(), locations, written_count) };
+        }
+        unsafe { *out_count = found.len() };
+        if found.len() > locations_capacity {
+            Err(LtFmStatus::BufferTooSmall)
+        } else {
+            Ok(())
+        }
+    })
+}
+
+fn guard<F: FnOnce() -> Result<(), LtFmStatus>>(function: F) -> LtFmStatus {
+    match catch_unwind(AssertUnwindSafe(function)) {
+        Ok(Ok(())) => LtFmStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => LtFmStatus::Panicked,
+    }
+}
+fn non_null<T>(pointer: *mut T) -> Result<*mut T, LtFmStatus> {
+    if pointer.is_null() {
+        Err(LtFmStatus::NullPointer)
+    } else {
+        Ok(pointer)
+    }
+}
+// The null pointer is allowed for the empty slice
+unsafe fn slice_from<'a, T>(pointer: *const T, len: usize) -> Result<&'a [T], LtFmStatus> {
+    if len == 0 {
+        Ok(&[])
+    } else if pointer.is_null() {
+        Err(LtFmStatus::NullPointer)
+    } else {
+        Ok(unsafe { std::slice::from_raw_parts(pointer, len) })
+    }
+}
+unsafe fn handle<'a>(index: *const LtFmIndexHandle) -> Result<&'a LtFmIndexHandle, LtFmStatus> {
+    unsafe { index.as_ref() }.ok_or(LtFmStatus::NullPointer)
+}
+unsafe fn path_from<'a>(path: *const c_char) -> Result<&'a str, LtFmStatus> {
+    if path.is_null() {
+        return Err(LtFmStatus::NullPointer);
+    }
+    unsafe { CStr::from_ptr(path) }.to_str().map_err(|_| LtFmStatus::IoFailed)
+}
@@ -3,14 +3,15 @@ use std::pin::Pin;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
 use crate::core::AsyncSerialize;
-use super::ChrIdxTable;
+use super::{ChrIdxTable, NO_SEPARATOR};
 
 impl AsyncSerialize for ChrIdxTable {
     fn async_save_to<W>(&self, mut writer: Pin<&mut W>) -> impl Future<Output = Result<(), std::io::Error>> + Send where
         W: tokio::io::AsyncWrite + Send,
     {
         async move {
-            writer.as_mut().write_all(&self.0).await?;
+            writer.as_mut().write_all(&self.table).await?;
+            writer.as_mut().write_all(&[self.separator.unwrap_or(NO_SEPARATOR)]).await?;
             Ok(())
         }
     }
@@ -19,9 +20,28 @@ impl AsyncSerialize for ChrIdxTable {
         Self: Sized 
     {
         async move {
-            let mut buf = [0; 256];
-            reader.as_mut().read_exact(&mut buf).await?;
-            Ok(Self(buf))
+            let mut table = [0; 256];
+            reader.as_mut().read_exact(&mut table).await?;
+            let mut separator = [0; 1];
+            reader.as_mut().read_exact(&mut separator).await?;
+            Ok(Self {
+                table,
+                separator: (separator[0] != NO_SEPARATOR).then_some(separator[0]),
+            })
         }
     }
 }
+
+impl ChrIdxTable {
+    // Table saved without the format version, which has no separator
+    pub(crate) async fn async_load_unversioned_from<R>(mut reader: Pin<&mut R>) -> Result<Self, std::io::Error> where
+        R: tokio::io::AsyncRead + Send,
+    {
+        let mut table = [0; 256];
+        reader.as_mut().read_exact(&mut table).await?;
+        Ok(Self {
+            table,
+            separator: None,
+        })
+    }
+}
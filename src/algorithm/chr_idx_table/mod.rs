@@ -1,10 +1,48 @@
+use crate::core::errors::BuildError;
+use super::UnindexedCharacters;
+
 // A table mapping characters to their indices in the FM-index
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct ChrIdxTable(pub [u8; 256]);
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct ChrIdxTable {
+    #[cfg_attr(feature = "serde", serde(with = "serde_table"))]
+    pub table: [u8; 256],
+    // The index of the unindexed characters, if they are separators
+    pub separator: Option<u8>,
+}
+
+// Serialized in place of `separator` when it is `None`
+const NO_SEPARATOR: u8 = u8::MAX;
+// The indices of the groups and the unindexed characters, with the pidx, fit in `u8`
+const MAX_GROUP_COUNT: usize = u8::MAX as usize - 1;
 
 impl ChrIdxTable {
+    // Checks that the groups are not empty, fit in the index, and do not share characters
+    pub fn check_characters<T>(characters_by_index: &[T]) -> Result<(), BuildError>
+    where
+        T: AsRef<[u8]>,
+    {
+        if characters_by_index.is_empty() {
+            return Err(BuildError::EmptyAlphabet);
+        }
+        if characters_by_index.len() > MAX_GROUP_COUNT {
+            return Err(BuildError::TooManyCharacterGroups(characters_by_index.len()));
+        }
+        let mut given = [false; 256];
+        for &chr in characters_by_index.iter().flat_map(|chrs| chrs.as_ref()) {
+            if given[chr as usize] {
+                return Err(BuildError::DuplicateCharacter(chr as char));
+            }
+            given[chr as usize] = true;
+        }
+        Ok(())
+    }
     #[inline]
-    pub fn new_with_counting_chr<T>(characters_by_index: &[T]) -> (Self, u32)
+    pub fn new_with_counting_chr<T>(
+        characters_by_index: &[T],
+        unindexed_characters: UnindexedCharacters,
+    ) -> (Self, u32)
     where
         T: AsRef<[u8]>,
     {
@@ -13,14 +51,38 @@ impl ChrIdxTable {
         characters_by_index.iter().enumerate().for_each(|(idx, chr)| {
             chr.as_ref().iter().for_each(|x| table[*x as usize] = idx as u8);
         });
-        (Self(table), chr_count)
+        let separator = match unindexed_characters {
+            UnindexedCharacters::Same => None,
+            UnindexedCharacters::Separator => Some((chr_count - 1) as u8),
+        };
+        (Self { table, separator }, chr_count)
     }
     #[inline(always)]
     pub fn idx_of(&self, chr: u8) -> u8 {
-        unsafe { *self.0.get_unchecked(chr as usize) }
+        unsafe { *self.table.get_unchecked(chr as usize) }
+    }
+    #[inline]
+    pub fn is_separator(&self, chridx: u8) -> bool {
+        self.separator == Some(chridx)
+    }
+    // True if the pattern can not be matched, because of the separator
+    #[inline]
+    pub fn has_separator(&self, pattern: &[u8]) -> bool {
+        match self.separator {
+            Some(separator) => pattern.iter().any(|&chr| self.idx_of(chr) == separator),
+            None => false,
+        }
+    }
+    pub fn unindexed_characters(&self) -> UnindexedCharacters {
+        match self.separator {
+            Some(_) => UnindexedCharacters::Separator,
+            None => UnindexedCharacters::Same,
+        }
     }
 }
 
 mod serialize;
 #[cfg(feature = "async-io")]
-mod async_serialize;
\ No newline at end of file
+mod async_serialize;
+#[cfg(feature = "serde")]
+mod serde_table;
@@ -0,0 +1,11 @@
+// `serde` supports the arrays up to 32 elements, so the table is (de)serialized as bytes.
+use serde::{Serializer, Deserializer, Deserialize, de::Error};
+
+pub fn serialize<S: Serializer>(table: &[u8; 256], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_bytes(table)
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 256], D::Error> {
+    let bytes = Vec::<u8>::deserialize(deserializer)?;
+    bytes.try_into().map_err(|bytes: Vec<u8>| D::Error::invalid_length(bytes.len(), &"256 bytes"))
+}
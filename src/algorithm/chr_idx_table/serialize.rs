@@ -1,22 +1,42 @@
 use crate::core::Serialize;
-use super::ChrIdxTable;
+use super::{ChrIdxTable, NO_SEPARATOR};
 
 impl Serialize for ChrIdxTable {
     fn save_to<W>(&self, writer: &mut W) -> Result<(), std::io::Error> where
         W: std::io::Write
     {
-        writer.write_all(&self.0)?;
+        writer.write_all(&self.table)?;
+        writer.write_all(&[self.separator.unwrap_or(NO_SEPARATOR)])?;
         Ok(())
     }
     fn load_from<R>(reader: &mut R) -> Result<Self, std::io::Error> where
         R: std::io::Read,
         Self: Sized
     {
-        let mut buf = [0; 256];
-        reader.read_exact(&mut buf)?;
-        Ok(Self(buf))
+        let mut table = [0; 256];
+        reader.read_exact(&mut table)?;
+        let mut separator = [0; 1];
+        reader.read_exact(&mut separator)?;
+        Ok(Self {
+            table,
+            separator: (separator[0] != NO_SEPARATOR).then_some(separator[0]),
+        })
     }
     fn encoded_len(&self) -> usize {
-        256
+        256 + 1
+    }
+}
+
+impl ChrIdxTable {
+    // Table saved without the format version, which has no separator
+    pub(crate) fn load_unversioned_from<R>(reader: &mut R) -> Result<Self, std::io::Error> where
+        R: std::io::Read,
+    {
+        let mut table = [0; 256];
+        reader.read_exact(&mut table)?;
+        Ok(Self {
+            table,
+            separator: None,
+        })
     }
 }
@@ -0,0 +1,21 @@
+// Bases of the IUPAC nucleotide code, or the character itself if it is not the code
+pub fn bases_of_code(chr: &u8) -> &[u8] {
+    match chr.to_ascii_uppercase() {
+        b'A' => b"A",
+        b'C' => b"C",
+        b'G' => b"G",
+        b'T' | b'U' => b"T",
+        b'R' => b"AG",
+        b'Y' => b"CT",
+        b'S' => b"CG",
+        b'W' => b"AT",
+        b'K' => b"GT",
+        b'M' => b"AC",
+        b'B' => b"CGT",
+        b'D' => b"AGT",
+        b'H' => b"ACT",
+        b'V' => b"ACG",
+        b'N' => b"ACGT",
+        _ => std::slice::from_ref(chr),
+    }
+}
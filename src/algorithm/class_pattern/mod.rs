@@ -0,0 +1,82 @@
+/// Pattern of which each position is a set of characters (e.g. IUPAC degenerate nucleotide codes).
+///
+/// The members of the sets are the indices of the characters, which can be obtained from the decoding table
+/// (e.g. [crate::LtFmIndex::decoding_table]).
+/// Searched with [crate::LtFmIndex::count_class_pattern] and [crate::LtFmIndex::locate_class_pattern].
+///
+/// # Example
+/// ```rust
+/// use lt_fm_index::{LtFmIndex, ClassPattern};
+/// use lt_fm_index::blocks::Block3;
+///
+/// let text = b"ACGTTAGTACCT".to_vec();
+/// let lt_fm_index = LtFmIndex::<u32, Block3<u64>>::build(
+///     text, &[b"A", b"C", b"G", b"T"], 2, 3,
+/// ).unwrap();
+/// // N: A, C, G, or T
+/// let pattern = ClassPattern::iupac(b"NGT", lt_fm_index.decoding_table());
+/// let mut locations = lt_fm_index.locate_class_pattern(&pattern);
+/// locations.sort();
+/// assert_eq!(locations, vec![1, 5]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassPattern {
+    sets: Vec<ChrIdxSet>,
+}
+
+// Bit set of the chridx
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ChrIdxSet([u64; 4]);
+
+impl ChrIdxSet {
+    fn insert(&mut self, chridx: u8) {
+        self.0[chridx as usize / 64] |= 1 << (chridx % 64);
+    }
+    pub fn contains(&self, chridx: u8) -> bool {
+        (self.0[chridx as usize / 64] >> (chridx % 64)) & 1 == 1
+    }
+}
+
+impl ClassPattern {
+    /// Makes a pattern from the sets of the character indices of each position.
+    pub fn from_chridx_sets<I, S>(sets: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: IntoIterator<Item = u8>,
+    {
+        let sets = sets.into_iter().map(|set| {
+            let mut chridx_set = ChrIdxSet::default();
+            set.into_iter().for_each(|chridx| chridx_set.insert(chridx));
+            chridx_set
+        }).collect();
+        Self { sets }
+    }
+    /// Makes a pattern from the sets of the characters of each position, using the decoding table.
+    pub fn from_character_sets<S: AsRef<[u8]>>(sets: &[S], decoding_table: &[u8; 256]) -> Self {
+        Self::from_chridx_sets(sets.iter().map(|set| {
+            set.as_ref().iter().map(|&chr| decoding_table[chr as usize])
+        }))
+    }
+    /// Makes a pattern from the IUPAC nucleotide codes, for the index of `A`, `C`, `G`, and `T`.
+    ///
+    /// `U` is the same as `T`, and the case is ignored.
+    /// Other characters than the IUPAC codes are used as they are.
+    pub fn iupac(pattern: &[u8], decoding_table: &[u8; 256]) -> Self {
+        let sets: Vec<&[u8]> = pattern.iter().map(iupac::bases_of_code).collect();
+        Self::from_character_sets(&sets, decoding_table)
+    }
+
+    /// Length of the pattern.
+    pub fn len(&self) -> usize {
+        self.sets.len()
+    }
+    /// Returns `true` if the pattern has no position.
+    pub fn is_empty(&self) -> bool {
+        self.sets.is_empty()
+    }
+    pub(crate) fn sets(&self) -> &[ChrIdxSet] {
+        &self.sets
+    }
+}
+
+mod iupac;
@@ -1,7 +1,7 @@
 use std::pin::Pin;
 
 use crate::core::{Position, AsyncSerialize};
-use super::CountArray;
+use super::{CountArray, KmerCountTable};
 use capwriter::{AsyncSave, AsyncLoad};
 
 impl<P: Position> AsyncSerialize for CountArray<P> {
@@ -16,10 +16,19 @@ impl<P: Position> AsyncSerialize for CountArray<P> {
             self.count_table.save_as_ne(writer.as_mut()).await?;
 
             // kmer_count_table
-            self.kmer_count_table.save_as_ne(writer.as_mut()).await?;
-
-            // multiplier
-            self.multiplier.save_as_ne(writer.as_mut()).await?;
+            //  - The empty dense table marks the sparse table, which follows the multiplier.
+            match &self.kmer_count_table {
+                KmerCountTable::Dense(accumed_counts) => {
+                    accumed_counts.save_as_ne(writer.as_mut()).await?;
+                    self.multiplier.save_as_ne(writer.as_mut()).await?;
+                },
+                KmerCountTable::Sparse { kmers, accumed_counts } => {
+                    Vec::<P>::new().save_as_ne(writer.as_mut()).await?;
+                    self.multiplier.save_as_ne(writer.as_mut()).await?;
+                    kmers.save_as_ne(writer.as_mut()).await?;
+                    accumed_counts.save_as_ne(writer.as_mut()).await?;
+                },
+            }
 
             Ok(())
         }
@@ -36,10 +45,15 @@ impl<P: Position> AsyncSerialize for CountArray<P> {
             let count_table = Vec::<P>::load_as_ne(reader.as_mut()).await?;
 
             // kmer_count_table
-            let kmer_count_table = Vec::<P>::load_as_ne(reader.as_mut()).await?;
-
-            // multiplier
+            let dense_accumed_counts = Vec::<P>::load_as_ne(reader.as_mut()).await?;
             let multiplier = Vec::<usize>::load_as_ne(reader.as_mut()).await?;
+            let kmer_count_table = if dense_accumed_counts.is_empty() {
+                let kmers = Vec::<u64>::load_as_ne(reader.as_mut()).await?;
+                let accumed_counts = Vec::<P>::load_as_ne(reader.as_mut()).await?;
+                KmerCountTable::Sparse { kmers, accumed_counts }
+            } else {
+                KmerCountTable::Dense(dense_accumed_counts)
+            };
 
             Ok(Self {
                 kmer_size,
@@ -0,0 +1,135 @@
+use crate::core::Position;
+use crate::algorithm::LookupTable;
+use fxhash::FxHashMap;
+
+// Accumulated counts of the k-mers, indexed by the k-mer count table index
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub enum KmerCountTable<P: Position> {
+    // Count of every k-mer, including the k-mers not in the text
+    Dense(Vec<P>),
+    // Only the k-mers in the text (sorted)
+    Sparse {
+        kmers: Vec<u64>,
+        accumed_counts: Vec<P>,
+    },
+}
+
+// Counter of the k-mers used while building `KmerCountTable`
+pub enum KmerCounter<P: Position> {
+    Dense(Vec<P>),
+    Sparse(FxHashMap<usize, P>),
+}
+
+impl<P: Position> KmerCounter<P> {
+    pub fn new(lookup_table: LookupTable, table_length: usize) -> Self {
+        match lookup_table {
+            LookupTable::Dense => Self::Dense(vec![P::ZERO; table_length]),
+            LookupTable::Sparse => Self::Sparse(FxHashMap::default()),
+        }
+    }
+    #[inline]
+    pub fn add(&mut self, table_index: usize) {
+        self.add_count(table_index, P::ONE);
+    }
+    #[inline]
+    pub fn add_count(&mut self, table_index: usize, count: P) {
+        match self {
+            Self::Dense(counts) => counts[table_index] += count,
+            Self::Sparse(counts) => *counts.entry(table_index).or_insert(P::ZERO) += count,
+        }
+    }
+    // The k-mer must be added before
+    pub fn remove(&mut self, table_index: usize) {
+        match self {
+            Self::Dense(counts) => counts[table_index] = counts[table_index] - P::ONE,
+            Self::Sparse(counts) => {
+                let count = counts.get_mut(&table_index).unwrap();
+                *count = *count - P::ONE;
+            },
+        }
+    }
+    pub fn into_table(self) -> KmerCountTable<P> {
+        match self {
+            Self::Dense(mut counts) => {
+                accumulate(&mut counts);
+                KmerCountTable::Dense(counts)
+            },
+            Self::Sparse(counts) => {
+                let mut counts: Vec<(usize, P)> = counts.into_iter().filter(|(_, count)| *count != P::ZERO).collect();
+                counts.sort_unstable_by_key(|(kmer, _)| *kmer);
+                let (kmers, mut accumed_counts): (Vec<u64>, Vec<P>) = counts.into_iter()
+                    .map(|(kmer, count)| (kmer as u64, count))
+                    .unzip();
+                accumulate(&mut accumed_counts);
+                KmerCountTable::Sparse { kmers, accumed_counts }
+            },
+        }
+    }
+}
+
+impl<P: Position> KmerCountTable<P> {
+    // Count of the k-mers of which index is less than or equal to `table_index`
+    #[inline]
+    pub fn accumed_count(&self, table_index: usize) -> P {
+        match self {
+            Self::Dense(accumed_counts) => accumed_counts[table_index],
+            Self::Sparse { kmers, accumed_counts } => {
+                match kmers.partition_point(|&kmer| kmer <= table_index as u64) {
+                    0 => P::ZERO,
+                    count => accumed_counts[count - 1],
+                }
+            },
+        }
+    }
+    // The stored accumulated counts
+    pub fn stored_accumed_counts(&self) -> &[P] {
+        match self {
+            Self::Dense(accumed_counts) => accumed_counts,
+            Self::Sparse { accumed_counts, .. } => accumed_counts,
+        }
+    }
+    // Index of `stored_accumed_counts` having the accumulated count of `table_index`
+    #[inline]
+    pub fn stored_idx(&self, table_index: usize) -> usize {
+        match self {
+            Self::Dense(_) => table_index,
+            Self::Sparse { kmers, .. } => {
+                kmers.partition_point(|&kmer| kmer <= table_index as u64).saturating_sub(1)
+            },
+        }
+    }
+    pub fn lookup_table(&self) -> LookupTable {
+        match self {
+            Self::Dense(_) => LookupTable::Dense,
+            Self::Sparse { .. } => LookupTable::Sparse,
+        }
+    }
+    // Adds the counts of the k-mers in this table to the counter
+    pub fn add_counts_to(&self, counter: &mut KmerCounter<P>) {
+        let mut pre_accumed_count = P::ZERO;
+        let mut add = |table_index: usize, accumed_count: P| {
+            if accumed_count != pre_accumed_count {
+                counter.add_count(table_index, accumed_count - pre_accumed_count);
+                pre_accumed_count = accumed_count;
+            }
+        };
+        match self {
+            Self::Dense(accumed_counts) => {
+                accumed_counts.iter().enumerate().for_each(|(table_index, &accumed_count)| add(table_index, accumed_count));
+            },
+            Self::Sparse { kmers, accumed_counts } => {
+                kmers.iter().zip(accumed_counts).for_each(|(&kmer, &accumed_count)| add(kmer as usize, accumed_count));
+            },
+        }
+    }
+}
+
+pub fn accumulate<P: Position>(count_table: &mut [P]) {
+    let mut accumed_count = P::ZERO;
+    count_table.iter_mut().for_each(|count| {
+        *count += accumed_count;
+        accumed_count = *count;
+    });
+}
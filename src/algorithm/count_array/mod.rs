@@ -1,76 +1,153 @@
 use crate::core::Position;
-use super::ChrIdxTable;
+use crate::core::errors::BuildError;
+use super::{ChrIdxTable, LookupTable, BuildMonitor, BuildPhase};
+
+mod kmer_count_table;
+pub use kmer_count_table::KmerCountTable;
+use kmer_count_table::{KmerCounter, accumulate};
 
 // A data structure for storing and querying character counts in the FM-index
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 pub struct CountArray<P: Position> {
     pub(crate) kmer_size: u32,
     pub(crate) count_table: Vec<P>,
-    pub(crate) kmer_count_table: Vec<P>,
+    pub(crate) kmer_count_table: KmerCountTable<P>,
     pub(crate) multiplier: Vec<usize>,
 }
 
+// Builds `CountArray` with the characters of the text in order
+pub struct CountArrayBuilder<P: Position> {
+    kmer_size: u32,
+    chr_with_pidx_count: usize,
+    count_table: Vec<P>,
+    kmer_counter: KmerCounter<P>,
+    multiplier: Vec<usize>,
+    // Index of the last k-mer pushed, and the count of the characters pushed
+    table_index: usize,
+    pushed_count: usize,
+}
+
+impl<P: Position> CountArrayBuilder<P> {
+    pub fn new(chr_count: u32, lookup_table_kmer_size: u32, lookup_table: LookupTable) -> Self {
+        let chr_with_pidx_count = (chr_count + 1) as usize;
+        let table_length: usize = (chr_with_pidx_count).pow(lookup_table_kmer_size);
+        let multiplier: Vec<usize> = {
+            (0..lookup_table_kmer_size).map(|pos| {
+                (chr_with_pidx_count).pow(pos)
+            }).rev().collect()
+        };
+        Self {
+            kmer_size: lookup_table_kmer_size,
+            chr_with_pidx_count,
+            count_table: vec![P::ZERO; chr_with_pidx_count],
+            kmer_counter: KmerCounter::new(lookup_table, table_length),
+            multiplier,
+            table_index: 0,
+            pushed_count: 0,
+        }
+    }
+    #[inline]
+    pub fn push(&mut self, chridx: u8) {
+        // Add count to counts
+        self.count_table[chridx as usize + 1] += P::ONE;
+        // Add count of the k-mer ending with this character to lookup table
+        self.table_index = (self.table_index % self.multiplier[0]) * self.chr_with_pidx_count + chridx as usize + 1;
+        self.pushed_count += 1;
+        if self.pushed_count >= self.kmer_size as usize {
+            self.kmer_counter.add(self.table_index);
+        }
+    }
+    pub fn finish(mut self) -> CountArray<P> {
+        // The last k-mers are padded with the sentinel (chridxwp: 0)
+        let kmer_size = self.kmer_size as usize;
+        let padded_count = if self.pushed_count < kmer_size {
+            if self.pushed_count != 0 {
+                self.table_index *= self.chr_with_pidx_count.pow((kmer_size - self.pushed_count) as u32);
+                self.kmer_counter.add(self.table_index);
+            }
+            self.pushed_count.saturating_sub(1)
+        } else {
+            kmer_size - 1
+        };
+        for _ in 0..padded_count {
+            self.table_index = (self.table_index % self.multiplier[0]) * self.chr_with_pidx_count;
+            self.kmer_counter.add(self.table_index);
+        }
+        accumulate(&mut self.count_table);
+
+        CountArray {
+            kmer_size: self.kmer_size,
+            count_table: self.count_table,
+            kmer_count_table: self.kmer_counter.into_table(),
+            multiplier: self.multiplier,
+        }
+    }
+}
+
 impl<P: Position> CountArray<P> {
     // Build
     pub fn new_while_encoding_text_to_chridxwp(
-        text: &mut Vec<u8>,
+        text: &mut [u8],
         chr_idx_table: &ChrIdxTable,
         chr_count: u32,
         lookup_table_kmer_size: u32,
-    ) -> Self {
-        let chr_with_pidx_count = (chr_count + 1) as usize;
-        let mut count_table: Vec<P> = vec![P::ZERO; chr_with_pidx_count];
-
-        let (kmer_count_table, multiplier) = {
-            let table_length: usize = (chr_with_pidx_count).pow(lookup_table_kmer_size);
-            let mut kmer_count_table: Vec<P> = vec![P::ZERO; table_length];
-            let mut table_index: usize = 0;
-    
-            let multiplier: Vec<usize> = {
-                (0..lookup_table_kmer_size).map(|pos| {
-                    (chr_with_pidx_count).pow(pos)
-                }).rev().collect()
-            };
-    
-            let index_for_each_chr: Vec<usize> = {
-                (0..chr_count as usize).map(|chridx| {
-                    multiplier[0] * (chridx + 1)
-                }).collect()
-            };
-    
-            text.iter_mut().rev().for_each(|chr| {
-                let chridx = chr_idx_table.idx_of(*chr);
-                *chr = chridx + 1;
-                // Add count to counts
-                count_table[chridx as usize + 1] += P::ONE;
-                // Add count to lookup table
-                table_index /= chr_with_pidx_count;
-                table_index += index_for_each_chr[chridx as usize];
-                kmer_count_table[table_index] += P::ONE;
-            });
-
-            Self::accumulate_count_table(&mut kmer_count_table);
-
-            (kmer_count_table, multiplier)
-        };
+        lookup_table: LookupTable,
+        monitor: &mut BuildMonitor,
+    ) -> Result<Self, BuildError> {
+        monitor.start_phase(BuildPhase::Encoding)?;
+        let text_len = text.len();
+        let mut builder = CountArrayBuilder::new(chr_count, lookup_table_kmer_size, lookup_table);
+        for (idx, chr) in text.iter_mut().enumerate() {
+            monitor.progress(BuildPhase::Encoding, idx, text_len)?;
+            let chridx = chr_idx_table.idx_of(*chr);
+            *chr = chridx + 1;
+            builder.push(chridx);
+        }
+        let count_array = builder.finish();
+        monitor.finish_phase(BuildPhase::Encoding)?;
+        Ok(count_array)
+    }
+    // Counts of the concatenated text of `a` and `b`.
+    // `a_tail` is the last (k-1) characters of `a` and `b_head` is the first (k-1) characters of `b` (in chridx),
+    // or the whole text if shorter: the k-mers starting in `a_tail` are padded in `a`, but span the join in the concatenation.
+    pub fn merge(a: &Self, b: &Self, a_tail: &[u8], b_head: &[u8]) -> Self {
+        let count_table = a.count_table.iter().zip(&b.count_table).map(|(&x, &y)| x + y).collect();
 
-        Self::accumulate_count_table(&mut count_table);
+        let table_length = a.multiplier[0] * (a.chr_count() + 1) as usize;
+        let mut kmer_counter = KmerCounter::new(a.lookup_table(), table_length);
+        a.kmer_count_table.add_counts_to(&mut kmer_counter);
+        b.kmer_count_table.add_counts_to(&mut kmer_counter);
+        let joined: Vec<u8> = a_tail.iter().chain(b_head).copied().collect();
+        (0..a_tail.len()).for_each(|start| {
+            kmer_counter.remove(a.table_index_of_kmer(&a_tail[start..]));
+            kmer_counter.add(a.table_index_of_kmer(&joined[start..]));
+        });
 
         Self {
-            kmer_size: lookup_table_kmer_size,
+            kmer_size: a.kmer_size,
             count_table,
-            kmer_count_table,
-            multiplier,
+            kmer_count_table: kmer_counter.into_table(),
+            multiplier: a.multiplier.clone(),
         }
     }
-    fn accumulate_count_table(count_table: &mut [P]) {
-        let mut accumed_count = P::ZERO;
-        count_table.iter_mut().for_each(|count| {
-            accumed_count += *count;
-            *count = accumed_count;
-        });
+    // Index of the k-mer at the start of the text in chridx, padded if shorter than k
+    fn table_index_of_kmer(&self, text: &[u8]) -> usize {
+        text.iter().zip(self.multiplier.iter())
+            .map(|(&chridx, &mul_of_pos)| (chridx as usize + 1) * mul_of_pos)
+            .sum()
+    }
+    // Checks that the indices of the k-mer count table fit in `usize`
+    pub fn table_length_fits(chr_count: u32, lookup_table_kmer_size: u32) -> bool {
+        ((chr_count + 1) as usize).checked_pow(lookup_table_kmer_size).is_some()
     }
     
+    // Count of the characters without the pidx
+    pub fn chr_count(&self) -> u32 {
+        self.count_table.len() as u32 - 1
+    }
+
     // Locate
     pub fn get_precount(&self, chridx: usize) -> P {
         self.count_table[chridx]
@@ -80,36 +157,61 @@ impl<P: Position> CountArray<P> {
         pattern: &[u8],
         chr_idx_table: &ChrIdxTable,
     ) -> ((P, P), usize) {
-        let pattern_len = pattern.len();
+        let (pos_range, _, idx) = self.get_initial_pos_range_and_table_idx_of_pattern(pattern, chr_idx_table);
+        (pos_range, idx)
+    }
+    // Also returns the index of the k-mer count table of the end of the range
+    pub fn get_initial_pos_range_and_table_idx_of_pattern(
+        &self,
+        pattern: &[u8],
+        chr_idx_table: &ChrIdxTable,
+    ) -> ((P, P), usize, usize) {
+        self.get_initial_pos_range_and_table_idx_of_chridx(pattern.len(), |idx| chr_idx_table.idx_of(pattern[idx]))
+    }
+    // Same as above, with the chridx of each index of the pattern
+    pub fn get_initial_pos_range_and_table_idx_of_chridx(
+        &self,
+        pattern_len: usize,
+        chridx_of: impl Fn(usize) -> u8,
+    ) -> ((P, P), usize, usize) {
         if pattern_len < self.kmer_size as usize {
-            let start_idx = self.get_idx_of_kmer_count_table(pattern, chr_idx_table);
+            let start_idx = self.get_idx_of_kmer_count_table(0..pattern_len, chridx_of);
             let gap_btw_unsearched_kmer = self.multiplier[pattern_len - 1] - 1;
             let end_idx = start_idx + gap_btw_unsearched_kmer;
 
-            let pos_range = (self.kmer_count_table[start_idx -1], self.kmer_count_table[end_idx]);
-            (pos_range, 0)
+            let pos_range = (
+                self.kmer_count_table.accumed_count(start_idx -1),
+                self.kmer_count_table.accumed_count(end_idx),
+            );
+            (pos_range, end_idx, 0)
         } else {
-            let sliced_pattern = &pattern[pattern.len() - self.kmer_size as usize ..];
-            let start_idx = self.get_idx_of_kmer_count_table(sliced_pattern, chr_idx_table);
+            let sliced_start = pattern_len - self.kmer_size as usize;
+            let start_idx = self.get_idx_of_kmer_count_table(sliced_start..pattern_len, chridx_of);
 
-            let pos_range = (self.kmer_count_table[start_idx -1], self.kmer_count_table[start_idx]);
-            (pos_range, pattern_len - self.kmer_size as usize)
+            let pos_range = (
+                self.kmer_count_table.accumed_count(start_idx -1),
+                self.kmer_count_table.accumed_count(start_idx),
+            );
+            (pos_range, start_idx, sliced_start)
         }
     }
     fn get_idx_of_kmer_count_table(
         &self,
-        sliced_pattern: &[u8],
-        chr_idx_table: &ChrIdxTable,
+        sliced_range: std::ops::Range<usize>,
+        chridx_of: impl Fn(usize) -> u8,
     ) -> usize {
-        sliced_pattern.iter().zip(self.multiplier.iter())
-            .map(|(&chr, &mul_of_pos)| {
-                (chr_idx_table.idx_of(chr) + 1) as usize * mul_of_pos
+        sliced_range.zip(self.multiplier.iter())
+            .map(|(idx, &mul_of_pos)| {
+                (chridx_of(idx) + 1) as usize * mul_of_pos
             }).sum()
     }
 
     pub fn kmer_size(&self) -> u32 {
         self.kmer_size
     }
+    pub fn lookup_table(&self) -> LookupTable {
+        self.kmer_count_table.lookup_table()
+    }
 }
 
 mod serialize;
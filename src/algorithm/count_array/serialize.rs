@@ -1,5 +1,5 @@
 use crate::core::{Position, Serialize};
-use super::CountArray;
+use super::{CountArray, KmerCountTable};
 use capwriter::{Save, Load};
 
 impl<P: Position> Serialize for CountArray<P> {
@@ -13,10 +13,19 @@ impl<P: Position> Serialize for CountArray<P> {
         self.count_table.save_as_ne(writer)?;
 
         // kmer_count_table
-        self.kmer_count_table.save_as_ne(writer)?;
-
-        // multiplier
-        self.multiplier.save_as_ne(writer)?;
+        //  - The empty dense table marks the sparse table, which follows the multiplier.
+        match &self.kmer_count_table {
+            KmerCountTable::Dense(accumed_counts) => {
+                accumed_counts.save_as_ne(writer)?;
+                self.multiplier.save_as_ne(writer)?;
+            },
+            KmerCountTable::Sparse { kmers, accumed_counts } => {
+                Vec::<P>::new().save_as_ne(writer)?;
+                self.multiplier.save_as_ne(writer)?;
+                kmers.save_as_ne(writer)?;
+                accumed_counts.save_as_ne(writer)?;
+            },
+        }
 
         Ok(())
     }
@@ -31,10 +40,15 @@ impl<P: Position> Serialize for CountArray<P> {
         let count_table = Vec::<P>::load_as_ne(reader)?;
 
         // kmer_count_table
-        let kmer_count_table = Vec::<P>::load_as_ne(reader)?;
-
-        // multiplier
+        let dense_accumed_counts = Vec::<P>::load_as_ne(reader)?;
         let multiplier = Vec::<usize>::load_as_ne(reader)?;
+        let kmer_count_table = if dense_accumed_counts.is_empty() {
+            let kmers = Vec::<u64>::load_as_ne(reader)?;
+            let accumed_counts = Vec::<P>::load_as_ne(reader)?;
+            KmerCountTable::Sparse { kmers, accumed_counts }
+        } else {
+            KmerCountTable::Dense(dense_accumed_counts)
+        };
 
         Ok(Self {
             kmer_size,
@@ -46,7 +60,14 @@ impl<P: Position> Serialize for CountArray<P> {
     fn encoded_len(&self) -> usize {
         4 // kmer_size
         + self.count_table.encoded_len() // count_table
-        + self.kmer_count_table.encoded_len() // kmer_count_table
         + self.multiplier.encoded_len() // multiplier
+        + match &self.kmer_count_table { // kmer_count_table
+            KmerCountTable::Dense(accumed_counts) => accumed_counts.encoded_len(),
+            KmerCountTable::Sparse { kmers, accumed_counts } => {
+                Vec::<P>::new().encoded_len()
+                + kmers.encoded_len()
+                + accumed_counts.encoded_len()
+            },
+        }
     }
 }
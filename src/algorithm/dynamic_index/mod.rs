@@ -0,0 +1,234 @@
+use crate::core::{
+    Position,
+    errors::{BuildError, QueryError},
+};
+use super::{LtFmIndex, BwmBackend, BuildOptions, ChrIdxTable, EncodedPattern, check_text_len};
+
+/// Updatable FM-index, appending new texts without the full rebuild.
+///
+/// The text is indexed with a log-structured set of static [LtFmIndex] segments,
+/// which are queried together. When a segment is not much smaller than the segment before it,
+/// the two are merged by rebuilding, so the count of the segments stays logarithmic.
+/// The occurrences across the boundaries of the segments are found from the text,
+/// so `count` and `locate` return the same results as the [LtFmIndex] built on the whole text.
+///
+/// The whole text is kept in memory to rebuild the segments.
+///
+/// # Example
+/// ```rust
+/// use lt_fm_index::{DynamicLtFmIndex, BuildOptions};
+/// use lt_fm_index::blocks::Block3;
+///
+/// let mut index = DynamicLtFmIndex::<u32, Block3<u64>>::new(
+///     &[b"A", b"C", b"G", b"T"], 2, 3, BuildOptions::default(),
+/// ).unwrap();
+/// index.insert_text(b"ACGTAC").unwrap();
+/// index.insert_text(b"GTACGT").unwrap();
+/// // "ACGTACGTACGT"
+/// assert_eq!(index.count(b"ACGT"), 3);
+/// ```
+pub struct DynamicLtFmIndex<P: Position, B: BwmBackend<P>> {
+    characters_by_index: Vec<Vec<u8>>,
+    suffix_array_sampling_ratio: P,
+    lookup_table_kmer_size: u32,
+    options: BuildOptions,
+    chr_idx_table: ChrIdxTable,
+    text: Vec<u8>,
+    segments: Vec<Segment<P, B>>,
+}
+
+struct Segment<P: Position, B: BwmBackend<P>> {
+    start: usize,
+    end: usize,
+    index: LtFmIndex<P, B>,
+}
+
+impl<P: Position, B: BwmBackend<P>> DynamicLtFmIndex<P, B> {
+    /// Makes an empty index.
+    ///
+    /// The arguments are used to build each segment (see [LtFmIndex::build_with_options]),
+    /// and are checked as [LtFmIndex::build_with_options] does.
+    pub fn new<T>(
+        characters_by_index: &[T],
+        suffix_array_sampling_ratio: P,
+        lookup_table_kmer_size: u32,
+        options: BuildOptions,
+    ) -> Result<Self, BuildError>
+    where
+        T: AsRef<[u8]>,
+    {
+        let (chr_idx_table, _) = LtFmIndex::<P, B>::prepare_build(
+            characters_by_index,
+            suffix_array_sampling_ratio,
+            lookup_table_kmer_size,
+            &options,
+        )?;
+        Ok(Self {
+            characters_by_index: characters_by_index.iter().map(|chr| chr.as_ref().to_vec()).collect(),
+            suffix_array_sampling_ratio,
+            lookup_table_kmer_size,
+            options,
+            chr_idx_table,
+            text: Vec::new(),
+            segments: Vec::new(),
+        })
+    }
+    /// Appends the text to the end of the indexed text.
+    ///
+    /// Returns [BuildError::TextTooLong] if the whole text is too long for the position.
+    pub fn insert_text(&mut self, text: &[u8]) -> Result<(), BuildError> {
+        if text.is_empty() {
+            return Ok(());
+        }
+        check_text_len::<P>(self.text.len() + text.len())?;
+        let text_len = self.text.len();
+        self.text.extend_from_slice(text);
+        // Merge with the last segments, which are not much larger than the new one
+        let mut start = text_len;
+        let mut kept_count = self.segments.len();
+        while kept_count > 0 && self.segments[kept_count - 1].len() <= 2 * (self.text.len() - start) {
+            kept_count -= 1;
+            start = self.segments[kept_count].start;
+        }
+        match self.build_segment(start, self.text.len()) {
+            Ok(segment) => {
+                self.segments.truncate(kept_count);
+                self.segments.push(segment);
+                Ok(())
+            },
+            Err(error) => {
+                self.text.truncate(text_len);
+                Err(error)
+            },
+        }
+    }
+    /// Merges all segments into one.
+    pub fn compact(&mut self) -> Result<(), BuildError> {
+        if self.segments.len() > 1 {
+            let segment = self.build_segment(0, self.text.len())?;
+            self.segments = vec![segment];
+        }
+        Ok(())
+    }
+    fn build_segment(&self, start: usize, end: usize) -> Result<Segment<P, B>, BuildError> {
+        let index = LtFmIndex::build_with_options(
+            self.text[start..end].to_vec(),
+            &self.characters_by_index,
+            self.suffix_array_sampling_ratio,
+            self.lookup_table_kmer_size,
+            &self.options,
+        )?;
+        Ok(Segment { start, end, index })
+    }
+
+    /// Counts the number of occurrences of a pattern in the indexed text.
+    pub fn count(&self, pattern: &[u8]) -> P {
+        let count_in_segments = self.segments.iter().fold(P::ZERO, |count, segment| {
+            count + segment.index.count(pattern)
+        });
+        count_in_segments + P::from_usize(self.locations_across_segments(&self.raw_index_of(pattern)).count())
+    }
+    /// Locates all occurrences of a pattern in the indexed text.
+    ///
+    /// The locations are not in order.
+    pub fn locate(&self, pattern: &[u8]) -> Vec<P> {
+        let mut locations: Vec<P> = Vec::new();
+        self.segments.iter().for_each(|segment| {
+            let offset = P::from_usize(segment.start);
+            locations.extend(segment.index.locate(pattern).into_iter().map(|location| location + offset));
+        });
+        locations.extend(self.locations_across_segments(&self.raw_index_of(pattern)).map(P::from_usize));
+        locations
+    }
+    /// Encodes the pattern with the decoding table of this index.
+    pub fn encode_pattern(&self, pattern: &[u8]) -> EncodedPattern {
+        EncodedPattern::new(pattern, self.decoding_table())
+    }
+    /// Counts the number of occurrences of an [EncodedPattern] in the indexed text.
+    ///
+    /// The pattern must be encoded with the decoding table of this index,
+    /// otherwise this may panic or return the wrong count.
+    /// [Self::try_count_encoded_pattern] checks the decoding table.
+    pub fn count_encoded_pattern(&self, pattern: &EncodedPattern) -> P {
+        let count_in_segments = self.segments.iter().fold(P::ZERO, |count, segment| {
+            count + segment.index.count_encoded_pattern(pattern)
+        });
+        count_in_segments + P::from_usize(self.locations_across_segments(&pattern.raw_index()).count())
+    }
+    /// Locates all occurrences of an [EncodedPattern] in the indexed text.
+    ///
+    /// The locations are not in order.
+    /// The pattern must be encoded as [Self::count_encoded_pattern].
+    pub fn locate_encoded_pattern(&self, pattern: &EncodedPattern) -> Vec<P> {
+        let mut locations: Vec<P> = Vec::new();
+        self.segments.iter().for_each(|segment| {
+            let offset = P::from_usize(segment.start);
+            locations.extend(segment.index.locate_encoded_pattern(pattern).into_iter().map(|location| location + offset));
+        });
+        locations.extend(self.locations_across_segments(&pattern.raw_index()).map(P::from_usize));
+        locations
+    }
+    /// Checked version of [Self::count_encoded_pattern].
+    ///
+    /// Returns [QueryError::IncompatibleAlphabet] if the pattern is not encoded with the decoding table of this index.
+    pub fn try_count_encoded_pattern(&self, pattern: &EncodedPattern) -> Result<P, QueryError> {
+        pattern.check_encoded_with(self.decoding_table())?;
+        Ok(self.count_encoded_pattern(pattern))
+    }
+    /// Checked version of [Self::locate_encoded_pattern].
+    ///
+    /// Returns [QueryError::IncompatibleAlphabet] if the pattern is not encoded with the decoding table of this index.
+    pub fn try_locate_encoded_pattern(&self, pattern: &EncodedPattern) -> Result<Vec<P>, QueryError> {
+        pattern.check_encoded_with(self.decoding_table())?;
+        Ok(self.locate_encoded_pattern(pattern))
+    }
+    fn raw_index_of(&self, pattern: &[u8]) -> Vec<u8> {
+        pattern.iter().map(|&chr| self.chr_idx_table.idx_of(chr)).collect()
+    }
+    // Occurrences starting in a segment and ending in the following segments
+    fn locations_across_segments<'a>(&'a self, raw_index: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        self.segments.windows(2).flat_map(move |segments| {
+            let boundary = segments[0].end;
+            let first = segments[0].start.max((boundary + 1).saturating_sub(raw_index.len()));
+            let last = boundary.min((self.text.len() + 1).saturating_sub(raw_index.len()));
+            (first..last).filter(move |&location| self.is_matched_at(raw_index, location))
+        })
+    }
+    fn is_matched_at(&self, raw_index: &[u8], location: usize) -> bool {
+        raw_index.iter().zip(&self.text[location..]).all(|(&chridx, &text_chr)| {
+            chridx == self.chr_idx_table.idx_of(text_chr) && !self.chr_idx_table.is_separator(chridx)
+        })
+    }
+
+    /// Returns the decoding table.
+    /// The decoding table is a 256-length array that stores the indices for 1-byte characters.
+    pub fn decoding_table(&self) -> &[u8; 256] {
+        &self.chr_idx_table.table
+    }
+    /// Length of the indexed text.
+    pub fn len_text(&self) -> P {
+        P::from_usize(self.text.len())
+    }
+    /// Count of the static segments.
+    pub fn segment_count(&self) -> usize {
+        self.segments.len()
+    }
+}
+
+impl<P: Position, B: BwmBackend<P>> Segment<P, B> {
+    fn len(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+impl<P: Position, B: BwmBackend<P>> std::fmt::Debug for DynamicLtFmIndex<P, B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DynamicLtFmIndex")
+            .field("bit_size_for_position", &P::BITS)
+            .field("text_length", &self.len_text())
+            .field("segment_count", &self.segment_count())
+            .field("lookup_table_kmer_size", &self.lookup_table_kmer_size)
+            .field("suffix_array_sampling_ratio", &self.suffix_array_sampling_ratio)
+            .finish()
+    }
+}
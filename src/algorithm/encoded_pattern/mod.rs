@@ -0,0 +1,68 @@
+use crate::core::errors::QueryError;
+
+/// Pattern encoded to the indices of the characters once, to be searched in many indexes.
+///
+/// The pattern can be searched in every index built with the same decoding table
+/// (e.g. the shards of a reference built with the same characters),
+/// with [crate::LtFmIndex::count_encoded_pattern] and [crate::LtFmIndex::locate_encoded_pattern]
+/// (also of [crate::RlFmIndex] and [crate::DynamicLtFmIndex]).
+/// [crate::LtFmIndex::try_count_encoded_patterns] and [crate::LtFmIndex::try_locate_encoded_patterns] search the patterns in a batch.
+/// The `try_` versions check that the index has the decoding table of the pattern.
+///
+/// # Example
+/// ```rust
+/// use lt_fm_index::{LtFmIndex, EncodedPattern};
+/// use lt_fm_index::blocks::Block3;
+///
+/// let characters_by_index: &[&[u8]] = &[b"A", b"C", b"G", b"T"];
+/// let shards = [b"ACGTACGT".to_vec(), b"TTACGAAC".to_vec()].map(|text| {
+///     LtFmIndex::<u32, Block3<u64>>::build(text, characters_by_index, 2, 3).unwrap()
+/// });
+/// let pattern = EncodedPattern::new(b"ACG", shards[0].decoding_table());
+/// let counts: Vec<u32> = shards.iter().map(|shard| shard.count_encoded_pattern(&pattern)).collect();
+/// assert_eq!(counts, vec![2, 1]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EncodedPattern {
+    // The indices of the characters in the reverse order
+    raw_index_rev: Vec<u8>,
+    decoding_table: [u8; 256],
+}
+
+impl EncodedPattern {
+    /// Encodes the pattern with the decoding table (e.g. [crate::LtFmIndex::decoding_table]).
+    pub fn new(pattern: &[u8], decoding_table: &[u8; 256]) -> Self {
+        Self {
+            raw_index_rev: pattern.iter().rev().map(|&chr| decoding_table[chr as usize]).collect(),
+            decoding_table: *decoding_table,
+        }
+    }
+    /// Length of the pattern.
+    pub fn len(&self) -> usize {
+        self.raw_index_rev.len()
+    }
+    /// Returns `true` if the pattern is empty.
+    pub fn is_empty(&self) -> bool {
+        self.raw_index_rev.is_empty()
+    }
+    /// Iterator of the raw index in the reverse order,
+    /// as the input of [crate::LtFmIndex::count_from_raw_index] and [crate::LtFmIndex::locate_from_raw_index].
+    pub fn raw_index_rev_iter(&self) -> std::iter::Copied<std::slice::Iter<'_, u8>> {
+        self.raw_index_rev.iter().copied()
+    }
+    /// Returns `true` if the pattern is encoded with the decoding table.
+    pub fn is_encoded_with(&self, decoding_table: &[u8; 256]) -> bool {
+        &self.decoding_table == decoding_table
+    }
+    pub(crate) fn check_encoded_with(&self, decoding_table: &[u8; 256]) -> Result<(), QueryError> {
+        if self.is_encoded_with(decoding_table) {
+            Ok(())
+        } else {
+            Err(QueryError::IncompatibleAlphabet)
+        }
+    }
+    // The indices of the characters in order
+    pub(crate) fn raw_index(&self) -> Vec<u8> {
+        self.raw_index_rev.iter().rev().copied().collect()
+    }
+}
@@ -1,14 +1,19 @@
 use std::pin::Pin;
 
 use crate::core::{Position, AsyncSerialize};
-use super::{LtFmIndex, ChrIdxTable, SuffixArray, CountArray, Bwm, Block};
+use super::{LtFmIndex, ChrIdxTable, SuffixArray, CountArray, BwmBackend};
+use super::serialize::{FORMAT_MAGIC, FORMAT_VERSION, checked_format_version};
 use capwriter::{AsyncSave, AsyncLoad};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
-impl<P: Position, B: Block<P>> LtFmIndex<P, B> {
+impl<P: Position, B: BwmBackend<P>> LtFmIndex<P, B> {
     pub fn async_save_to<W>(&self, mut writer: Pin<&mut W>) -> impl Future<Output = Result<(), std::io::Error>> + Send where
         W: tokio::io::AsyncWrite + Send,
     {
         async move {
+            // header
+            writer.as_mut().write_all(&FORMAT_MAGIC).await?;
+            FORMAT_VERSION.save_as_ne(writer.as_mut()).await?;
             // text_len
             self.text_len.as_u64().save_as_ne(writer.as_mut()).await?;
             // chr_idx_table
@@ -27,11 +32,27 @@ impl<P: Position, B: Block<P>> LtFmIndex<P, B> {
         Self: Sized
     {
         async move {
-            let text_len = P::from_u64(u64::load_as_ne(reader.as_mut()).await?);
-            let chr_idx_table = ChrIdxTable::async_load_from(reader.as_mut()).await?;
-            let suffix_array = SuffixArray::async_load_from(reader.as_mut()).await?;
+            let mut head = [0; 8];
+            reader.as_mut().read_exact(&mut head).await?;
+            let (version, text_len) = if head == FORMAT_MAGIC {
+                let version = checked_format_version(u32::load_as_ne(reader.as_mut()).await?)?;
+                (version, u64::load_as_ne(reader.as_mut()).await?)
+            } else {
+                (0, u64::from_ne_bytes(head))
+            };
+            let text_len = P::from_u64(text_len);
+            let (chr_idx_table, suffix_array) = match version {
+                0 => (
+                    ChrIdxTable::async_load_unversioned_from(reader.as_mut()).await?,
+                    SuffixArray::async_load_unversioned_from(reader.as_mut()).await?,
+                ),
+                _ => (
+                    ChrIdxTable::async_load_from(reader.as_mut()).await?,
+                    SuffixArray::async_load_from(reader.as_mut()).await?,
+                ),
+            };
             let count_array = CountArray::async_load_from(reader.as_mut()).await?;
-            let bwm = Bwm::async_load_from(reader.as_mut()).await?;
+            let bwm = B::Bwm::async_load_from(reader.as_mut()).await?;
             Ok(Self {
                 text_len,
                 chr_idx_table,
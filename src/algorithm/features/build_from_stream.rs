@@ -0,0 +1,101 @@
+use crate::core::{
+    Position,
+    errors::BuildError,
+};
+use crate::algorithm::{BuildOptions, BuildMonitor, count_array::CountArrayBuilder};
+
+use super::{LtFmIndex, BwmBackend};
+use crate::algorithm::check_text_len;
+
+// Size of the buffer to read the text
+const READ_BUFFER_SIZE: usize = 1 << 16;
+
+impl<P: Position, B: BwmBackend<P>> LtFmIndex<P, B> {
+    /// Builds a new FM-index from the text streamed by the iterator.
+    ///
+    /// The characters are encoded and counted as they arrive, so the text is held only once.
+    /// The other arguments are the same as [Self::build_with_options].
+    pub fn build_from_iter<I, T>(
+        text: I,
+        characters_by_index: &[T],
+        suffix_array_sampling_ratio: P,
+        lookup_table_kmer_size: u32,
+        options: &BuildOptions,
+    ) -> Result<Self, BuildError>
+    where
+        I: IntoIterator<Item = u8>,
+        T: AsRef<[u8]>,
+    {
+        let (chr_idx_table, chr_count) = Self::prepare_build(
+            characters_by_index,
+            suffix_array_sampling_ratio,
+            lookup_table_kmer_size,
+            options,
+        )?;
+        let mut builder = CountArrayBuilder::new(chr_count, lookup_table_kmer_size, options.lookup_table);
+        let chridxwp_text: Vec<u8> = text.into_iter().map(|chr| {
+            let chridx = chr_idx_table.idx_of(chr);
+            builder.push(chridx);
+            chridx + 1
+        }).collect();
+        check_text_len::<P>(chridxwp_text.len())?;
+        Self::build_from_chridxwp_text(
+            chridxwp_text,
+            chr_idx_table,
+            chr_count,
+            builder.finish(),
+            suffix_array_sampling_ratio,
+            options,
+            &mut BuildMonitor::none(),
+        )
+    }
+    /// Builds a new FM-index from the text read from the reader.
+    ///
+    /// The text is read in chunks, and the characters are encoded and counted as they arrive,
+    /// so the text is held only once.
+    /// The other arguments are the same as [Self::build_with_options].
+    pub fn build_from_reader<R, T>(
+        mut reader: R,
+        characters_by_index: &[T],
+        suffix_array_sampling_ratio: P,
+        lookup_table_kmer_size: u32,
+        options: &BuildOptions,
+    ) -> Result<Self, BuildError>
+    where
+        R: std::io::Read,
+        T: AsRef<[u8]>,
+    {
+        let (chr_idx_table, chr_count) = Self::prepare_build(
+            characters_by_index,
+            suffix_array_sampling_ratio,
+            lookup_table_kmer_size,
+            options,
+        )?;
+        let mut builder = CountArrayBuilder::new(chr_count, lookup_table_kmer_size, options.lookup_table);
+        let mut chridxwp_text: Vec<u8> = Vec::new();
+        let mut buffer = vec![0; READ_BUFFER_SIZE];
+        loop {
+            let read_len = match reader.read(&mut buffer) {
+                Ok(0) => break,
+                Ok(read_len) => read_len,
+                Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(error) => return Err(error.into()),
+            };
+            chridxwp_text.extend(buffer[..read_len].iter().map(|&chr| {
+                let chridx = chr_idx_table.idx_of(chr);
+                builder.push(chridx);
+                chridx + 1
+            }));
+        }
+        check_text_len::<P>(chridxwp_text.len())?;
+        Self::build_from_chridxwp_text(
+            chridxwp_text,
+            chr_idx_table,
+            chr_count,
+            builder.finish(),
+            suffix_array_sampling_ratio,
+            options,
+            &mut BuildMonitor::none(),
+        )
+    }
+}
@@ -0,0 +1,63 @@
+use crate::core::{
+    Position,
+    errors::QueryError,
+};
+
+use super::{LtFmIndex, BurrowsWheelerMatrix, BwmBackend};
+
+impl<P: Position, B: BwmBackend<P>> LtFmIndex<P, B> {
+    /// Checked version of [Self::count].
+    ///
+    /// Returns [QueryError::UnindexedCharacter] if any character of the pattern is not in `characters_by_index`,
+    /// instead of matching it as the unindexed characters.
+    pub fn try_count(&self, pattern: &[u8]) -> Result<P, QueryError> {
+        self.check_indexed(pattern)?;
+        Ok(self.count(pattern))
+    }
+    /// Checked version of [Self::locate].
+    ///
+    /// Returns [QueryError::UnindexedCharacter] if any character of the pattern is not in `characters_by_index`,
+    /// instead of matching it as the unindexed characters.
+    pub fn try_locate(&self, pattern: &[u8]) -> Result<Vec<P>, QueryError> {
+        self.check_indexed(pattern)?;
+        Ok(self.locate(pattern))
+    }
+    /// Checked version of [Self::count_from_raw_index].
+    ///
+    /// Returns [QueryError::ChrIdxOutOfRange] if any raw index is not less than the count of the characters
+    /// (the count of `characters_by_index` plus one for the unindexed characters).
+    pub fn try_count_from_raw_index<I: Iterator<Item = u8>>(
+        &self,
+        raw_index_rev_iter: I,
+    ) -> Result<P, QueryError> {
+        let raw_index_rev = self.checked_raw_index_rev(raw_index_rev_iter)?;
+        Ok(self.count_from_raw_index(raw_index_rev.into_iter()))
+    }
+    /// Checked version of [Self::locate_from_raw_index].
+    ///
+    /// Returns [QueryError::ChrIdxOutOfRange] if any raw index is not less than the count of the characters
+    /// (the count of `characters_by_index` plus one for the unindexed characters).
+    pub fn try_locate_from_raw_index<I: Iterator<Item = u8>>(
+        &self,
+        raw_index_rev_iter: I,
+    ) -> Result<Vec<P>, QueryError> {
+        let raw_index_rev = self.checked_raw_index_rev(raw_index_rev_iter)?;
+        Ok(self.locate_from_raw_index(raw_index_rev.into_iter()))
+    }
+    fn check_indexed(&self, pattern: &[u8]) -> Result<(), QueryError> {
+        // The unindexed characters are of the last index
+        let unindexed_chridx = (self.bwm.chr_count() - 1) as u8;
+        match pattern.iter().find(|&&chr| self.chr_idx_table.idx_of(chr) == unindexed_chridx) {
+            Some(&chr) => Err(QueryError::UnindexedCharacter(chr as char)),
+            None => Ok(()),
+        }
+    }
+    fn checked_raw_index_rev<I: Iterator<Item = u8>>(&self, raw_index_rev_iter: I) -> Result<Vec<u8>, QueryError> {
+        let chr_count = self.bwm.chr_count();
+        let raw_index_rev: Vec<u8> = raw_index_rev_iter.collect();
+        if let Some(&chridx) = raw_index_rev.iter().find(|&&chridx| chridx as u32 >= chr_count) {
+            return Err(QueryError::ChrIdxOutOfRange(chridx, chr_count));
+        }
+        Ok(raw_index_rev)
+    }
+}
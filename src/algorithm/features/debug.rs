@@ -1,30 +1,47 @@
 use crate::core::{Position};
-use super::{LtFmIndex, Block};
+use super::{LtFmIndex, BurrowsWheelerMatrix, BwmBackend};
+use crate::algorithm::{Alphabet, LookupTable, UnindexedCharacters, SuffixArraySampling};
 use std::fmt::Debug;
 
-impl<P: Position, B: Block<P>> Debug for LtFmIndex<P, B> {
+impl<P: Position, B: BwmBackend<P>> Debug for LtFmIndex<P, B> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("LtFmIndex")
             .field("bit_size_for_position", &P::BITS)
             .field("text_length", &self.len_text())
             .field("index_count", &self.index_count())
             .field("lookup_table_kmer_size", &self.lookup_table_kmer_size())
+            .field("lookup_table", &self.lookup_table())
+            .field("unindexed_characters", &self.unindexed_characters())
             .field("suffix_array_sampling_ratio", &self.suffix_array_sampling_ratio())
+            .field("suffix_array_sampling", &self.suffix_array_sampling())
             .finish()
     }
 }
 
-impl<P: Position, B: Block<P>> LtFmIndex<P, B> {
+impl<P: Position, B: BwmBackend<P>> LtFmIndex<P, B> {
     pub fn len_text(&self) -> P {
         self.text_len
     }
     pub fn index_count(&self) -> u32 {
         self.bwm.chr_count()
     }
+    /// The groups of the characters the index is built with.
+    pub fn alphabet(&self) -> Alphabet {
+        Alphabet::from_chr_idx_table(&self.chr_idx_table, self.index_count())
+    }
     pub fn lookup_table_kmer_size(&self) -> u32 {
         self.count_array.kmer_size()
     }
+    pub fn lookup_table(&self) -> LookupTable {
+        self.count_array.lookup_table()
+    }
+    pub fn unindexed_characters(&self) -> UnindexedCharacters {
+        self.chr_idx_table.unindexed_characters()
+    }
     pub fn suffix_array_sampling_ratio(&self) -> P {
         self.suffix_array.sampling_ratio()
     }
+    pub fn suffix_array_sampling(&self) -> SuffixArraySampling {
+        self.suffix_array.sampling()
+    }
 }
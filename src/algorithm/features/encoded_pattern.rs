@@ -0,0 +1,64 @@
+use crate::core::{
+    Position,
+    errors::QueryError,
+};
+use crate::algorithm::EncodedPattern;
+
+use super::{LtFmIndex, BwmBackend};
+
+impl<P: Position, B: BwmBackend<P>> LtFmIndex<P, B> {
+    /// Encodes the pattern with the decoding table of this index.
+    pub fn encode_pattern(&self, pattern: &[u8]) -> EncodedPattern {
+        EncodedPattern::new(pattern, self.decoding_table())
+    }
+    /// Counts the number of occurrences of an [EncodedPattern] in the indexed text.
+    ///
+    /// The pattern must be encoded with the decoding table of this index,
+    /// otherwise this may panic or return the wrong count.
+    /// [Self::try_count_encoded_pattern] checks the decoding table.
+    #[inline]
+    pub fn count_encoded_pattern(&self, pattern: &EncodedPattern) -> P {
+        self.count_from_raw_index(pattern.raw_index_rev_iter())
+    }
+    /// Locates all occurrences of an [EncodedPattern] in the indexed text.
+    ///
+    /// The pattern must be encoded with the decoding table of this index,
+    /// otherwise this may panic or return the wrong locations.
+    /// [Self::try_locate_encoded_pattern] checks the decoding table.
+    #[inline]
+    pub fn locate_encoded_pattern(&self, pattern: &EncodedPattern) -> Vec<P> {
+        self.locate_from_raw_index(pattern.raw_index_rev_iter())
+    }
+    /// Checked version of [Self::count_encoded_pattern].
+    ///
+    /// Returns [QueryError::IncompatibleAlphabet] if the pattern is not encoded with the decoding table of this index.
+    pub fn try_count_encoded_pattern(&self, pattern: &EncodedPattern) -> Result<P, QueryError> {
+        pattern.check_encoded_with(self.decoding_table())?;
+        Ok(self.count_encoded_pattern(pattern))
+    }
+    /// Checked version of [Self::locate_encoded_pattern].
+    ///
+    /// Returns [QueryError::IncompatibleAlphabet] if the pattern is not encoded with the decoding table of this index.
+    pub fn try_locate_encoded_pattern(&self, pattern: &EncodedPattern) -> Result<Vec<P>, QueryError> {
+        pattern.check_encoded_with(self.decoding_table())?;
+        Ok(self.locate_encoded_pattern(pattern))
+    }
+    /// Counts the occurrences of each of the patterns, in order of the patterns.
+    ///
+    /// Every pattern is checked before the search, and [QueryError::IncompatibleAlphabet] is returned
+    /// if any pattern is not encoded with the decoding table of this index.
+    pub fn try_count_encoded_patterns(&self, patterns: &[EncodedPattern]) -> Result<Vec<P>, QueryError> {
+        self.check_encoded_patterns(patterns)?;
+        Ok(patterns.iter().map(|pattern| self.count_encoded_pattern(pattern)).collect())
+    }
+    /// Locates the occurrences of each of the patterns, in order of the patterns.
+    ///
+    /// The patterns are checked as [Self::try_count_encoded_patterns].
+    pub fn try_locate_encoded_patterns(&self, patterns: &[EncodedPattern]) -> Result<Vec<Vec<P>>, QueryError> {
+        self.check_encoded_patterns(patterns)?;
+        Ok(patterns.iter().map(|pattern| self.locate_encoded_pattern(pattern)).collect())
+    }
+    fn check_encoded_patterns(&self, patterns: &[EncodedPattern]) -> Result<(), QueryError> {
+        patterns.iter().try_for_each(|pattern| pattern.check_encoded_with(self.decoding_table()))
+    }
+}
@@ -0,0 +1,68 @@
+use crate::Position;
+use crate::algorithm::class_pattern::{ClassPattern, ChrIdxSet};
+
+use super::{LtFmIndex, BurrowsWheelerMatrix, BwmBackend};
+
+impl<P: Position, B: BwmBackend<P>> LtFmIndex<P, B> {
+    /// Counts the number of occurrences of a [ClassPattern] in the indexed text.
+    ///
+    /// The empty pattern occurs at every position, as [Self::count] of the empty pattern.
+    pub fn count_class_pattern(&self, pattern: &ClassPattern) -> P {
+        self.get_pos_ranges_of_class_pattern(pattern).into_iter()
+            .fold(P::ZERO, |count, pos_range| count + pos_range.1 - pos_range.0)
+    }
+    /// Locates all occurrences of a [ClassPattern] in the indexed text.
+    ///
+    /// The locations are not in order. The empty pattern is located at every position of the text.
+    pub fn locate_class_pattern(&self, pattern: &ClassPattern) -> Vec<P> {
+        self.get_pos_ranges_of_class_pattern(pattern).into_iter()
+            .flat_map(|pos_range| self.get_locations(pos_range))
+            .collect()
+    }
+    // Disjoint pos ranges of all the matched strings
+    fn get_pos_ranges_of_class_pattern(&self, pattern: &ClassPattern) -> Vec<(P, P)> {
+        let chr_count = self.bwm.chr_count() as u8;
+        let mut sets = pattern.sets().iter().rev();
+        let mut pos_ranges: Vec<(P, P)> = match sets.next() {
+            Some(set) => {
+                (0..chr_count).filter(|&chridx| self.is_matchable(set, chridx)).map(|chridx| (
+                    self.count_array.get_precount(chridx as usize),
+                    self.count_array.get_precount(chridx as usize + 1),
+                )).collect()
+            },
+            None => return vec![(P::ZERO, self.text_len)],
+        };
+        for set in sets {
+            let mut next_pos_ranges = Vec::new();
+            pos_ranges.iter().for_each(|&pos_range| {
+                (0..chr_count).filter(|&chridx| self.is_matchable(set, chridx)).for_each(|chridx| {
+                    let precount = self.count_array.get_precount(chridx as usize);
+                    let (start_rank, end_rank) = self.bwm.get_next_ranks(pos_range, chridx);
+                    next_pos_ranges.push((precount + start_rank, precount + end_rank));
+                });
+            });
+            pos_ranges = Self::merge_pos_ranges(next_pos_ranges);
+            if pos_ranges.is_empty() {
+                break;
+            }
+        }
+        Self::merge_pos_ranges(pos_ranges)
+    }
+    #[inline]
+    fn is_matchable(&self, set: &ChrIdxSet, chridx: u8) -> bool {
+        set.contains(chridx) && !self.chr_idx_table.is_separator(chridx)
+    }
+    // Removes the empty ranges, and merges the adjacent ranges
+    fn merge_pos_ranges(mut pos_ranges: Vec<(P, P)>) -> Vec<(P, P)> {
+        pos_ranges.retain(|pos_range| pos_range.0 < pos_range.1);
+        pos_ranges.sort_unstable();
+        let mut merged: Vec<(P, P)> = Vec::with_capacity(pos_ranges.len());
+        pos_ranges.into_iter().for_each(|pos_range| {
+            match merged.last_mut() {
+                Some(last) if last.1 == pos_range.0 => last.1 = pos_range.1,
+                _ => merged.push(pos_range),
+            }
+        });
+        merged
+    }
+}
@@ -1,16 +1,33 @@
+use std::cell::Cell;
+
 use crate::Position;
 
-use super::{LtFmIndex, CountArray, Block};
+use super::{LtFmIndex, CountArray, BurrowsWheelerMatrix, BwmBackend};
 
-impl<P: Position, B: Block<P>> LtFmIndex<P, B> {
+impl<P: Position, B: BwmBackend<P>> LtFmIndex<P, B> {
     #[inline]
     /// Returns the decoding table.
     /// The decoding table is a 256-length array that stores the indices for 1-byte characters.
     pub fn decoding_table(&self) -> &[u8; 256] {
-        &self.chr_idx_table.0
+        &self.chr_idx_table.table
+    }
+    #[inline]
+    /// Performs the same functionality as [Self::count], but instead of plain text, it iterates over the text in reverse order and takes a raw index as input using the decoding table (with [Self::decoding_table]).
+    ///
+    /// The raw index must be less than the count of the characters, otherwise this may panic or return the wrong count.
+    /// [Self::try_count_from_raw_index] checks the raw index.
+    pub fn count_from_raw_index<I: Iterator<Item = u8>>(
+        &self,
+        raw_index_rev_iter: I,
+    ) -> P {
+        let pos_range = self.get_pos_range_from_raw_index(raw_index_rev_iter);
+        pos_range.1 - pos_range.0
     }
     #[inline]
     /// Performs the same functionality as [Self::locate], but instead of plain text, it iterates over the text in reverse order and takes a raw index as input using the decoding table (with [Self::decoding_table]).
+    ///
+    /// The raw index must be less than the count of the characters, otherwise this may panic or return the wrong locations.
+    /// [Self::try_locate_from_raw_index] checks the raw index.
     pub fn locate_from_raw_index<I: Iterator<Item = u8>>(
         &self,
         raw_index_rev_iter: I,
@@ -22,8 +39,18 @@ impl<P: Position, B: Block<P>> LtFmIndex<P, B> {
     #[inline]
     fn get_pos_range_from_raw_index<I: Iterator<Item = u8>>(
         &self,
-        mut raw_index_rev_iter: I,
+        raw_index_rev_iter: I,
     ) -> (P, P) {
+        let mut raw_index_rev_iter = raw_index_rev_iter.peekable();
+        if raw_index_rev_iter.peek().is_none() {
+            return (P::ZERO, self.text_len);
+        }
+        let has_separator = Cell::new(false);
+        let mut raw_index_rev_iter = raw_index_rev_iter.inspect(|&chridx| {
+            if self.chr_idx_table.is_separator(chridx) {
+                has_separator.set(true);
+            }
+        });
         let mut pos_range = self.count_array.get_initial_pos_range_and_idx_of_pattern_without_chr_idx_table(
             &mut raw_index_rev_iter,
         );
@@ -36,13 +63,15 @@ impl<P: Position, B: Block<P>> LtFmIndex<P, B> {
                 None => break,
             };
         }
+        if has_separator.get() {
+            return (P::ZERO, P::ZERO);
+        }
         pos_range
     }
     #[inline]
     fn next_pos_range_without_chr_idx_table(&self, pos_range: (P, P), chridx: u8) -> (P, P) {
         let precount = self.count_array.get_precount(chridx as usize);
-        let start_rank = self.bwm.get_next_rank(pos_range.0, chridx);
-        let end_rank = self.bwm.get_next_rank(pos_range.1, chridx);
+        let (start_rank, end_rank) = self.bwm.get_next_ranks(pos_range, chridx);
         (precount + start_rank, precount + end_rank)
     }
 }
@@ -74,18 +103,17 @@ impl<P: Position> CountArray<P> {
                     let end_idx = start_idx + gap_btw_unsearched_kmer;
 
                     let pos_range = (
-                        self.kmer_count_table[start_idx -1],
-                        self.kmer_count_table[end_idx],
+                        self.kmer_count_table.accumed_count(start_idx -1),
+                        self.kmer_count_table.accumed_count(end_idx),
                     );
                     return pos_range
                 },
             };
         }
 
-        let pos_range = (
-            self.kmer_count_table[start_idx -1],
-            self.kmer_count_table[start_idx],
-        );
-        pos_range
+        (
+            self.kmer_count_table.accumed_count(start_idx -1),
+            self.kmer_count_table.accumed_count(start_idx),
+        )
     }
 }
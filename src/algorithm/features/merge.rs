@@ -0,0 +1,178 @@
+use std::cmp::Ordering;
+
+use crate::core::{
+    Position,
+    errors::BuildError,
+};
+use super::{LtFmIndex, SuffixArray, CountArray, BurrowsWheelerMatrix, BwmBackend};
+use crate::algorithm::{BuildMonitor, SuffixArraySampling, check_text_len};
+
+impl<P: Position, B: BwmBackend<P>> LtFmIndex<P, B> {
+    /// Merges two indexes into the index of the concatenated text (`a` followed by `b`).
+    ///
+    /// The result is the same as the index built on the concatenated text.
+    /// The rows of `b` keep their order, and the suffixes of `a` are inserted between them
+    /// by their ranks among the suffixes of `b`, which are found by the backward search on `b`.
+    /// The suffixes of `a` of the same rank keep their order in `a`, so they are not sorted again.
+    /// The count tables are added, and only the k-mers spanning the join are counted again.
+    ///
+    /// Both indexes must be built with the same characters and options,
+    /// otherwise [BuildError::IncompatibleIndexes] is returned.
+    pub fn merge(a: &Self, b: &Self) -> Result<Self, BuildError> {
+        if a.chr_idx_table != b.chr_idx_table
+            || a.bwm.chr_count() != b.bwm.chr_count()
+            || a.suffix_array_sampling_ratio() != b.suffix_array_sampling_ratio()
+            || a.suffix_array_sampling() != b.suffix_array_sampling()
+            || a.lookup_table_kmer_size() != b.lookup_table_kmer_size()
+            || a.lookup_table() != b.lookup_table()
+        {
+            return Err(BuildError::IncompatibleIndexes);
+        }
+        let (a_len, b_len) = (a.text_len.as_usize(), b.text_len.as_usize());
+        check_text_len::<P>(a_len + b_len)?;
+        let text_len = a_len + b_len;
+        let sampling_ratio = a.suffix_array_sampling_ratio();
+        let sampling = a.suffix_array_sampling();
+        let is_sampled = |row: usize, location: usize| match sampling {
+            SuffixArraySampling::Row => row % sampling_ratio.as_usize() == 0,
+            SuffixArraySampling::TextPosition => location % sampling_ratio.as_usize() == 0,
+        };
+
+        let (a_text, a_rows) = a.recover_text_and_rows();
+
+        // Count of the suffixes of `b`, less than each suffix of `a` followed by `b`
+        let mut ranks: Vec<P> = vec![P::ZERO; a_len + 1];
+        ranks[a_len] = b.bwm.primary_index() - P::ONE;
+        for location in (0..a_len).rev() {
+            let chridx = a_text[location];
+            ranks[location] = b.count_array.get_precount(chridx as usize) + b.bwm.get_next_rank(ranks[location + 1], chridx);
+        }
+        let a_locations = a.sort_suffixes_followed_by_b(&a_text, &a_rows, &ranks);
+        let a_ranks: Vec<P> = a_locations.iter().map(|&location| ranks[location]).collect();
+        drop((ranks, a_rows));
+
+        // Interleave the rows: the rows of `a` are placed before the rows of `b` of the same rank
+        let mut bwt: Vec<u8> = vec![0; text_len];
+        let mut samples: Vec<(P, P)> = Vec::new();
+        let pidx = a_locations.iter().zip(&a_ranks).enumerate().find_map(|(a_row, (&location, &rank))| {
+            (location == 0).then_some(a_row + rank.as_usize() + 1)
+        }).unwrap();
+        let bwt_idx_of_row = |row: usize| if row + 1 < pidx { row + 1 } else { row };
+        a_locations.iter().zip(&a_ranks).enumerate().for_each(|(a_row, (&location, &rank))| {
+            let row = a_row + rank.as_usize();
+            if location != 0 {
+                bwt[bwt_idx_of_row(row)] = a_text[location - 1] + 1;
+            }
+            if is_sampled(row, location) {
+                samples.push((P::from_usize(row), P::from_usize(location)));
+            }
+        });
+        // The rows of `b`, by LF mapping from the last suffix
+        let b_head_len = b_len.min(a.lookup_table_kmer_size() as usize - 1);
+        let mut b_head: Vec<u8> = vec![0; b_head_len];
+        let (mut pos, b_last_chridx) = b.last_suffix_row_and_chridx();
+        bwt[0] = b_last_chridx + 1;
+        if b_len - 1 < b_head_len {
+            b_head[b_len - 1] = b_last_chridx;
+        }
+        for location in (0..b_len).rev() {
+            let row = pos.as_usize() + a_ranks.partition_point(|&rank| rank <= pos);
+            if is_sampled(row, a_len + location) {
+                samples.push((P::from_usize(row), P::from_usize(a_len + location)));
+            }
+            match b.bwm.get_pre_rank_and_chridx(pos) {
+                Some((rank, chridx)) => {
+                    bwt[bwt_idx_of_row(row)] = chridx + 1;
+                    if location - 1 < b_head_len {
+                        b_head[location - 1] = chridx;
+                    }
+                    pos = b.count_array.get_precount(chridx as usize) + rank;
+                },
+                // The start of `b` follows the end of `a`
+                None => bwt[bwt_idx_of_row(row)] = a_text[a_len - 1] + 1,
+            }
+        }
+        samples.sort_unstable_by_key(|&(row, _)| row);
+
+        let a_tail = &a_text[a_len - a_len.min(a.lookup_table_kmer_size() as usize - 1)..];
+        let count_array = CountArray::merge(&a.count_array, &b.count_array, a_tail, &b_head);
+        let suffix_array = SuffixArray::from_samples(samples, P::from_usize(text_len), sampling_ratio, sampling);
+        let bwm = B::Bwm::new(bwt, P::from_usize(pidx), a.bwm.chr_count(), &mut BuildMonitor::none())?;
+
+        Ok(Self {
+            text_len: a.text_len + b.text_len,
+            chr_idx_table: a.chr_idx_table.clone(),
+            suffix_array,
+            count_array,
+            bwm,
+        })
+    }
+    // Locations of the suffixes of `a` followed by `b` in order.
+    // The suffixes are ordered by their ranks in `b` (`ranks`), and the suffixes of the same rank
+    // keep their order in `a` (`a_rows`), except when one is a prefix of the other.
+    // Then the shorter is compared with `b`, and the longer with the rest after the shorter,
+    // so the shorter is after the longer when the rest followed by `b` is less than `b`.
+    fn sort_suffixes_followed_by_b(&self, a_text: &[u8], a_rows: &[P], ranks: &[P]) -> Vec<usize> {
+        let a_len = a_text.len();
+        let is_less_than_b = |location: usize| ranks[location] <= ranks[a_len];
+        // Rows of the occurrences of each suffix of `a` found more than once, from the last suffix
+        let mut repeated_pos_ranges: Vec<(P, P)> = Vec::new();
+        let chridx = a_text[a_len - 1] as usize;
+        let mut pos_range = (self.count_array.get_precount(chridx), self.count_array.get_precount(chridx + 1));
+        while pos_range.1 - pos_range.0 > P::ONE {
+            repeated_pos_ranges.push(pos_range);
+            let Some(location) = (a_len - repeated_pos_ranges.len()).checked_sub(1) else { break };
+            let chridx = a_text[location];
+            let precount = self.count_array.get_precount(chridx as usize);
+            let (start_rank, end_rank) = self.bwm.get_next_ranks(pos_range, chridx);
+            pos_range = (precount + start_rank, precount + end_rank);
+        }
+        // Whether the suffix at `location` has the suffix at `prefix_location` as a proper prefix
+        let has_prefix = |location: usize, prefix_location: usize| {
+            location < prefix_location
+                && repeated_pos_ranges.get(a_len - 1 - prefix_location).is_some_and(|&(start, end)| {
+                    start <= a_rows[location] && a_rows[location] < end
+                })
+        };
+
+        let mut locations: Vec<usize> = (0..a_len).collect();
+        locations.sort_unstable_by(|&x, &y| {
+            ranks[x].cmp(&ranks[y]).then_with(|| {
+                if has_prefix(y, x) {
+                    if is_less_than_b(y + a_len - x) { Ordering::Greater } else { Ordering::Less }
+                } else if has_prefix(x, y) {
+                    if is_less_than_b(x + a_len - y) { Ordering::Less } else { Ordering::Greater }
+                } else {
+                    a_rows[x].cmp(&a_rows[y])
+                }
+            })
+        });
+        locations
+    }
+    // The last suffix is in the first row of the suffixes starting with the last character
+    fn last_suffix_row_and_chridx(&self) -> (P, u8) {
+        let text_len = self.text_len.as_usize();
+        (0..self.bwm.chr_count() as u8).find_map(|chridx| {
+            let row = self.count_array.get_precount(chridx as usize);
+            let is_last = row < self.count_array.get_precount(chridx as usize + 1)
+                && self.get_locations((row, row + P::ONE))[0].as_usize() == text_len - 1;
+            is_last.then_some((row, chridx))
+        }).unwrap()
+    }
+    // Text in chridx and the row of each suffix, by LF mapping from the last suffix
+    fn recover_text_and_rows(&self) -> (Vec<u8>, Vec<P>) {
+        let text_len = self.text_len.as_usize();
+        let mut text = vec![0; text_len];
+        let mut rows = vec![P::ZERO; text_len];
+        let (mut pos, last_chridx) = self.last_suffix_row_and_chridx();
+        text[text_len - 1] = last_chridx;
+        rows[text_len - 1] = pos;
+        for location in (1..text_len).rev() {
+            let (rank, chridx) = self.bwm.get_pre_rank_and_chridx(pos).unwrap();
+            text[location - 1] = chridx;
+            pos = self.count_array.get_precount(chridx as usize) + rank;
+            rows[location - 1] = pos;
+        }
+        (text, rows)
+    }
+}
@@ -1,8 +1,14 @@
-use super::{LtFmIndex, ChrIdxTable, SuffixArray, CountArray, Bwm, Block};
+use super::{LtFmIndex, ChrIdxTable, SuffixArray, CountArray, BurrowsWheelerMatrix, BwmBackend};
 
 mod serialize;
+pub use serialize::ComponentSizes;
 #[cfg(feature = "async-io")]
 #[cfg_attr(docsrs, doc(cfg(feature = "async-io")))]
 mod async_serialize;
 mod debug;
-mod locate_from_reverse_raw_index;
\ No newline at end of file
+mod locate_from_reverse_raw_index;
+mod locate_class_pattern;
+mod merge;
+mod build_from_stream;
+mod checked_query;
+mod encoded_pattern;
@@ -1,11 +1,31 @@
 use crate::core::{Position, Serialize};
-use super::{LtFmIndex, ChrIdxTable, SuffixArray, CountArray, Bwm, Block};
+use super::{LtFmIndex, ChrIdxTable, SuffixArray, CountArray, BwmBackend};
 use capwriter::{Save, Load};
 
-impl<P: Position, B: Block<P>> LtFmIndex<P, B> {
+// The saved index starts with the magic and the version of the format.
+// The index saved by 0.7 has no header (the version of 0), and starts with the text length.
+pub(crate) const FORMAT_MAGIC: [u8; 8] = *b"LTFMINDX";
+pub(crate) const FORMAT_VERSION: u32 = 1;
+const HEADER_LEN: usize = 12; // magic(8) + version(4)
+
+pub(crate) fn checked_format_version(version: u32) -> Result<u32, std::io::Error> {
+    if version <= FORMAT_VERSION {
+        Ok(version)
+    } else {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("Unsupported format version of the index: {}", version),
+        ))
+    }
+}
+
+impl<P: Position, B: BwmBackend<P>> LtFmIndex<P, B> {
     pub fn save_to<W>(&self, mut writer: W) -> Result<(), std::io::Error> where
         W: std::io::Write
     {
+        // header
+        writer.write_all(&FORMAT_MAGIC)?;
+        FORMAT_VERSION.save_as_ne(&mut writer)?;
         // text_len
         self.text_len.as_u64().save_as_ne(&mut writer)?;
         // chr_idx_table
@@ -22,11 +42,21 @@ impl<P: Position, B: Block<P>> LtFmIndex<P, B> {
         R: std::io::Read,
         Self: Sized
     {
-        let text_len = P::from_u64(u64::load_as_ne(&mut reader)?);
-        let chr_idx_table = ChrIdxTable::load_from(&mut reader)?;
-        let suffix_array = SuffixArray::load_from(&mut reader)?;
+        let mut head = [0; 8];
+        reader.read_exact(&mut head)?;
+        let (version, text_len) = if head == FORMAT_MAGIC {
+            let version = checked_format_version(u32::load_as_ne(&mut reader)?)?;
+            (version, u64::load_as_ne(&mut reader)?)
+        } else {
+            (0, u64::from_ne_bytes(head))
+        };
+        let text_len = P::from_u64(text_len);
+        let (chr_idx_table, suffix_array) = match version {
+            0 => (ChrIdxTable::load_unversioned_from(&mut reader)?, SuffixArray::load_unversioned_from(&mut reader)?),
+            _ => (ChrIdxTable::load_from(&mut reader)?, SuffixArray::load_from(&mut reader)?),
+        };
         let count_array = CountArray::load_from(&mut reader)?;
-        let bwm = Bwm::load_from(&mut reader)?;
+        let bwm = B::Bwm::load_from(&mut reader)?;
         Ok(Self {
             text_len,
             chr_idx_table,
@@ -36,10 +66,33 @@ impl<P: Position, B: Block<P>> LtFmIndex<P, B> {
         })
     }
     pub fn encoded_len(&self) -> usize {
-        8 // text_len
+        HEADER_LEN // header
+        + 8 // text_len
         + self.chr_idx_table.encoded_len() // chr_idx_table
         + self.suffix_array.encoded_len() // suffix_array
         + self.count_array.encoded_len() // count_array
         + self.bwm.encoded_len() // bwm
     }
+    /// Encoded sizes of the components of the index in bytes.
+    pub fn component_sizes(&self) -> ComponentSizes {
+        ComponentSizes {
+            chr_idx_table: self.chr_idx_table.encoded_len(),
+            suffix_array: self.suffix_array.encoded_len(),
+            count_array: self.count_array.encoded_len(),
+            bwm: self.bwm.encoded_len(),
+        }
+    }
+}
+
+/// Encoded sizes of the components of [LtFmIndex] in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComponentSizes {
+    /// Table of the characters
+    pub chr_idx_table: usize,
+    /// Sampled suffix array
+    pub suffix_array: usize,
+    /// Counts of the characters and the lookup table
+    pub count_array: usize,
+    /// Burrows-Wheeler transformed text
+    pub bwm: usize,
 }
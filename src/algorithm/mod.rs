@@ -7,12 +7,14 @@ use crate::core::{
 /// This is a space-efficient implementation of the FM-index that uses a lookup table
 /// for the first k-mer search to improve performance.
 #[derive(Clone, PartialEq, Eq)]
-pub struct  LtFmIndex<P: Position, B: Block<P>> {
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
+pub struct  LtFmIndex<P: Position, B: BwmBackend<P>> {
     text_len: P,
     chr_idx_table: ChrIdxTable,
     suffix_array: SuffixArray<P>,
     count_array: CountArray<P>,
-    bwm: Bwm<P, B>,
+    bwm: B::Bwm,
 }
 
 mod chr_idx_table;
@@ -21,12 +23,30 @@ mod suffix_array;
 use suffix_array::SuffixArray;
 mod count_array;
 use count_array::CountArray;
+mod build_options;
+pub use build_options::{BuildOptions, LookupTable, UnindexedCharacters, SuffixArraySampling};
+mod build_progress;
+pub use build_progress::{BuildPhase, BuildObserver, CancellationToken};
+pub(crate) use build_progress::BuildMonitor;
+mod symbol_index;
+pub use symbol_index::{SymbolLtFmIndex, Symbol};
+mod alphabet;
+pub use alphabet::Alphabet;
+mod class_pattern;
+pub use class_pattern::ClassPattern;
+mod encoded_pattern;
+pub use encoded_pattern::EncodedPattern;
+mod bit_vector;
 mod bwm;
-use bwm::Bwm;
-pub use bwm::{Block, blocks};
+use bwm::BurrowsWheelerMatrix;
+pub use bwm::{Block, BwmBackend, blocks, backends};
 
-impl<P: Position, B: Block<P>> LtFmIndex<P, B> {
+impl<P: Position, B: BwmBackend<P>> LtFmIndex<P, B> {
     /// Counts the number of occurrences of a pattern in the indexed text.
+    ///
+    /// The empty pattern occurs at every position, so its count is the length of the text.
+    /// Every byte string is a valid pattern, so the query never fails:
+    /// the pattern with a separator or a character out of the index just counts 0.
     /// 
     /// # Arguments
     /// * `pattern` - The pattern to search for
@@ -40,6 +60,8 @@ impl<P: Position, B: Block<P>> LtFmIndex<P, B> {
     }
 
     /// Locates all occurrences of a pattern in the indexed text.
+    ///
+    /// The empty pattern is located at every position of the text.
     /// 
     /// # Arguments
     /// * `pattern` - The pattern to search for
@@ -53,7 +75,7 @@ impl<P: Position, B: Block<P>> LtFmIndex<P, B> {
     }
 }
 
-impl<P: Position, B: Block<P>> LtFmIndex<P, B> {
+impl<P: Position, B: BwmBackend<P>> LtFmIndex<P, B> {
     /// Builds a new FM-index from the given text.
     /// 
     /// # Arguments
@@ -65,11 +87,141 @@ impl<P: Position, B: Block<P>> LtFmIndex<P, B> {
     /// # Returns
     /// A Result containing the built FM-index or a BuildError if construction fails
     pub fn build<T>(
+        text: Vec<u8>,
+        characters_by_index: &[T],
+        suffix_array_sampling_ratio: P,
+        lookup_table_kmer_size: u32,
+    ) -> Result<Self, BuildError>
+    where
+        T: AsRef<[u8]>,
+    {
+        Self::build_with_options(
+            text,
+            characters_by_index,
+            suffix_array_sampling_ratio,
+            lookup_table_kmer_size,
+            &BuildOptions::default(),
+        )
+    }
+    /// Builds a new FM-index from the given text with the [BuildOptions].
+    ///
+    /// The arguments other than `options` are the same as [Self::build].
+    pub fn build_with_options<T>(
+        text: Vec<u8>,
+        characters_by_index: &[T],
+        suffix_array_sampling_ratio: P,
+        lookup_table_kmer_size: u32,
+        options: &BuildOptions,
+    ) -> Result<Self, BuildError>
+    where
+        T: AsRef<[u8]>,
+    {
+        Self::build_with_monitor(
+            text,
+            characters_by_index,
+            suffix_array_sampling_ratio,
+            lookup_table_kmer_size,
+            options,
+            &mut BuildMonitor::none(),
+        )
+    }
+    /// Builds a new FM-index, reporting the progress of each [BuildPhase] to the observer.
+    ///
+    /// The build returns [BuildError::Cancelled] if the token is cancelled,
+    /// which is checked between the phases and in the long loops of the phases.
+    /// The arguments other than `observer` and `cancellation` are the same as [Self::build_with_options].
+    ///
+    /// # Example
+    /// ```rust
+    /// use std::time::Duration;
+    /// use lt_fm_index::{LtFmIndex, BuildOptions, BuildObserver, BuildPhase, CancellationToken};
+    /// use lt_fm_index::blocks::Block2;
+    ///
+    /// #[derive(Default)]
+    /// struct Timings(Vec<(BuildPhase, Duration)>);
+    /// impl BuildObserver for Timings {
+    ///     fn on_phase_finished(&mut self, phase: BuildPhase, elapsed: Duration) {
+    ///         self.0.push((phase, elapsed));
+    ///     }
+    /// }
+    ///
+    /// let mut timings = Timings::default();
+    /// let lt_fm_index = LtFmIndex::<u32, Block2<u64>>::build_with_progress(
+    ///     b"ACGTACGTAC".to_vec(),
+    ///     &[b"A", b"C", b"G"],
+    ///     2,
+    ///     4,
+    ///     &BuildOptions::default(),
+    ///     &mut timings,
+    ///     &CancellationToken::new(),
+    /// ).unwrap();
+    /// assert_eq!(timings.0.len(), 4);
+    /// ```
+    pub fn build_with_progress<T, O>(
+        text: Vec<u8>,
+        characters_by_index: &[T],
+        suffix_array_sampling_ratio: P,
+        lookup_table_kmer_size: u32,
+        options: &BuildOptions,
+        observer: &mut O,
+        cancellation: &CancellationToken,
+    ) -> Result<Self, BuildError>
+    where
+        T: AsRef<[u8]>,
+        O: BuildObserver,
+    {
+        Self::build_with_monitor(
+            text,
+            characters_by_index,
+            suffix_array_sampling_ratio,
+            lookup_table_kmer_size,
+            options,
+            &mut BuildMonitor::new(observer, cancellation),
+        )
+    }
+    fn build_with_monitor<T>(
         mut text: Vec<u8>,
         characters_by_index: &[T],
         suffix_array_sampling_ratio: P,
         lookup_table_kmer_size: u32,
+        options: &BuildOptions,
+        monitor: &mut BuildMonitor,
     ) -> Result<Self, BuildError>
+    where
+        T: AsRef<[u8]>,
+    {
+        let (chr_idx_table, chr_count) = Self::prepare_build(
+            characters_by_index,
+            suffix_array_sampling_ratio,
+            lookup_table_kmer_size,
+            options,
+        )?;
+        check_text_len::<P>(text.len())?;
+        let count_array = CountArray::new_while_encoding_text_to_chridxwp(
+            &mut text,
+            &chr_idx_table,
+            chr_count,
+            lookup_table_kmer_size,
+            options.lookup_table,
+            monitor,
+        )?;
+        Self::build_from_chridxwp_text(
+            text,
+            chr_idx_table,
+            chr_count,
+            count_array,
+            suffix_array_sampling_ratio,
+            options,
+            monitor,
+        )
+    }
+    // Checks the arguments, and makes the table of the characters
+    fn prepare_build<T>(
+        characters_by_index: &[T],
+        suffix_array_sampling_ratio: P,
+        lookup_table_kmer_size: u32,
+        options: &BuildOptions,
+    ) -> Result<(ChrIdxTable, u32), BuildError>
     where
         T: AsRef<[u8]>,
     {
@@ -79,19 +231,38 @@ impl<P: Position, B: Block<P>> LtFmIndex<P, B> {
         if lookup_table_kmer_size == 0 {
             return Err(BuildError::LookupTableKmerSize);
         }
-        let text_len = P::from_usize(text.len());
-        let (chr_idx_table, chr_count) = ChrIdxTable::new_with_counting_chr(characters_by_index);
-        if chr_count - 1 > B::MAX_CHR {
-            return Err(BuildError::IndexCountOver(B::MAX_CHR, chr_count));
+        ChrIdxTable::check_characters(characters_by_index)?;
+        let (chr_idx_table, chr_count) = ChrIdxTable::new_with_counting_chr(
+            characters_by_index,
+            options.unindexed_characters,
+        );
+        let max_chr = <B::Bwm as BurrowsWheelerMatrix<P>>::MAX_CHR;
+        if chr_count - 1 > max_chr {
+            return Err(BuildError::IndexCountOver(max_chr, chr_count));
         }
-        let count_array = CountArray::new_while_encoding_text_to_chridxwp(
+        if !CountArray::<P>::table_length_fits(chr_count, lookup_table_kmer_size) {
+            return Err(BuildError::LookupTableOverflow(lookup_table_kmer_size));
+        }
+        Ok((chr_idx_table, chr_count))
+    }
+    // Builds with the text encoded to chridxwp
+    fn build_from_chridxwp_text(
+        mut text: Vec<u8>,
+        chr_idx_table: ChrIdxTable,
+        chr_count: u32,
+        count_array: CountArray<P>,
+        suffix_array_sampling_ratio: P,
+        options: &BuildOptions,
+        monitor: &mut BuildMonitor,
+    ) -> Result<Self, BuildError> {
+        let text_len = P::from_usize(text.len());
+        let (suffix_array, pidx) = SuffixArray::new_while_bwt(
             &mut text,
-            &chr_idx_table,
-            chr_count,
-            lookup_table_kmer_size,
-        );
-        let (suffix_array, pidx) = SuffixArray::new_while_bwt(&mut text, suffix_array_sampling_ratio);
-        let bwm = Bwm::new(text, pidx, chr_count);
+            suffix_array_sampling_ratio,
+            options.suffix_array_sampling,
+            monitor,
+        )?;
+        let bwm = B::Bwm::new(text, pidx, chr_count, monitor)?;
         Ok(Self {
             text_len,
             chr_idx_table,
@@ -103,6 +274,12 @@ impl<P: Position, B: Block<P>> LtFmIndex<P, B> {
     
     // Pos range
     fn get_pos_range(&self, pattern: &[u8]) -> (P, P) {
+        if pattern.is_empty() {
+            return (P::ZERO, self.text_len);
+        }
+        if self.chr_idx_table.has_separator(pattern) {
+            return (P::ZERO, P::ZERO);
+        }
         let (mut pos_range, mut idx) = self.count_array.get_initial_pos_range_and_idx_of_pattern(
             pattern,
             &self.chr_idx_table,
@@ -118,8 +295,7 @@ impl<P: Position, B: Block<P>> LtFmIndex<P, B> {
     fn next_pos_range(&self, pos_range: (P, P), chr: u8) -> (P, P) {
         let chridx = self.chr_idx_table.idx_of(chr);
         let precount = self.count_array.get_precount(chridx as usize);
-        let start_rank = self.bwm.get_next_rank(pos_range.0, chridx);
-        let end_rank = self.bwm.get_next_rank(pos_range.1, chridx);
+        let (start_rank, end_rank) = self.bwm.get_next_ranks(pos_range, chridx);
         (precount + start_rank, precount + end_rank)
     }
 
@@ -129,7 +305,7 @@ impl<P: Position, B: Block<P>> LtFmIndex<P, B> {
 
         'each_pos: for mut pos in P::as_vec_in_range(&pos_range.0, &pos_range.1) {
             let mut offset: P = P::ZERO;
-            while pos % self.suffix_array.sampling_ratio() != P::ZERO { 
+            while !self.suffix_array.is_sampled(pos) {
                 match self.bwm.get_pre_rank_and_chridx(pos) {
                     Some((rank, chridx)) => {
                         let precount = self.count_array.get_precount(chridx as usize);
@@ -149,4 +325,45 @@ impl<P: Position, B: Block<P>> LtFmIndex<P, B> {
     }
 }
 
+// Checks that the text is not empty, and its length fits in the position
+pub(crate) fn check_text_len<P: Position>(text_len: usize) -> Result<(), BuildError> {
+    if text_len == 0 {
+        return Err(BuildError::EmptyText);
+    }
+    if text_len as u64 > u64::MAX >> (u64::BITS - P::BITS) {
+        return Err(BuildError::TextTooLong(text_len as u64, P::BITS));
+    }
+    Ok(())
+}
+
 mod features;
+pub use features::ComponentSizes;
+
+mod rl_fm_index;
+pub use rl_fm_index::RlFmIndex;
+
+mod dynamic_index;
+pub use dynamic_index::DynamicLtFmIndex;
+
+mod any_index;
+pub use any_index::{AnyLtFmIndex, PositionType, BackendType};
+
+#[cfg(feature = "bio-formats")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bio-formats")))]
+pub mod bio_formats;
+
+#[cfg(feature = "server")]
+#[cfg_attr(docsrs, doc(cfg(feature = "server")))]
+pub mod server;
+
+#[cfg(feature = "capi")]
+#[cfg_attr(docsrs, doc(cfg(feature = "capi")))]
+pub mod capi;
+
+#[cfg(feature = "python")]
+#[cfg_attr(docsrs, doc(cfg(feature = "python")))]
+pub mod python;
+
+#[cfg(feature = "wasm")]
+#[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+pub mod wasm;
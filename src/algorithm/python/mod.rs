@@ -0,0 +1,188 @@
+/*!
+Python extension module (`python` feature).
+
+The module `lt_fm_index` exposes the [LtFmIndex] as the class `LtFmIndex`,
+with the position and backend chosen at runtime.
+It is built with `maturin` from `pyproject.toml` in the repository:
+```sh
+maturin develop --release
+```
+```python
+from lt_fm_index import LtFmIndex
+
+index = LtFmIndex(b"ACGTACGTAC", alphabet="dna", sampling_ratio=2)
+index.count(b"ACG")         # 2
+index.locate("ACG")         # [0, 4]
+index.locate_array(b"ACG")  # array('Q', [0, 4]), usable by `numpy.frombuffer(..., dtype=numpy.uint64)`
+index.save("genome.idx")
+index = LtFmIndex.load("genome.idx")
+```
+*/
+use std::io::{Write, BufReader, BufWriter};
+
+use pyo3::prelude::*;
+use pyo3::exceptions::{PyValueError, PyTypeError};
+use pyo3::types::{PyBytes, PyString, PyList};
+
+use super::{AnyLtFmIndex, PositionType, BackendType, BuildOptions, Alphabet};
+
+/// `LtFmIndex` class of Python.
+///
+/// The text and patterns are `bytes` or `str`.
+/// The position (`"u32"` or `"u64"`) and backend (`"block2"`-`"block6"` or `"wavelet"`)
+/// are chosen from the text and alphabet, if not given.
+#[pyclass(name = "LtFmIndex", module = "lt_fm_index", frozen)]
+pub struct PyLtFmIndex {
+    index: AnyLtFmIndex,
+}
+
+#[pymethods]
+impl PyLtFmIndex {
+    /// Builds the index of the text.
+    ///
+    /// The `characters` (list of the groups of characters treated as the same) overrides the `alphabet` preset
+    /// (`"dna"`, `"dna-n"`, `"rna"`, `"iupac"`, `"protein"`, or `"reduced-protein"`, case-insensitive).
+    #[new]
+    #[pyo3(signature = (
+        text,
+        alphabet = "dna",
+        characters = None,
+        position = None,
+        backend = None,
+        sampling_ratio = 2,
+        kmer_size = None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        py: Python<'_>,
+        text: &Bound<'_, PyAny>,
+        alphabet: &str,
+        characters: Option<Vec<Bound<'_, PyAny>>>,
+        position: Option<&str>,
+        backend: Option<&str>,
+        sampling_ratio: u64,
+        kmer_size: Option<u32>,
+    ) -> PyResult<Self> {
+        let text = bytes_of(text)?;
+        let characters_by_index = match characters {
+            Some(characters) => characters.iter().map(bytes_of).collect::<PyResult<Vec<_>>>()?,
+            None => alphabet_preset(alphabet)?,
+        };
+        let position = match position {
+            Some(name) => PositionType::from_name(name).ok_or_else(|| invalid_type("position", name))?,
+            None => PositionType::fitting(text.len() as u64),
+        };
+        let backend = match backend {
+            Some(name) => BackendType::from_name(name).ok_or_else(|| invalid_type("backend", name))?,
+            None => BackendType::fitting(characters_by_index.len()),
+        };
+        let kmer_size = kmer_size.unwrap_or_else(|| AnyLtFmIndex::default_kmer_size(characters_by_index.len()));
+        let index = py.detach(|| AnyLtFmIndex::build(
+            text,
+            &characters_by_index,
+            position,
+            backend,
+            sampling_ratio,
+            kmer_size,
+            &BuildOptions::default(),
+        )).map_err(|err| PyValueError::new_err(err.to_string()))?;
+        Ok(Self { index })
+    }
+    /// Loads the index saved by `save`, or by the other bindings and the command-line tool.
+    #[staticmethod]
+    fn load(py: Python<'_>, path: &str) -> PyResult<Self> {
+        py.detach(|| {
+            let reader = BufReader::new(std::fs::File::open(path)?);
+            let index = AnyLtFmIndex::load_from(reader)?;
+            Ok(Self { index })
+        })
+    }
+    /// Saves the index to the file of the path.
+    fn save(&self, py: Python<'_>, path: &str) -> PyResult<()> {
+        py.detach(|| {
+            let mut writer = BufWriter::new(std::fs::File::create(path)?);
+            self.index.save_to(&mut writer)?;
+            writer.flush()?;
+            Ok(())
+        })
+    }
+    /// Counts the occurrences of the pattern.
+    fn count(&self, pattern: &Bound<'_, PyAny>) -> PyResult<u64> {
+        Ok(self.index.count(&bytes_of(pattern)?))
+    }
+    /// Locates the occurrences of the pattern, in ascending order.
+    fn locate<'py>(&self, pattern: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyList>> {
+        PyList::new(pattern.py(), self.sorted_locations(pattern)?)
+    }
+    /// Locates the occurrences of the pattern as `array.array("Q")`, in ascending order.
+    ///
+    /// The locations are copied into a new array, which supports the buffer protocol,
+    /// so `numpy.frombuffer` views the array without copying it again.
+    fn locate_array<'py>(&self, pattern: &Bound<'py, PyAny>) -> PyResult<Bound<'py, PyAny>> {
+        let py = pattern.py();
+        let locations = self.sorted_locations(pattern)?;
+        let bytes = PyBytes::new(py, bytemuck::cast_slice(&locations));
+        let array = py.import("array")?.getattr("array")?.call1(("Q",))?;
+        array.call_method1("frombytes", (bytes,))?;
+        Ok(array)
+    }
+    /// Position type of the index (`"u32"` or `"u64"`).
+    #[getter]
+    fn position(&self) -> &'static str {
+        self.index.position_type().name()
+    }
+    /// Backend of the index (`"block2"`-`"block6"` or `"wavelet"`).
+    #[getter]
+    fn backend(&self) -> &'static str {
+        self.index.backend_type().name()
+    }
+    /// Length of the indexed text.
+    fn __len__(&self) -> usize {
+        self.index.len_text() as usize
+    }
+    fn __repr__(&self) -> String {
+        format!(
+            "LtFmIndex(len={}, position={:?}, backend={:?})",
+            self.index.len_text(), self.index.position_type().name(), self.index.backend_type().name(),
+        )
+    }
+}
+
+impl PyLtFmIndex {
+    fn sorted_locations(&self, pattern: &Bound<'_, PyAny>) -> PyResult<Vec<u64>> {
+        let mut locations = self.index.locate(&bytes_of(pattern)?);
+        locations.sort_unstable();
+        Ok(locations)
+    }
+}
+
+/// Module `lt_fm_index` of Python.
+#[pymodule]
+#[pyo3(name = "lt_fm_index")]
+pub fn python_module(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyLtFmIndex>()?;
+    Ok(())
+}
+
+fn bytes_of(value: &Bound<'_, PyAny>) -> PyResult<Vec<u8>> {
+    if let Ok(bytes) = value.cast::<PyBytes>() {
+        Ok(bytes.as_bytes().to_vec())
+    } else if let Ok(string) = value.cast::<PyString>() {
+        Ok(string.to_str()?.as_bytes().to_vec())
+    } else {
+        Err(PyTypeError::new_err("Expected bytes or str"))
+    }
+}
+fn invalid_type(kind: &str, name: &str) -> PyErr {
+    PyValueError::new_err(format!(
+        "Unknown {}: {} (u32 or u64 for the position, and block2-block6 or wavelet for the backend)", kind, name,
+    ))
+}
+fn alphabet_preset(name: &str) -> PyResult<Vec<Vec<u8>>> {
+    match Alphabet::preset(name) {
+        Some(alphabet) => Ok(alphabet.groups().to_vec()),
+        None => Err(PyValueError::new_err(format!(
+            "Unknown alphabet: {} ({})", name, Alphabet::PRESET_NAMES.join(", "),
+        ))),
+    }
+}
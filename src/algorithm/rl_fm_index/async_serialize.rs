@@ -0,0 +1,94 @@
+use std::pin::Pin;
+
+use crate::core::{Position, AsyncSerialize};
+use super::{RlFmIndex, Runs, ChrIdxTable, CountArray};
+use capwriter::{AsyncSave, AsyncLoad};
+
+impl<P: Position> RlFmIndex<P> {
+    pub fn async_save_to<W>(&self, mut writer: Pin<&mut W>) -> impl Future<Output = Result<(), std::io::Error>> + Send where
+        W: tokio::io::AsyncWrite + Send,
+    {
+        async move {
+            // text_len
+            self.text_len.as_u64().save_as_ne(writer.as_mut()).await?;
+            // chr_idx_table
+            self.chr_idx_table.async_save_to(writer.as_mut()).await?;
+            // count_array
+            self.count_array.async_save_to(writer.as_mut()).await?;
+            // kmer_toeholds
+            self.kmer_toeholds.save_as_ne(writer.as_mut()).await?;
+            // runs
+            self.runs.async_save_to(writer.as_mut()).await?;
+            // phi
+            self.phi_keys.save_as_ne(writer.as_mut()).await?;
+            self.phi_values.save_as_ne(writer.as_mut()).await?;
+            Ok(())
+        }
+    }
+    pub fn async_load_from<R>(mut reader: Pin<&mut R>) -> impl Future<Output = Result<Self, std::io::Error>> + Send where
+        R: tokio::io::AsyncRead + Send,
+        Self: Sized
+    {
+        async move {
+            let text_len = P::from_u64(u64::load_as_ne(reader.as_mut()).await?);
+            let chr_idx_table = ChrIdxTable::async_load_from(reader.as_mut()).await?;
+            let count_array = CountArray::async_load_from(reader.as_mut()).await?;
+            let kmer_toeholds = Vec::<P>::load_as_ne(reader.as_mut()).await?;
+            let runs = Runs::async_load_from(reader.as_mut()).await?;
+            let phi_keys = Vec::<P>::load_as_ne(reader.as_mut()).await?;
+            let phi_values = Vec::<P>::load_as_ne(reader.as_mut()).await?;
+            Ok(Self {
+                text_len,
+                chr_idx_table,
+                count_array,
+                kmer_toeholds,
+                runs,
+                phi_keys,
+                phi_values,
+            })
+        }
+    }
+}
+
+impl<P: Position> AsyncSerialize for Runs<P> {
+    fn async_save_to<W>(&self, mut writer: Pin<&mut W>) -> impl Future<Output = Result<(), std::io::Error>> + Send where
+        W: tokio::io::AsyncWrite + Send,
+    {
+        async move {
+            self.heads.save_as_ne(writer.as_mut()).await?;
+            self.starts.save_as_ne(writer.as_mut()).await?;
+            self.end_locations.save_as_ne(writer.as_mut()).await?;
+            // run_ids_of_chr and pre_counts_of_chr have the same length
+            (self.run_ids_of_chr.len() as u64).save_as_ne(writer.as_mut()).await?;
+            for (run_ids, pre_counts) in self.run_ids_of_chr.iter().zip(self.pre_counts_of_chr.iter()) {
+                run_ids.save_as_ne(writer.as_mut()).await?;
+                pre_counts.save_as_ne(writer.as_mut()).await?;
+            }
+            Ok(())
+        }
+    }
+    fn async_load_from<R>(mut reader: Pin<&mut R>) -> impl Future<Output = Result<Self, std::io::Error>> + Send where
+        R: tokio::io::AsyncRead + Send,
+        Self: Sized,
+    {
+        async move {
+            let heads = Vec::<u8>::load_as_ne(reader.as_mut()).await?;
+            let starts = Vec::<P>::load_as_ne(reader.as_mut()).await?;
+            let end_locations = Vec::<P>::load_as_ne(reader.as_mut()).await?;
+            let chr_with_pidx_count = u64::load_as_ne(reader.as_mut()).await? as usize;
+            let mut run_ids_of_chr = Vec::with_capacity(chr_with_pidx_count);
+            let mut pre_counts_of_chr = Vec::with_capacity(chr_with_pidx_count);
+            for _ in 0..chr_with_pidx_count {
+                run_ids_of_chr.push(Vec::<P>::load_as_ne(reader.as_mut()).await?);
+                pre_counts_of_chr.push(Vec::<P>::load_as_ne(reader.as_mut()).await?);
+            }
+            Ok(Self {
+                heads,
+                starts,
+                end_locations,
+                run_ids_of_chr,
+                pre_counts_of_chr,
+            })
+        }
+    }
+}
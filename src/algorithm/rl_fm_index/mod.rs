@@ -0,0 +1,338 @@
+use crate::core::{
+    Position,
+    errors::{BuildError, QueryError},
+};
+use super::{Alphabet, EncodedPattern, ChrIdxTable, SuffixArray, CountArray, BuildOptions, BuildMonitor, UnindexedCharacters, check_text_len};
+
+/// FM-index over the run-length encoded BWT (r-index), using lookup table for first k-mer search.
+///
+/// The size is proportional to the count of runs in the BWT instead of the length of the text,
+/// so this suits highly repetitive collections (e.g. many genomes of the same species).
+/// The suffix array is sampled at the boundaries of runs, and `locate` finds one occurrence
+/// during the backward search (toehold) and the others from it.
+#[derive(Clone, PartialEq, Eq)]
+pub struct RlFmIndex<P: Position> {
+    text_len: P,
+    chr_idx_table: ChrIdxTable,
+    count_array: CountArray<P>,
+    // Location of the last row of the range of each stored accumulated count of the k-mer count table
+    kmer_toeholds: Vec<P>,
+    runs: Runs<P>,
+    // For `phi`: location of the first row of each run (sorted) and the location of the row before it
+    phi_keys: Vec<P>,
+    phi_values: Vec<P>,
+}
+
+// Run-length encoded BWT including the sentinel (chridxwp: 0)
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Runs<P: Position> {
+    heads: Vec<u8>,
+    starts: Vec<P>,
+    // Location of the last row of each run
+    end_locations: Vec<P>,
+    // For each chridxwp, the runs of the chr and the count of the chr before each of them
+    run_ids_of_chr: Vec<Vec<P>>,
+    pre_counts_of_chr: Vec<Vec<P>>,
+}
+
+impl<P: Position> RlFmIndex<P> {
+    const MAX_CHR: u32 = 254;
+
+    /// Counts the number of occurrences of a pattern in the indexed text.
+    ///
+    /// The empty pattern occurs at every position, so its count is the length of the text.
+    #[inline]
+    pub fn count(&self, pattern: &[u8]) -> P {
+        let (pos_range, _) = self.get_pos_range_and_toehold(pattern);
+        pos_range.1 - pos_range.0
+    }
+    /// Locates all occurrences of a pattern in the indexed text.
+    ///
+    /// The locations are not in order. The empty pattern is located at every position of the text.
+    #[inline]
+    pub fn locate(&self, pattern: &[u8]) -> Vec<P> {
+        self.get_locations(self.get_pos_range_and_toehold(pattern))
+    }
+    /// Encodes the pattern with the decoding table of this index.
+    pub fn encode_pattern(&self, pattern: &[u8]) -> EncodedPattern {
+        EncodedPattern::new(pattern, self.decoding_table())
+    }
+    /// Counts the number of occurrences of an [EncodedPattern] in the indexed text.
+    ///
+    /// The pattern must be encoded with the decoding table of this index,
+    /// otherwise this may panic or return the wrong count.
+    /// [Self::try_count_encoded_pattern] checks the decoding table.
+    #[inline]
+    pub fn count_encoded_pattern(&self, pattern: &EncodedPattern) -> P {
+        let (pos_range, _) = self.get_pos_range_and_toehold_of_encoded_pattern(pattern);
+        pos_range.1 - pos_range.0
+    }
+    /// Locates all occurrences of an [EncodedPattern] in the indexed text.
+    ///
+    /// The pattern must be encoded with the decoding table of this index,
+    /// otherwise this may panic or return the wrong locations.
+    /// [Self::try_locate_encoded_pattern] checks the decoding table.
+    #[inline]
+    pub fn locate_encoded_pattern(&self, pattern: &EncodedPattern) -> Vec<P> {
+        self.get_locations(self.get_pos_range_and_toehold_of_encoded_pattern(pattern))
+    }
+    /// Checked version of [Self::count_encoded_pattern].
+    ///
+    /// Returns [QueryError::IncompatibleAlphabet] if the pattern is not encoded with the decoding table of this index.
+    pub fn try_count_encoded_pattern(&self, pattern: &EncodedPattern) -> Result<P, QueryError> {
+        pattern.check_encoded_with(self.decoding_table())?;
+        Ok(self.count_encoded_pattern(pattern))
+    }
+    /// Checked version of [Self::locate_encoded_pattern].
+    ///
+    /// Returns [QueryError::IncompatibleAlphabet] if the pattern is not encoded with the decoding table of this index.
+    pub fn try_locate_encoded_pattern(&self, pattern: &EncodedPattern) -> Result<Vec<P>, QueryError> {
+        pattern.check_encoded_with(self.decoding_table())?;
+        Ok(self.locate_encoded_pattern(pattern))
+    }
+    // The toehold and the locations found from it by `phi`
+    fn get_locations(&self, (pos_range, toehold): ((P, P), P)) -> Vec<P> {
+        let count = (pos_range.1 - pos_range.0).as_usize();
+        let mut locations = Vec::with_capacity(count);
+        if count != 0 {
+            let mut location = toehold;
+            locations.push(location);
+            for _ in 1..count {
+                location = self.phi(location);
+                locations.push(location);
+            }
+        }
+        locations
+    }
+
+    /// Builds a new run-length FM-index from the given text.
+    ///
+    /// # Arguments
+    /// * `text` - The text to index
+    /// * `characters_by_index` - The characters to index, in order of their indices
+    /// * `lookup_table_kmer_size` - The size of k-mers to use in the lookup table
+    pub fn build<T>(
+        text: Vec<u8>,
+        characters_by_index: &[T],
+        lookup_table_kmer_size: u32,
+    ) -> Result<Self, BuildError>
+    where
+        T: AsRef<[u8]>,
+    {
+        Self::build_with_options(
+            text,
+            characters_by_index,
+            lookup_table_kmer_size,
+            &BuildOptions::default(),
+        )
+    }
+    /// Builds a new run-length FM-index from the given text with the [BuildOptions].
+    ///
+    /// The arguments other than `options` are the same as [Self::build].
+    pub fn build_with_options<T>(
+        mut text: Vec<u8>,
+        characters_by_index: &[T],
+        lookup_table_kmer_size: u32,
+        options: &BuildOptions,
+    ) -> Result<Self, BuildError>
+    where
+        T: AsRef<[u8]>,
+    {
+        if lookup_table_kmer_size == 0 {
+            return Err(BuildError::LookupTableKmerSize);
+        }
+        ChrIdxTable::check_characters(characters_by_index)?;
+        check_text_len::<P>(text.len())?;
+        let text_len = P::from_usize(text.len());
+        let (chr_idx_table, chr_count) = ChrIdxTable::new_with_counting_chr(
+            characters_by_index,
+            options.unindexed_characters,
+        );
+        if chr_count - 1 > Self::MAX_CHR {
+            return Err(BuildError::IndexCountOver(Self::MAX_CHR, chr_count));
+        }
+        if !CountArray::<P>::table_length_fits(chr_count, lookup_table_kmer_size) {
+            return Err(BuildError::LookupTableOverflow(lookup_table_kmer_size));
+        }
+        let count_array = CountArray::new_while_encoding_text_to_chridxwp(
+            &mut text,
+            &chr_idx_table,
+            chr_count,
+            lookup_table_kmer_size,
+            options.lookup_table,
+            &mut BuildMonitor::none(),
+        )?;
+        let (suffix_array, pidx) = SuffixArray::<P>::raw_suffix_array_while_bwt(&mut text);
+        let kmer_toeholds = count_array.kmer_count_table.stored_accumed_counts().iter().map(|&accumed_count: &P| {
+            if accumed_count == P::ZERO {
+                P::ZERO
+            } else {
+                suffix_array[(accumed_count - P::ONE).as_usize()]
+            }
+        }).collect();
+
+        // Insert the sentinel to the BWT, and the location of the sentinel to the suffix array
+        let mut bwt = text;
+        bwt.insert(pidx.as_usize(), 0);
+        let location_of_row = |row: usize| {
+            if row == 0 { text_len } else { suffix_array[row - 1] }
+        };
+
+        let mut heads = Vec::new();
+        let mut starts = Vec::new();
+        let mut end_locations = Vec::new();
+        let mut phi_pairs = Vec::new();
+        let mut run_ids_of_chr = vec![Vec::new(); chr_count as usize + 1];
+        let mut pre_counts_of_chr = vec![vec![P::ZERO]; chr_count as usize + 1];
+        bwt.iter().enumerate().for_each(|(row, &chridxwp)| {
+            if row == 0 || bwt[row - 1] != chridxwp {
+                if row != 0 {
+                    end_locations.push(location_of_row(row - 1));
+                    phi_pairs.push((location_of_row(row), location_of_row(row - 1)));
+                }
+                run_ids_of_chr[chridxwp as usize].push(P::from_usize(heads.len()));
+                let pre_counts = &mut pre_counts_of_chr[chridxwp as usize];
+                let last_count = *pre_counts.last().unwrap();
+                pre_counts.push(last_count);
+                heads.push(chridxwp);
+                starts.push(P::from_usize(row));
+            }
+            *pre_counts_of_chr[chridxwp as usize].last_mut().unwrap() += P::ONE;
+        });
+        end_locations.push(location_of_row(bwt.len() - 1));
+        starts.push(P::from_usize(bwt.len()));
+        phi_pairs.sort_unstable();
+        let (phi_keys, phi_values) = phi_pairs.into_iter().unzip();
+
+        Ok(Self {
+            text_len,
+            chr_idx_table,
+            count_array,
+            kmer_toeholds,
+            runs: Runs {
+                heads,
+                starts,
+                end_locations,
+                run_ids_of_chr,
+                pre_counts_of_chr,
+            },
+            phi_keys,
+            phi_values,
+        })
+    }
+
+    // Pos range (in the rows without the sentinel) and the location of the last row of it
+    fn get_pos_range_and_toehold(&self, pattern: &[u8]) -> ((P, P), P) {
+        if self.chr_idx_table.has_separator(pattern) {
+            return ((P::ZERO, P::ZERO), P::ZERO);
+        }
+        self.get_pos_range_and_toehold_of_chridx(pattern.len(), |idx| self.chr_idx_table.idx_of(pattern[idx]))
+    }
+    fn get_pos_range_and_toehold_of_encoded_pattern(&self, pattern: &EncodedPattern) -> ((P, P), P) {
+        let raw_index = pattern.raw_index();
+        if raw_index.iter().any(|&chridx| self.chr_idx_table.is_separator(chridx)) {
+            return ((P::ZERO, P::ZERO), P::ZERO);
+        }
+        self.get_pos_range_and_toehold_of_chridx(raw_index.len(), |idx| raw_index[idx])
+    }
+    fn get_pos_range_and_toehold_of_chridx(&self, pattern_len: usize, chridx_of: impl Fn(usize) -> u8) -> ((P, P), P) {
+        if pattern_len == 0 {
+            return ((P::ZERO, self.text_len), *self.runs.end_locations.last().unwrap());
+        }
+        let (mut pos_range, table_idx, mut idx) = self.count_array.get_initial_pos_range_and_table_idx_of_chridx(
+            pattern_len,
+            &chridx_of,
+        );
+        let mut toehold = self.kmer_toeholds[self.count_array.kmer_count_table.stored_idx(table_idx)];
+        // LF mapping with the rows including the sentinel
+        while pos_range.0 < pos_range.1 && idx > 0 {
+            idx -= 1;
+            let chridxwp = chridx_of(idx) + 1;
+            let last_row = pos_range.1; // `pos_range.1 - 1` + 1 for the sentinel
+            let run_id = self.runs.run_id_of_row(last_row);
+            toehold = if self.runs.heads[run_id] == chridxwp {
+                toehold - P::ONE
+            } else {
+                let run_ids = &self.runs.run_ids_of_chr[chridxwp as usize];
+                let count = run_ids.partition_point(|&x| x.as_usize() < run_id);
+                match count {
+                    0 => P::ZERO, // The range will be empty
+                    _ => self.runs.end_locations[run_ids[count - 1].as_usize()] - P::ONE,
+                }
+            };
+            let precount = self.count_array.get_precount(chridxwp as usize - 1);
+            pos_range = (
+                precount + self.runs.rank(pos_range.0 + P::ONE, chridxwp),
+                precount + self.runs.rank(pos_range.1 + P::ONE, chridxwp),
+            );
+        }
+        (pos_range, toehold)
+    }
+    // Location of the row before the row of the location
+    fn phi(&self, location: P) -> P {
+        let idx = self.phi_keys.partition_point(|&key| key <= location) - 1;
+        self.phi_values[idx] + (location - self.phi_keys[idx])
+    }
+
+    /// Length of the indexed text.
+    pub fn len_text(&self) -> P {
+        self.text_len
+    }
+    /// Semantics of the unindexed characters.
+    pub fn unindexed_characters(&self) -> UnindexedCharacters {
+        self.chr_idx_table.unindexed_characters()
+    }
+    /// Count of the runs in the BWT.
+    pub fn run_count(&self) -> usize {
+        self.runs.heads.len()
+    }
+    /// Returns the decoding table.
+    /// The decoding table is a 256-length array that stores the indices for 1-byte characters.
+    pub fn decoding_table(&self) -> &[u8; 256] {
+        &self.chr_idx_table.table
+    }
+    /// The groups of the characters the index is built with.
+    pub fn alphabet(&self) -> Alphabet {
+        Alphabet::from_chr_idx_table(&self.chr_idx_table, self.count_array.chr_count())
+    }
+}
+
+impl<P: Position> Runs<P> {
+    #[inline]
+    fn run_id_of_row(&self, row: P) -> usize {
+        self.starts.partition_point(|&start| start <= row) - 1
+    }
+    // Count of chridxwp in the rows `[0, row)`
+    #[inline]
+    fn rank(&self, row: P, chridxwp: u8) -> P {
+        if row == P::ZERO {
+            return P::ZERO;
+        }
+        let run_id = self.run_id_of_row(row - P::ONE);
+        let run_ids = &self.run_ids_of_chr[chridxwp as usize];
+        let count = run_ids.partition_point(|&x| x.as_usize() < run_id);
+        let pre_count = self.pre_counts_of_chr[chridxwp as usize][count];
+        if self.heads[run_id] == chridxwp {
+            pre_count + row - self.starts[run_id]
+        } else {
+            pre_count
+        }
+    }
+}
+
+impl<P: Position> std::fmt::Debug for RlFmIndex<P> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RlFmIndex")
+            .field("bit_size_for_position", &P::BITS)
+            .field("text_length", &self.len_text())
+            .field("lookup_table_kmer_size", &self.count_array.kmer_size())
+            .field("lookup_table", &self.count_array.lookup_table())
+            .field("unindexed_characters", &self.unindexed_characters())
+            .field("run_count", &self.run_count())
+            .finish()
+    }
+}
+
+mod serialize;
+#[cfg(feature = "async-io")]
+mod async_serialize;
@@ -0,0 +1,101 @@
+use crate::core::{Position, Serialize};
+use super::{RlFmIndex, Runs, ChrIdxTable, CountArray};
+use capwriter::{Save, Load};
+
+impl<P: Position> RlFmIndex<P> {
+    pub fn save_to<W>(&self, mut writer: W) -> Result<(), std::io::Error> where
+        W: std::io::Write
+    {
+        // text_len
+        self.text_len.as_u64().save_as_ne(&mut writer)?;
+        // chr_idx_table
+        self.chr_idx_table.save_to(&mut writer)?;
+        // count_array
+        self.count_array.save_to(&mut writer)?;
+        // kmer_toeholds
+        self.kmer_toeholds.save_as_ne(&mut writer)?;
+        // runs
+        self.runs.save_to(&mut writer)?;
+        // phi
+        self.phi_keys.save_as_ne(&mut writer)?;
+        self.phi_values.save_as_ne(&mut writer)?;
+        Ok(())
+    }
+    pub fn load_from<R>(mut reader: R) -> Result<Self, std::io::Error> where
+        R: std::io::Read,
+        Self: Sized
+    {
+        let text_len = P::from_u64(u64::load_as_ne(&mut reader)?);
+        let chr_idx_table = ChrIdxTable::load_from(&mut reader)?;
+        let count_array = CountArray::load_from(&mut reader)?;
+        let kmer_toeholds = Vec::<P>::load_as_ne(&mut reader)?;
+        let runs = Runs::load_from(&mut reader)?;
+        let phi_keys = Vec::<P>::load_as_ne(&mut reader)?;
+        let phi_values = Vec::<P>::load_as_ne(&mut reader)?;
+        Ok(Self {
+            text_len,
+            chr_idx_table,
+            count_array,
+            kmer_toeholds,
+            runs,
+            phi_keys,
+            phi_values,
+        })
+    }
+    pub fn encoded_len(&self) -> usize {
+        8 // text_len
+        + self.chr_idx_table.encoded_len() // chr_idx_table
+        + self.count_array.encoded_len() // count_array
+        + self.kmer_toeholds.encoded_len() // kmer_toeholds
+        + self.runs.encoded_len() // runs
+        + self.phi_keys.encoded_len() // phi_keys
+        + self.phi_values.encoded_len() // phi_values
+    }
+}
+
+impl<P: Position> Serialize for Runs<P> {
+    fn save_to<W>(&self, writer: &mut W) -> Result<(), std::io::Error> where
+        W: std::io::Write,
+    {
+        self.heads.save_as_ne(writer)?;
+        self.starts.save_as_ne(writer)?;
+        self.end_locations.save_as_ne(writer)?;
+        // run_ids_of_chr and pre_counts_of_chr have the same length
+        (self.run_ids_of_chr.len() as u64).save_as_ne(writer)?;
+        for (run_ids, pre_counts) in self.run_ids_of_chr.iter().zip(self.pre_counts_of_chr.iter()) {
+            run_ids.save_as_ne(writer)?;
+            pre_counts.save_as_ne(writer)?;
+        }
+        Ok(())
+    }
+    fn load_from<R>(reader: &mut R) -> Result<Self, std::io::Error> where
+        R: std::io::Read,
+        Self: Sized,
+    {
+        let heads = Vec::<u8>::load_as_ne(reader)?;
+        let starts = Vec::<P>::load_as_ne(reader)?;
+        let end_locations = Vec::<P>::load_as_ne(reader)?;
+        let chr_with_pidx_count = u64::load_as_ne(reader)? as usize;
+        let mut run_ids_of_chr = Vec::with_capacity(chr_with_pidx_count);
+        let mut pre_counts_of_chr = Vec::with_capacity(chr_with_pidx_count);
+        for _ in 0..chr_with_pidx_count {
+            run_ids_of_chr.push(Vec::<P>::load_as_ne(reader)?);
+            pre_counts_of_chr.push(Vec::<P>::load_as_ne(reader)?);
+        }
+        Ok(Self {
+            heads,
+            starts,
+            end_locations,
+            run_ids_of_chr,
+            pre_counts_of_chr,
+        })
+    }
+    fn encoded_len(&self) -> usize {
+        self.heads.encoded_len() // heads
+        + self.starts.encoded_len() // starts
+        + self.end_locations.encoded_len() // end_locations
+        + 8 // chr_with_pidx_count
+        + self.run_ids_of_chr.iter().map(|x| x.encoded_len()).sum::<usize>() // run_ids_of_chr
+        + self.pre_counts_of_chr.iter().map(|x| x.encoded_len()).sum::<usize>() // pre_counts_of_chr
+    }
+}
@@ -0,0 +1,97 @@
+use tokio::io::{AsyncRead, AsyncWrite, AsyncBufReadExt, AsyncWriteExt, BufStream};
+
+/// Client sending the queries to [super::IndexServer].
+///
+/// The error of the server is returned as [std::io::ErrorKind::Other].
+pub struct IndexClient<S: AsyncRead + AsyncWrite + Unpin> {
+    stream: BufStream<S>,
+    response: String,
+}
+
+impl IndexClient<tokio::net::TcpStream> {
+    /// Connects to the server on the TCP address.
+    pub async fn connect_tcp<A: tokio::net::ToSocketAddrs>(address: A) -> Result<Self, std::io::Error> {
+        Ok(Self::new(tokio::net::TcpStream::connect(address).await?))
+    }
+}
+
+#[cfg(unix)]
+impl IndexClient<tokio::net::UnixStream> {
+    /// Connects to the server on the Unix socket.
+    pub async fn connect_unix<A: AsRef<std::path::Path>>(path: A) -> Result<Self, std::io::Error> {
+        Ok(Self::new(tokio::net::UnixStream::connect(path).await?))
+    }
+}
+
+impl<S: AsyncRead + AsyncWrite + Unpin> IndexClient<S> {
+    /// Makes a client on the connected stream.
+    pub fn new(stream: S) -> Self {
+        Self {
+            stream: BufStream::new(stream),
+            response: String::new(),
+        }
+    }
+    /// Counts the number of occurrences of a pattern in the index of the name.
+    pub async fn count(&mut self, index_name: &str, pattern: &[u8]) -> Result<u64, std::io::Error> {
+        let response = self.request(&[b"COUNT ", index_name.as_bytes(), b" ", pattern]).await?;
+        parse_number(response)
+    }
+    /// Locates all occurrences of a pattern in the index of the name, in order.
+    pub async fn locate(&mut self, index_name: &str, pattern: &[u8]) -> Result<Vec<u64>, std::io::Error> {
+        let response = self.request(&[b"LOCATE ", index_name.as_bytes(), b" ", pattern]).await?;
+        parse_counted_values(response)?.map(parse_number).collect()
+    }
+    /// Locates all occurrences of a pattern in the index of the sequences of the name,
+    /// as the name of the sequence and the zero-based offset in it, in order.
+    pub async fn locate_in_contigs(&mut self, index_name: &str, pattern: &[u8]) -> Result<Vec<(String, u64)>, std::io::Error> {
+        let response = self.request(&[b"LOCATE_IN_CONTIGS ", index_name.as_bytes(), b" ", pattern]).await?;
+        parse_counted_values(response)?.map(|location| {
+            let (contig, offset) = location.rsplit_once(':').ok_or_else(|| invalid_data("Invalid location in response"))?;
+            Ok((contig.to_string(), parse_number(offset)?))
+        }).collect()
+    }
+    /// Names of the indexes on the server.
+    pub async fn list(&mut self) -> Result<Vec<String>, std::io::Error> {
+        let response = self.request(&[b"LIST"]).await?;
+        Ok(parse_counted_values(response)?.map(str::to_string).collect())
+    }
+    // Sends the request of the parts, and returns the response after `OK `
+    async fn request(&mut self, parts: &[&[u8]]) -> Result<&str, std::io::Error> {
+        if parts.iter().any(|part| part.contains(&b'\n')) {
+            return Err(invalid_data("Request must not contain the line feed"));
+        }
+        for part in parts {
+            self.stream.write_all(part).await?;
+        }
+        self.stream.write_all(b"\n").await?;
+        self.stream.flush().await?;
+
+        self.response.clear();
+        if self.stream.read_line(&mut self.response).await? == 0 {
+            return Err(std::io::ErrorKind::UnexpectedEof.into());
+        }
+        let response = self.response.trim_end_matches(['\n', '\r']);
+        if let Some(message) = response.strip_prefix("ERR ") {
+            Err(std::io::Error::other(message.to_string()))
+        } else {
+            response.strip_prefix("OK ").ok_or_else(|| invalid_data("Invalid response"))
+        }
+    }
+}
+
+fn invalid_data(message: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message)
+}
+fn parse_number(value: &str) -> Result<u64, std::io::Error> {
+    value.parse().map_err(|_| invalid_data("Invalid number in response"))
+}
+// Values after the count of them
+fn parse_counted_values(response: &str) -> Result<impl Iterator<Item = &str>, std::io::Error> {
+    let mut values = response.split(' ');
+    let count = parse_number(values.next().unwrap_or_default())?;
+    let values: Vec<&str> = values.collect();
+    if values.len() as u64 != count {
+        return Err(invalid_data("Count of values in response is mismatched"));
+    }
+    Ok(values.into_iter())
+}
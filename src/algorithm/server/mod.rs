@@ -0,0 +1,232 @@
+/*!
+Local query server and its client (`server` feature).
+
+[IndexServer] loads the indexes once and answers the queries over a localhost TCP port
+or a Unix socket, so the short-lived scripts do not pay the cost of loading on every run.
+It serves the files of [AnyLtFmIndex] and [AnyMultiSequenceIndex], which the command-line tool and the bindings write.
+[IndexClient] sends the queries to the server.
+
+# Protocol
+Each request and response is one line ending with `\n`.
+The pattern is the rest of the line after the name of the index, so it can contain spaces but not `\n`.
+
+| Request | Response |
+|---|---|
+| `COUNT <index> <pattern>` | `OK <count>` |
+| `LOCATE <index> <pattern>` | `OK <count> <location>...` |
+| `LOCATE_IN_CONTIGS <index> <pattern>` | `OK <count> <contig>:<offset>...` |
+| `LIST` | `OK <count> <index>...` |
+
+`LOCATE` is for the index of a text, and `LOCATE_IN_CONTIGS` is for the index of the sequences,
+answering the zero-based offsets in the sequences.
+The failed request gets `ERR <message>`.
+The request longer than [IndexServer::set_max_request_len] (1 MiB by default) is skipped with the error.
+
+The queries run on the blocking threads of Tokio, so the long `LOCATE` does not stall the other connections.
+
+# Example
+```rust,no_run
+use lt_fm_index::LtFmIndex;
+use lt_fm_index::blocks::Block3;
+use lt_fm_index::server::{IndexServer, IndexClient};
+
+# async fn run() -> std::io::Result<()> {
+let index = LtFmIndex::<u32, Block3<u64>>::build(b"ACGTACGT".to_vec(), &[b"A", b"C", b"G", b"T"], 2, 3).unwrap();
+let mut server = IndexServer::new();
+server.add_index("text", index);
+// The file built by `lt-fm-index build genome.fa -o genome.ltfm`
+server.load_index("genome", std::io::BufReader::new(std::fs::File::open("genome.ltfm")?))?;
+let listener = tokio::net::TcpListener::bind("127.0.0.1:7878").await?;
+tokio::spawn(server.serve_tcp(listener));
+
+let mut client = IndexClient::connect_tcp("127.0.0.1:7878").await?;
+assert_eq!(client.count("text", b"ACGT").await?, 2);
+let locations = client.locate_in_contigs("genome", b"ACGT").await?;
+# Ok(())
+# }
+```
+*/
+use std::collections::HashMap;
+use std::io::Read;
+use std::sync::Arc;
+
+use tokio::io::{AsyncRead, AsyncWrite, AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufStream};
+
+use super::AnyLtFmIndex;
+use super::bio_formats::{AnyMultiSequenceIndex, Coordinate};
+
+mod client;
+pub use client::IndexClient;
+
+const DEFAULT_MAX_REQUEST_LEN: usize = 1 << 20;
+
+/// Server answering the queries on the loaded indexes.
+pub struct IndexServer {
+    indexes: HashMap<String, ServedIndex>,
+    max_request_len: usize,
+}
+
+// Index of a text, or of the sequences
+enum ServedIndex {
+    Text(AnyLtFmIndex),
+    Sequences(AnyMultiSequenceIndex),
+}
+
+impl Default for IndexServer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IndexServer {
+    /// Makes a server without the indexes.
+    pub fn new() -> Self {
+        Self {
+            indexes: HashMap::new(),
+            max_request_len: DEFAULT_MAX_REQUEST_LEN,
+        }
+    }
+    /// Adds the index queried by the name. The index of the same name is replaced.
+    ///
+    /// The index is [AnyLtFmIndex], or the [LtFmIndex](crate::LtFmIndex) converted to it.
+    /// The name must not contain the whitespace.
+    pub fn add_index<N: Into<String>, I: Into<AnyLtFmIndex>>(&mut self, name: N, index: I) {
+        self.indexes.insert(name.into(), ServedIndex::Text(index.into()));
+    }
+    /// Adds the index of the sequences queried by the name, as [Self::add_index].
+    ///
+    /// The locations are answered to `LOCATE_IN_CONTIGS`, with the names of the sequences.
+    pub fn add_multi_sequence_index<N: Into<String>>(&mut self, name: N, index: AnyMultiSequenceIndex) {
+        self.indexes.insert(name.into(), ServedIndex::Sequences(index));
+    }
+    /// Loads the index saved by [AnyLtFmIndex::save_to], or the index of the sequences saved by
+    /// [AnyMultiSequenceIndex::save_to] (the file of the command-line tool), and adds it as [Self::add_index].
+    pub fn load_index<N: Into<String>, R: Read>(&mut self, name: N, mut reader: R) -> Result<(), std::io::Error> {
+        let index = AnyLtFmIndex::load_from(&mut reader)?;
+        // The contigs follow the index in the file of the sequences
+        let mut first_byte = [0; 1];
+        let served_index = if reader.read(&mut first_byte)? == 0 {
+            ServedIndex::Text(index)
+        } else {
+            ServedIndex::Sequences(AnyMultiSequenceIndex::load_contigs_from(index, Read::chain(&first_byte[..], reader))?)
+        };
+        self.indexes.insert(name.into(), served_index);
+        Ok(())
+    }
+    /// Sets the max length of the request line in bytes, without the line feed.
+    pub fn set_max_request_len(&mut self, max_request_len: usize) {
+        self.max_request_len = max_request_len;
+    }
+
+    /// Serves the connections from the TCP listener until accepting fails.
+    pub async fn serve_tcp(self, listener: tokio::net::TcpListener) -> Result<(), std::io::Error> {
+        let indexes = Arc::new(self.indexes);
+        loop {
+            let (stream, _) = listener.accept().await?;
+            tokio::spawn(Self::serve_connection(stream, indexes.clone(), self.max_request_len));
+        }
+    }
+    /// Serves the connections from the Unix socket listener until accepting fails.
+    #[cfg(unix)]
+    pub async fn serve_unix(self, listener: tokio::net::UnixListener) -> Result<(), std::io::Error> {
+        let indexes = Arc::new(self.indexes);
+        loop {
+            let (stream, _) = listener.accept().await?;
+            tokio::spawn(Self::serve_connection(stream, indexes.clone(), self.max_request_len));
+        }
+    }
+    // Answers the requests until the connection is closed
+    async fn serve_connection<S>(
+        stream: S,
+        indexes: Arc<HashMap<String, ServedIndex>>,
+        max_request_len: usize,
+    ) -> Result<(), std::io::Error> where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let mut stream = BufStream::new(stream);
+        let mut request = Vec::new();
+        loop {
+            request.clear();
+            let limit = max_request_len as u64 + 1;
+            if (&mut stream).take(limit).read_until(b'\n', &mut request).await? == 0 {
+                return Ok(());
+            }
+            let result = if request.len() > max_request_len && request.last() != Some(&b'\n') {
+                skip_line(&mut stream).await?;
+                Err(format!("Request is longer than {} bytes", max_request_len))
+            } else {
+                while matches!(request.last(), Some(b'\n' | b'\r')) {
+                    request.pop();
+                }
+                let indexes = indexes.clone();
+                let (result, returned_request) = tokio::task::spawn_blocking(move || {
+                    (Self::respond(&indexes, &request), request)
+                }).await.map_err(std::io::Error::other)?;
+                request = returned_request;
+                result
+            };
+            let response = match result {
+                Ok(response) => format!("OK {}\n", response),
+                Err(message) => format!("ERR {}\n", message),
+            };
+            stream.write_all(response.as_bytes()).await?;
+            stream.flush().await?;
+        }
+    }
+    fn respond(indexes: &HashMap<String, ServedIndex>, request: &[u8]) -> Result<String, String> {
+        let mut splitted = request.splitn(3, |&chr| chr == b' ');
+        let command = splitted.next().unwrap_or_default();
+        if command == b"LIST" {
+            let mut names: Vec<&str> = indexes.keys().map(String::as_str).collect();
+            names.sort_unstable();
+            return Ok(join_with_count(names.len(), names));
+        }
+        let name = String::from_utf8_lossy(splitted.next().ok_or("Missing index name")?);
+        let index = indexes.get(name.as_ref()).ok_or_else(|| format!("Unknown index: {}", name))?;
+        let pattern = splitted.next().ok_or("Missing pattern")?;
+        match (command, index) {
+            (b"COUNT", ServedIndex::Text(index)) => Ok(index.count(pattern).to_string()),
+            (b"COUNT", ServedIndex::Sequences(index)) => Ok(index.count(pattern).to_string()),
+            (b"LOCATE", ServedIndex::Text(index)) => {
+                let mut locations = index.locate(pattern);
+                locations.sort_unstable();
+                Ok(join_with_count(locations.len(), locations))
+            },
+            (b"LOCATE_IN_CONTIGS", ServedIndex::Sequences(index)) => {
+                let mut locations = index.locate_in_contigs(pattern, Coordinate::ZeroBased);
+                locations.sort_unstable();
+                Ok(join_with_count(
+                    locations.len(),
+                    locations.into_iter().map(|(contig, offset)| format!("{}:{}", contig, offset)),
+                ))
+            },
+            (b"LOCATE", ServedIndex::Sequences(_)) => Err(format!("Index of sequences is located by LOCATE_IN_CONTIGS: {}", name)),
+            (b"LOCATE_IN_CONTIGS", ServedIndex::Text(_)) => Err(format!("Index has no contigs: {}", name)),
+            _ => Err(format!("Unknown command: {}", String::from_utf8_lossy(command))),
+        }
+    }
+}
+
+// Consumes the rest of the line
+async fn skip_line<S: AsyncBufRead + Unpin>(stream: &mut S) -> Result<(), std::io::Error> {
+    loop {
+        let buffer = stream.fill_buf().await?;
+        let (consumed_len, is_line_end) = match buffer.iter().position(|&chr| chr == b'\n') {
+            Some(position) => (position + 1, true),
+            None => (buffer.len(), buffer.is_empty()),
+        };
+        stream.consume(consumed_len);
+        if is_line_end {
+            return Ok(());
+        }
+    }
+}
+
+fn join_with_count<T: ToString>(count: usize, values: impl IntoIterator<Item = T>) -> String {
+    let mut joined = count.to_string();
+    values.into_iter().for_each(|value| {
+        joined.push(' ');
+        joined.push_str(&value.to_string());
+    });
+    joined
+}
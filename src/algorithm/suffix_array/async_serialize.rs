@@ -1,7 +1,7 @@
 use std::pin::Pin;
 
 use crate::core::{Position, AsyncSerialize};
-use super::SuffixArray;
+use super::{SuffixArray, BitVector};
 use capwriter::{AsyncSave, AsyncLoad};
 
 impl<P: Position> AsyncSerialize for SuffixArray<P> {
@@ -13,6 +13,14 @@ impl<P: Position> AsyncSerialize for SuffixArray<P> {
 
             self.array.save_as_ne(writer.as_mut()).await?;
 
+            match &self.sampled_rows {
+                Some(sampled_rows) => {
+                    1_u32.save_as_ne(writer.as_mut()).await?;
+                    sampled_rows.async_save_to(writer.as_mut()).await?;
+                },
+                None => 0_u32.save_as_ne(writer.as_mut()).await?,
+            }
+
             Ok(())
         }
     }
@@ -25,10 +33,33 @@ impl<P: Position> AsyncSerialize for SuffixArray<P> {
 
             let array = Vec::<P>::load_as_ne(reader.as_mut()).await?;
 
+            let sampled_rows = match u32::load_as_ne(reader.as_mut()).await? {
+                0 => None,
+                _ => Some(BitVector::async_load_from(reader.as_mut()).await?),
+            };
+
             Ok(Self{
                 sampling_ratio,
                 array,
+                sampled_rows,
             })
         }
     }
 }
+
+impl<P: Position> SuffixArray<P> {
+    // Suffix array saved without the format version, which is sampled by the rows
+    pub(crate) async fn async_load_unversioned_from<R>(mut reader: Pin<&mut R>) -> Result<Self, std::io::Error> where
+        R: tokio::io::AsyncRead + Send,
+    {
+        let sampling_ratio = P::from_u64(u64::load_as_ne(reader.as_mut()).await?);
+
+        let array = Vec::<P>::load_as_ne(reader.as_mut()).await?;
+
+        Ok(Self{
+            sampling_ratio,
+            array,
+            sampled_rows: None,
+        })
+    }
+}
@@ -7,23 +7,27 @@
 //! The implementation is based on the lecture notes
 //! "Algorithmen auf Sequenzen", Kopczynski, Marschall, Martin and Rahmann, 2008 - 2015.
 
-use std::iter::repeat;
 
 use super::suffix_array::RawSuffixArraySlice;
+use crate::core::errors::BuildError;
+use crate::algorithm::{BuildMonitor, BuildPhase};
 
+#[allow(clippy::upper_case_acronyms)]
 pub type BWT = Vec<u8>;
 pub type Less = Vec<usize>;
 
-pub fn bwt(text: &[u8], pos: RawSuffixArraySlice) -> BWT {
+// The progress of the transform is reported to the monitor
+pub fn bwt(text: &[u8], pos: RawSuffixArraySlice, monitor: &mut BuildMonitor) -> Result<BWT, BuildError> {
     assert_eq!(text.len(), pos.len());
     let n = text.len();
-    let mut bwt: BWT = repeat(0).take(n).collect();
+    let mut bwt: BWT = vec![0; n];
     for r in 0..n {
+        monitor.progress(BuildPhase::Bwt, r, n)?;
         let p = pos[r];
         bwt[r] = if p > 0 { text[p - 1] } else { text[n - 1] };
     }
 
-    bwt
+    Ok(bwt)
 }
 
 
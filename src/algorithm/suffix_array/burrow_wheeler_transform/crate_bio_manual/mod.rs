@@ -1,15 +1,25 @@
-use crate::core::Position;
+use crate::core::{Position, errors::BuildError};
+use crate::algorithm::{BuildMonitor, BuildPhase};
 use suffix_array::suffix_array as get_suffix_array;
 use bwt::bwt as get_bwt;
 
 const SENTINEL_SYMBOL: u8 = 0;
 
+// The phase of the BWT is finished by the caller
 #[inline]
-pub fn get_compressed_suffix_array_and_pidx_while_bwt_with_crate_bio<P: Position>(text: &mut Vec<u8>, sampling_ratio: P) -> (Vec<P>, P) {
-    let mut input_string = text.to_vec();
-    input_string.push(SENTINEL_SYMBOL);
-    let mut suffix_array = get_suffix_array(&input_string);
-    let mut bwt = get_bwt(&input_string, &suffix_array);
+pub fn get_compressed_suffix_array_and_pidx_while_bwt_with_crate_bio<P: Position>(
+    text: &mut Vec<u8>,
+    sampling_ratio: P,
+    monitor: &mut BuildMonitor,
+) -> Result<(Vec<P>, P), BuildError> {
+    monitor.start_phase(BuildPhase::SuffixSorting)?;
+    // Use the text as the input string without copying
+    text.push(SENTINEL_SYMBOL);
+    let mut suffix_array = get_suffix_array(text, monitor)?;
+    monitor.finish_phase(BuildPhase::SuffixSorting)?;
+
+    monitor.start_phase(BuildPhase::Bwt)?;
+    let mut bwt = get_bwt(text, &suffix_array, monitor)?;
     
     let pidx = get_pidx_from_bwt(&bwt);
 
@@ -19,7 +29,7 @@ pub fn get_compressed_suffix_array_and_pidx_while_bwt_with_crate_bio<P: Position
     // Change original text to bwt
     *text = bwt;
     let compressed_suffix_array = suffix_array.into_iter().step_by(sampling_ratio.as_usize()).map(|x| P::from_usize(x)).collect();
-    (compressed_suffix_array, P::from_usize(pidx))
+    Ok((compressed_suffix_array, P::from_usize(pidx)))
 }
 
 fn get_pidx_from_bwt(bwt: &[u8]) -> usize {
@@ -16,6 +16,9 @@ use vec_map::VecMap;
 
 use fxhash::FxHasher;
 
+use crate::core::errors::BuildError;
+use crate::algorithm::{BuildMonitor, BuildPhase};
+
 use super::alphabets::{Alphabet, RankTransform};
 use super::bwt::{Less, Occ, BWT};
 
@@ -36,26 +39,27 @@ pub struct SampledSuffixArray<DBWT: Borrow<BWT>, DLess: Borrow<Less>, DOcc: Borr
     sentinel: u8,
 }
 
-pub fn suffix_array(text: &[u8]) -> RawSuffixArray {
+// The progress of the sorting is reported to the monitor
+pub fn suffix_array(text: &[u8], monitor: &mut BuildMonitor) -> Result<RawSuffixArray, BuildError> {
     let n = text.len();
     let alphabet = Alphabet::new(text);
     let sentinel_count = sentinel_count(text);
     let mut sais = Sais::new(n);
 
     match alphabet.len() + sentinel_count {
-        a if a <= std::u8::MAX as usize => {
-            sais.construct(&transform_text::<u8>(text, &alphabet, sentinel_count))
+        a if a <= u8::MAX as usize => {
+            sais.construct(&transform_text::<u8>(text, &alphabet, sentinel_count), monitor)?
         }
-        a if a <= std::u16::MAX as usize => {
-            sais.construct(&transform_text::<u16>(text, &alphabet, sentinel_count))
+        a if a <= u16::MAX as usize => {
+            sais.construct(&transform_text::<u16>(text, &alphabet, sentinel_count), monitor)?
         }
-        a if a <= std::u32::MAX as usize => {
-            sais.construct(&transform_text::<u32>(text, &alphabet, sentinel_count))
+        a if a <= u32::MAX as usize => {
+            sais.construct(&transform_text::<u32>(text, &alphabet, sentinel_count), monitor)?
         }
-        _ => sais.construct(&transform_text::<u64>(text, &alphabet, sentinel_count)),
+        _ => sais.construct(&transform_text::<u64>(text, &alphabet, sentinel_count), monitor)?,
     }
 
-    sais.pos
+    Ok(sais.pos)
 }
 
 /// Return last character of the text (expected to be the sentinel).
@@ -109,6 +113,11 @@ struct Sais {
     bucket_sizes: VecMap<usize>,
     bucket_start: Vec<usize>,
     bucket_end: Vec<usize>,
+    // Steps of the insertions into the buckets done, and their upper bound.
+    // Each level inserts twice in both directions, and the reduced text is at most half of the text,
+    // so the steps are at most 8 times the length of the text.
+    steps_done: usize,
+    max_steps: usize,
 }
 
 impl Sais {
@@ -121,6 +130,8 @@ impl Sais {
             bucket_sizes: VecMap::new(),
             bucket_start: Vec::with_capacity(n),
             bucket_end: Vec::with_capacity(n),
+            steps_done: 0,
+            max_steps: 8 * n,
         }
     }
 
@@ -188,7 +199,8 @@ impl Sais {
         text: &[T],
         pos_types: &PosTypes,
         lms_substring_count: usize,
-    ) {
+        monitor: &mut BuildMonitor,
+    ) -> Result<(), BuildError> {
         // if less than 2 LMS substrings are present, no further sorting is needed
         if lms_substring_count > 1 {
             // sort LMS suffixes by recursively building SA on reduced text
@@ -213,7 +225,7 @@ impl Sais {
                 // backup lms_pos
                 let lms_pos = self.lms_pos.clone();
                 // recurse SA construction for reduced text
-                self.construct(&reduced_text);
+                self.construct(&reduced_text, monitor)?;
                 // obtain sorted lms suffixes
                 self.lms_pos.clear();
                 for &p in &self.pos {
@@ -230,13 +242,18 @@ impl Sais {
                 }
             }
         }
+        Ok(())
     }
 
     /// Construct the suffix array.
-    fn construct<T: Integer + Unsigned + NumCast + Copy + Debug>(&mut self, text: &[T]) {
+    fn construct<T: Integer + Unsigned + NumCast + Copy + Debug>(
+        &mut self,
+        text: &[T],
+        monitor: &mut BuildMonitor,
+    ) -> Result<(), BuildError> {
         let pos_types = PosTypes::new(text);
-        self.calc_lms_pos(text, &pos_types);
-        self.calc_pos(text, &pos_types);
+        self.calc_lms_pos(text, &pos_types, monitor)?;
+        self.calc_pos(text, &pos_types, monitor)
     }
 
     /// Step 1 of the SAIS algorithm.
@@ -244,7 +261,8 @@ impl Sais {
         &mut self,
         text: &[T],
         pos_types: &PosTypes,
-    ) {
+        monitor: &mut BuildMonitor,
+    ) -> Result<(), BuildError> {
         let n = text.len();
 
         // collect LMS positions
@@ -259,18 +277,18 @@ impl Sais {
         }
 
         // sort LMS substrings by applying step 2 with unsorted LMS positions
-        self.calc_pos(text, pos_types);
+        self.calc_pos(text, pos_types, monitor)?;
 
         let lms_substring_count = self.lms_pos.len();
 
-        if lms_substring_count <= std::u8::MAX as usize {
-            self.sort_lms_suffixes::<T, u8>(text, pos_types, lms_substring_count);
-        } else if lms_substring_count <= std::u16::MAX as usize {
-            self.sort_lms_suffixes::<T, u16>(text, pos_types, lms_substring_count);
-        } else if lms_substring_count <= std::u32::MAX as usize {
-            self.sort_lms_suffixes::<T, u32>(text, pos_types, lms_substring_count);
+        if lms_substring_count <= u8::MAX as usize {
+            self.sort_lms_suffixes::<T, u8>(text, pos_types, lms_substring_count, monitor)
+        } else if lms_substring_count <= u16::MAX as usize {
+            self.sort_lms_suffixes::<T, u16>(text, pos_types, lms_substring_count, monitor)
+        } else if lms_substring_count <= u32::MAX as usize {
+            self.sort_lms_suffixes::<T, u32>(text, pos_types, lms_substring_count, monitor)
         } else {
-            self.sort_lms_suffixes::<T, u64>(text, pos_types, lms_substring_count);
+            self.sort_lms_suffixes::<T, u64>(text, pos_types, lms_substring_count, monitor)
         }
     }
 
@@ -279,7 +297,8 @@ impl Sais {
         &mut self,
         text: &[T],
         pos_types: &PosTypes,
-    ) {
+        monitor: &mut BuildMonitor,
+    ) -> Result<(), BuildError> {
         let n = text.len();
         self.pos.clear();
 
@@ -302,6 +321,7 @@ impl Sais {
 
         // insert L-positions into buckets
         for r in 0..n {
+            monitor.progress(BuildPhase::SuffixSorting, self.steps_done + r, self.max_steps)?;
             let p = self.pos[r];
             // ignore undefined positions and the zero since it has no predecessor
             if p == n || p == 0 {
@@ -315,8 +335,11 @@ impl Sais {
             }
         }
 
+        self.steps_done += n;
+
         // insert S-positions into buckets
         for r in (0..n).rev() {
+            monitor.progress(BuildPhase::SuffixSorting, self.steps_done + n - r, self.max_steps)?;
             let p = self.pos[r];
             if p == 0 {
                 continue;
@@ -329,6 +352,8 @@ impl Sais {
                 self.bucket_end[c] = self.bucket_end[c].wrapping_sub(1);
             }
         }
+        self.steps_done += n;
+        Ok(())
     }
 }
 
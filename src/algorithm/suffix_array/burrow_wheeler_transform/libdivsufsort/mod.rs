@@ -1,25 +1,39 @@
-use crate::core::Position;
+use crate::core::{Position, errors::BuildError};
+use crate::algorithm::{BuildMonitor, BuildPhase};
 // Always use 64 bit version because the 32 bit version return the i32, smaller than u32.
-use libdivsufsort_rs::{
-    divsufsort64 as divsufsort,
-    bw_transform64 as bw_transform,
-};
+use libdivsufsort_rs::divsufsort64 as divsufsort;
 
+// The phase of the BWT is finished by the caller.
+// The suffixes are sorted in one call of the library, so the sorting is only cancelled at its start and end.
 #[inline]
 pub fn get_compressed_suffix_array_and_pidx_while_bwt_with_libdivsufsort<P: Position>(
     text: &mut Vec<u8>,
     sampling_ratio: P,
-) -> (Vec<P>, P) {
+    monitor: &mut BuildMonitor,
+) -> Result<(Vec<P>, P), BuildError> {
+    monitor.start_phase(BuildPhase::SuffixSorting)?;
     let suffix_array = divsufsort(text).unwrap();
-    let pidx = {
-        let mut sa = suffix_array.clone();
-        let pidx = bw_transform(text, &mut sa).unwrap();
-        pidx
-    };
+    monitor.finish_phase(BuildPhase::SuffixSorting)?;
+
+    monitor.start_phase(BuildPhase::Bwt)?;
+    // The BWT of the text with the sentinel at the end, without the sentinel
+    let text_len = text.len();
+    let mut bwt = Vec::with_capacity(text_len);
+    bwt.extend(text.last());
+    let mut pidx = 0;
+    for (row, &location) in suffix_array.iter().enumerate() {
+        monitor.progress(BuildPhase::Bwt, row, text_len)?;
+        if location == 0 {
+            pidx = row as i64 + 1;
+        } else {
+            bwt.push(text[location as usize - 1]);
+        }
+    }
+    *text = bwt;
 
     let compressed_suffix_array = suffix_array.into_iter()
         .step_by(sampling_ratio.as_usize())
         .map(|x| P::from_i64(x))
         .collect();
-    (compressed_suffix_array, P::from_i64(pidx))
+    Ok((compressed_suffix_array, P::from_i64(pidx)))
 }
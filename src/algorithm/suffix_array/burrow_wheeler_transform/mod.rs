@@ -15,6 +15,7 @@ pub use libdivsufsort::get_compressed_suffix_array_and_pidx_while_bwt_with_libdi
 #[cfg(feature = "fastbwt")]
 mod tests {
     use crate::Position;
+    use crate::algorithm::BuildMonitor;
     use crate::tests::random_data::*;
 
     use super::crate_bio_manual::get_compressed_suffix_array_and_pidx_while_bwt_with_crate_bio as bwt1;
@@ -46,14 +47,16 @@ mod tests {
             let (suffix_array_1, pidx_1) = bwt1::<P>(
                 &mut bwt_res_1,
                 P::from_u32(sampling_ratio),
-            );
+                &mut BuildMonitor::none(),
+            ).unwrap();
 
             // Result from libdivsufsort_rs
             let mut bwt_res_2 = text.to_vec();
             let (suffix_array_2, pidx_2) = bwt2::<P>(
                 &mut bwt_res_2,
                 P::from_u32(sampling_ratio),
-            );
+                &mut BuildMonitor::none(),
+            ).unwrap();
 
             assert_eq!(suffix_array_1, suffix_array_2);
             assert_eq!(bwt_res_1, bwt_res_2);
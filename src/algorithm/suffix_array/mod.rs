@@ -1,33 +1,114 @@
-use crate::core::Position;
+use crate::core::{Position, errors::BuildError};
+use super::{SuffixArraySampling, bit_vector::BitVector, BuildMonitor, BuildPhase};
 
 #[allow(dead_code)]
 mod burrow_wheeler_transform;
 use burrow_wheeler_transform::get_compressed_suffix_array_and_pidx_while_bwt;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "rkyv", derive(rkyv::Archive, rkyv::Serialize, rkyv::Deserialize))]
 pub struct SuffixArray<P: Position> {
     sampling_ratio: P,
     array: Vec<P>,
+    // Rows of the samples, if sampled by the text position
+    sampled_rows: Option<BitVector>,
 }
 
 impl<P: Position> SuffixArray<P> {
     // Build
-    pub fn new_while_bwt(text: &mut Vec<u8>, sasr: P) -> (Self, P) {
-        let (compressed_suffix_array, pidx) = get_compressed_suffix_array_and_pidx_while_bwt(text, sasr);
-
-        let suffix_array = Self {
-            sampling_ratio: sasr,
-            array: compressed_suffix_array,
+    pub fn new_while_bwt(
+        text: &mut Vec<u8>,
+        sasr: P,
+        sampling: SuffixArraySampling,
+        monitor: &mut BuildMonitor,
+    ) -> Result<(Self, P), BuildError> {
+        match sampling {
+            SuffixArraySampling::Row => {
+                let (compressed_suffix_array, pidx) = get_compressed_suffix_array_and_pidx_while_bwt(text, sasr, monitor)?;
+                monitor.finish_phase(BuildPhase::Bwt)?;
+                let suffix_array = Self {
+                    sampling_ratio: sasr,
+                    array: compressed_suffix_array,
+                    sampled_rows: None,
+                };
+                Ok((suffix_array, pidx))
+            },
+            SuffixArraySampling::TextPosition => {
+                let (raw_suffix_array, pidx) = get_compressed_suffix_array_and_pidx_while_bwt(text, P::ONE, monitor)?;
+                let suffix_array = Self::from_raw_suffix_array(raw_suffix_array, sasr, sampling);
+                monitor.finish_phase(BuildPhase::Bwt)?;
+                Ok((suffix_array, pidx))
+            },
+        }
+    }
+    pub fn from_raw_suffix_array(raw_suffix_array: Vec<P>, sasr: P, sampling: SuffixArraySampling) -> Self {
+        match sampling {
+            SuffixArraySampling::Row => Self {
+                sampling_ratio: sasr,
+                array: raw_suffix_array.into_iter().step_by(sasr.as_usize()).collect(),
+                sampled_rows: None,
+            },
+            SuffixArraySampling::TextPosition => {
+                let sampled_rows = BitVector::from_bits(
+                    raw_suffix_array.iter().map(|&location| location % sasr == P::ZERO)
+                );
+                let array = raw_suffix_array.into_iter().filter(|&location| location % sasr == P::ZERO).collect();
+                Self {
+                    sampling_ratio: sasr,
+                    array,
+                    sampled_rows: Some(sampled_rows),
+                }
+            },
+        }
+    }
+    // From the rows and locations of the samples, sorted by the row
+    pub fn from_samples(samples: Vec<(P, P)>, len: P, sasr: P, sampling: SuffixArraySampling) -> Self {
+        let sampled_rows = match sampling {
+            SuffixArraySampling::Row => None,
+            SuffixArraySampling::TextPosition => {
+                let mut sampled_rows = samples.iter().map(|&(row, _)| row).peekable();
+                Some(BitVector::from_bits(
+                    (0..len.as_u64()).map(|row| sampled_rows.next_if(|sampled_row| sampled_row.as_u64() == row).is_some())
+                ))
+            },
         };
-        (suffix_array, pidx)
+        Self {
+            sampling_ratio: sasr,
+            array: samples.into_iter().map(|(_, location)| location).collect(),
+            sampled_rows,
+        }
+    }
+    // Uncompressed suffix array
+    pub fn raw_suffix_array_while_bwt(text: &mut Vec<u8>) -> (Vec<P>, P) {
+        // Never fails without the cancellation token
+        get_compressed_suffix_array_and_pidx_while_bwt(text, P::ONE, &mut BuildMonitor::none()).unwrap()
     }
 
     // Locate
     pub fn sampling_ratio(&self) -> P {
         self.sampling_ratio
     }
+    pub fn sampling(&self) -> SuffixArraySampling {
+        match self.sampled_rows {
+            Some(_) => SuffixArraySampling::TextPosition,
+            None => SuffixArraySampling::Row,
+        }
+    }
+    #[inline]
+    pub fn is_sampled(&self, position: P) -> bool {
+        match &self.sampled_rows {
+            Some(sampled_rows) => sampled_rows.get(position.as_u64()),
+            None => position % self.sampling_ratio == P::ZERO,
+        }
+    }
+    // The position must be sampled
+    #[inline]
     pub fn get_location_of(&self, position: P) -> P {
-        self.array[(position / self.sampling_ratio).as_usize()]
+        match &self.sampled_rows {
+            Some(sampled_rows) => self.array[sampled_rows.rank1(position.as_u64()) as usize],
+            None => self.array[(position / self.sampling_ratio).as_usize()],
+        }
     }
 }
 
@@ -39,9 +120,9 @@ mod async_serialize;
 mod tests {
     fn compress_suffix_array(suffix_array: Vec<u32>, sampling_ratio: u32) -> Vec<u32> {
         if sampling_ratio == 1 {
-            suffix_array.into_iter().map(|x| x as u32).collect()
+            suffix_array
         } else {
-            suffix_array.into_iter().step_by(sampling_ratio as usize).map(|x| x ).collect()
+            suffix_array.into_iter().step_by(sampling_ratio as usize).collect()
         }
     }
 
@@ -1,5 +1,5 @@
 use crate::core::{Position, Serialize};
-use super::SuffixArray;
+use super::{SuffixArray, BitVector};
 use capwriter::{Save, Load};
 
 impl<P: Position> Serialize for SuffixArray<P> {
@@ -10,6 +10,14 @@ impl<P: Position> Serialize for SuffixArray<P> {
 
         self.array.save_as_ne(writer)?;
 
+        match &self.sampled_rows {
+            Some(sampled_rows) => {
+                1_u32.save_as_ne(writer)?;
+                sampled_rows.save_to(writer)?;
+            },
+            None => 0_u32.save_as_ne(writer)?,
+        }
+
         Ok(())
     }
     fn load_from<R>(reader: &mut R) -> Result<Self, std::io::Error> where
@@ -20,13 +28,37 @@ impl<P: Position> Serialize for SuffixArray<P> {
 
         let array = Vec::<P>::load_as_ne(reader)?;
 
+        let sampled_rows = match u32::load_as_ne(reader)? {
+            0 => None,
+            _ => Some(BitVector::load_from(reader)?),
+        };
+
         Ok(Self{
             sampling_ratio,
             array,
+            sampled_rows,
         })
     }
     fn encoded_len(&self) -> usize {
         8 // sampling_ratio
         + self.array.encoded_len() // array
+        + 4 + self.sampled_rows.as_ref().map_or(0, |sampled_rows| sampled_rows.encoded_len()) // sampled_rows
+    }
+}
+
+impl<P: Position> SuffixArray<P> {
+    // Suffix array saved without the format version, which is sampled by the rows
+    pub(crate) fn load_unversioned_from<R>(reader: &mut R) -> Result<Self, std::io::Error> where
+        R: std::io::Read,
+    {
+        let sampling_ratio = P::from_u64(u64::load_as_ne(reader)?);
+
+        let array = Vec::<P>::load_as_ne(reader)?;
+
+        Ok(Self{
+            sampling_ratio,
+            array,
+            sampled_rows: None,
+        })
     }
 }
@@ -0,0 +1,193 @@
+use crate::core::{
+    Position,
+    errors::BuildError,
+};
+use super::{LtFmIndex, BwmBackend, BuildOptions, EncodedPattern};
+
+/// Symbol of the text of [SymbolLtFmIndex]: `u8`, `u16`, or `u32`.
+pub trait Symbol: Copy + Ord + std::hash::Hash + std::fmt::Debug + Send + Sync + 'static {
+    fn as_u32(self) -> u32;
+    fn from_u32(value: u32) -> Self;
+}
+
+macro_rules! impl_symbol {
+    ($($symbol:ty),*) => {
+        $(impl Symbol for $symbol {
+            #[inline(always)]
+            fn as_u32(self) -> u32 {
+                self as u32
+            }
+            #[inline(always)]
+            fn from_u32(value: u32) -> Self {
+                value as Self
+            }
+        })*
+    };
+}
+impl_symbol!(u8, u16, u32);
+
+/// FM-index of the text of the wider symbols than `u8` (e.g. token IDs, or k-mers encoded to integers).
+///
+/// The groups of the symbols are remapped to the dense indices of the characters,
+/// and indexed by an [LtFmIndex] of the indices.
+/// So at most 254 groups of the symbols can be indexed, as the characters of [LtFmIndex],
+/// and the other symbols are the unindexed characters.
+///
+/// # Example
+/// ```rust
+/// use lt_fm_index::SymbolLtFmIndex;
+/// use lt_fm_index::blocks::Block3;
+///
+/// // Token IDs of the documents
+/// let text: Vec<u32> = vec![1000, 70000, 3, 1000, 70000, 3, 42];
+/// let symbols_by_index: &[&[u32]] = &[&[1000], &[70000], &[3]];
+/// let index = SymbolLtFmIndex::<u32, u32, Block3<u64>>::build(text, symbols_by_index, 2, 2).unwrap();
+/// assert_eq!(index.count(&[70000, 3]), 2);
+/// let mut locations = index.locate(&[1000, 70000]);
+/// locations.sort();
+/// assert_eq!(locations, vec![0, 3]);
+/// ```
+#[derive(Clone, PartialEq, Eq)]
+pub struct SymbolLtFmIndex<S: Symbol, P: Position, B: BwmBackend<P>> {
+    symbol_table: SymbolTable<S>,
+    // Index of the text of the chridx, with the identity table of the chridx
+    index: LtFmIndex<P, B>,
+}
+
+// Table mapping the symbols to their chridx
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SymbolTable<S: Symbol> {
+    groups: Vec<Vec<S>>,
+    // Sorted by the symbol
+    chridx_of_symbol: Vec<(S, u8)>,
+}
+
+impl<S: Symbol, P: Position, B: BwmBackend<P>> SymbolLtFmIndex<S, P, B> {
+    /// Builds a new FM-index from the text of the symbols.
+    ///
+    /// The arguments are the same as [LtFmIndex::build], with the groups of the symbols as `symbols_by_index`.
+    pub fn build<T>(
+        text: Vec<S>,
+        symbols_by_index: &[T],
+        suffix_array_sampling_ratio: P,
+        lookup_table_kmer_size: u32,
+    ) -> Result<Self, BuildError>
+    where
+        T: AsRef<[S]>,
+    {
+        Self::build_with_options(
+            text,
+            symbols_by_index,
+            suffix_array_sampling_ratio,
+            lookup_table_kmer_size,
+            &BuildOptions::default(),
+        )
+    }
+    /// Builds a new FM-index from the text of the symbols with the [BuildOptions].
+    pub fn build_with_options<T>(
+        text: Vec<S>,
+        symbols_by_index: &[T],
+        suffix_array_sampling_ratio: P,
+        lookup_table_kmer_size: u32,
+        options: &BuildOptions,
+    ) -> Result<Self, BuildError>
+    where
+        T: AsRef<[S]>,
+    {
+        let symbol_table = SymbolTable::new(symbols_by_index)?;
+        let chridx_text = symbol_table.encode(&text);
+        drop(text);
+        let index = LtFmIndex::build_with_options(
+            chridx_text,
+            &symbol_table.identity_characters(),
+            suffix_array_sampling_ratio,
+            lookup_table_kmer_size,
+            options,
+        )?;
+        Ok(Self { symbol_table, index })
+    }
+
+    /// Counts the number of occurrences of a pattern in the indexed text.
+    #[inline]
+    pub fn count(&self, pattern: &[S]) -> P {
+        self.index.count(&self.symbol_table.encode(pattern))
+    }
+    /// Locates all occurrences of a pattern in the indexed text.
+    ///
+    /// The locations are not in order.
+    #[inline]
+    pub fn locate(&self, pattern: &[S]) -> Vec<P> {
+        self.index.locate(&self.symbol_table.encode(pattern))
+    }
+    /// Encodes the pattern to search it in the indexes of the same groups of the symbols,
+    /// with [LtFmIndex::count_encoded_pattern] and [LtFmIndex::locate_encoded_pattern] of [Self::index].
+    pub fn encode_pattern(&self, pattern: &[S]) -> EncodedPattern {
+        self.index.encode_pattern(&self.symbol_table.encode(pattern))
+    }
+    /// Index of the character of the symbol.
+    pub fn chridx_of(&self, symbol: S) -> u8 {
+        self.symbol_table.chridx_of(symbol)
+    }
+    /// Groups of the symbols, in order of their indices.
+    pub fn symbol_groups(&self) -> &[Vec<S>] {
+        &self.symbol_table.groups
+    }
+    /// Length of the indexed text.
+    pub fn len_text(&self) -> P {
+        self.index.len_text()
+    }
+    /// The index of the text of the indices of the characters.
+    pub fn index(&self) -> &LtFmIndex<P, B> {
+        &self.index
+    }
+}
+
+impl<S: Symbol> SymbolTable<S> {
+    const MAX_GROUP_COUNT: usize = u8::MAX as usize - 1;
+
+    fn new<T: AsRef<[S]>>(symbols_by_index: &[T]) -> Result<Self, BuildError> {
+        if symbols_by_index.is_empty() {
+            return Err(BuildError::EmptyAlphabet);
+        }
+        if symbols_by_index.len() > Self::MAX_GROUP_COUNT {
+            return Err(BuildError::TooManyCharacterGroups(symbols_by_index.len()));
+        }
+        let groups: Vec<Vec<S>> = symbols_by_index.iter().map(|symbols| symbols.as_ref().to_vec()).collect();
+        Self::from_groups(groups)
+    }
+    fn from_groups(groups: Vec<Vec<S>>) -> Result<Self, BuildError> {
+        let mut chridx_of_symbol: Vec<(S, u8)> = groups.iter().enumerate().flat_map(|(chridx, symbols)| {
+            symbols.iter().map(move |&symbol| (symbol, chridx as u8))
+        }).collect();
+        chridx_of_symbol.sort_unstable();
+        if let Some(pair) = chridx_of_symbol.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+            return Err(BuildError::DuplicateSymbol(pair[0].0.as_u32()));
+        }
+        Ok(Self { groups, chridx_of_symbol })
+    }
+    #[inline]
+    fn chridx_of(&self, symbol: S) -> u8 {
+        match self.chridx_of_symbol.binary_search_by_key(&symbol, |&(symbol, _)| symbol) {
+            Ok(idx) => self.chridx_of_symbol[idx].1,
+            Err(_) => self.groups.len() as u8, // Unindexed
+        }
+    }
+    fn encode(&self, symbols: &[S]) -> Vec<u8> {
+        symbols.iter().map(|&symbol| self.chridx_of(symbol)).collect()
+    }
+    // Characters of which the index is the same as the character
+    fn identity_characters(&self) -> Vec<[u8; 1]> {
+        (0..self.groups.len() as u8).map(|chridx| [chridx]).collect()
+    }
+}
+
+impl<S: Symbol, P: Position, B: BwmBackend<P>> std::fmt::Debug for SymbolLtFmIndex<S, P, B> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SymbolLtFmIndex")
+            .field("symbol_group_count", &self.symbol_table.groups.len())
+            .field("index", &self.index)
+            .finish()
+    }
+}
+
+mod serialize;
@@ -0,0 +1,55 @@
+use crate::core::Position;
+use super::{SymbolLtFmIndex, SymbolTable, Symbol, LtFmIndex, BwmBackend};
+use capwriter::{Save, Load};
+
+impl<S: Symbol, P: Position, B: BwmBackend<P>> SymbolLtFmIndex<S, P, B> {
+    pub fn save_to<W>(&self, mut writer: W) -> Result<(), std::io::Error> where
+        W: std::io::Write
+    {
+        // index
+        self.index.save_to(&mut writer)?;
+        // symbol table
+        let (group_lens, symbols) = self.symbol_table.encode_groups();
+        group_lens.save_as_ne(&mut writer)?;
+        symbols.save_as_ne(&mut writer)?;
+        Ok(())
+    }
+    pub fn load_from<R>(mut reader: R) -> Result<Self, std::io::Error> where
+        R: std::io::Read,
+        Self: Sized
+    {
+        let index = LtFmIndex::load_from(&mut reader)?;
+        let group_lens = Vec::<u64>::load_as_ne(&mut reader)?;
+        let symbols = Vec::<u32>::load_as_ne(&mut reader)?;
+        let symbol_table = SymbolTable::decode_groups(group_lens, symbols)?;
+        Ok(Self { symbol_table, index })
+    }
+    pub fn encoded_len(&self) -> usize {
+        let (group_lens, symbols) = self.symbol_table.encode_groups();
+        self.index.encoded_len() // index
+        + group_lens.encoded_len() + symbols.encoded_len() // symbol table
+    }
+}
+
+impl<S: Symbol> SymbolTable<S> {
+    // Lengths of the groups, and concatenated symbols
+    fn encode_groups(&self) -> (Vec<u64>, Vec<u32>) {
+        let group_lens = self.groups.iter().map(|group| group.len() as u64).collect();
+        let symbols = self.groups.iter().flatten().map(|&symbol| symbol.as_u32()).collect();
+        (group_lens, symbols)
+    }
+    fn decode_groups(group_lens: Vec<u64>, symbols: Vec<u32>) -> Result<Self, std::io::Error> {
+        let invalid_data = || std::io::Error::new(std::io::ErrorKind::InvalidData, "Invalid symbol table");
+        if group_lens.iter().sum::<u64>() != symbols.len() as u64 || symbols.iter().any(|&symbol| S::from_u32(symbol).as_u32() != symbol) {
+            return Err(invalid_data());
+        }
+        let mut start = 0;
+        let groups = group_lens.into_iter().map(|group_len| {
+            let end = start + group_len as usize;
+            let group = symbols[start..end].iter().map(|&symbol| S::from_u32(symbol)).collect();
+            start = end;
+            group
+        }).collect();
+        Self::from_groups(groups).map_err(|_| invalid_data())
+    }
+}
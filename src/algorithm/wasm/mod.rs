@@ -0,0 +1,78 @@
+/*!
+WebAssembly bindings (`wasm` feature).
+
+The class `LtFmIndex` of JavaScript loads the index serialized by [AnyLtFmIndex::save_to] from a `Uint8Array`,
+e.g. the file of the command-line tool or the other bindings.
+The position and backend are read from the header of the file.
+
+The module is built as `cdylib` and bound by `wasm-bindgen`:
+```sh
+cargo rustc --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/lt_fm_index.wasm
+```
+```js
+import init, { LtFmIndex } from "./pkg/lt_fm_index.js";
+
+await init();
+const bytes = new Uint8Array(await (await fetch("reference.idx")).arrayBuffer());
+const index = LtFmIndex.fromBytes(bytes);
+index.count(new TextEncoder().encode("ACGT"));
+index.locateString("ACGT"); // Float64Array of the locations
+```
+*/
+use wasm_bindgen::prelude::*;
+
+use super::AnyLtFmIndex;
+
+/// `LtFmIndex` class of JavaScript.
+///
+/// The counts and locations are JavaScript numbers, exact up to 2^53.
+#[wasm_bindgen(js_name = LtFmIndex)]
+pub struct WasmLtFmIndex {
+    index: AnyLtFmIndex,
+}
+
+#[wasm_bindgen(js_class = LtFmIndex)]
+impl WasmLtFmIndex {
+    /// Loads the index serialized by [AnyLtFmIndex::save_to].
+    #[wasm_bindgen(js_name = fromBytes)]
+    pub fn from_bytes(bytes: &[u8]) -> Result<WasmLtFmIndex, JsError> {
+        let index = AnyLtFmIndex::load_from(bytes).map_err(|err| JsError::new(&err.to_string()))?;
+        Ok(Self { index })
+    }
+    /// Position type of the index (`"u32"` or `"u64"`).
+    #[wasm_bindgen(getter)]
+    pub fn position(&self) -> String {
+        self.index.position_type().name().to_string()
+    }
+    /// Backend of the index (`"block2"`-`"block6"` or `"wavelet"`).
+    #[wasm_bindgen(getter)]
+    pub fn backend(&self) -> String {
+        self.index.backend_type().name().to_string()
+    }
+    /// Length of the indexed text.
+    #[wasm_bindgen(getter, js_name = textLength)]
+    pub fn text_length(&self) -> f64 {
+        self.index.len_text() as f64
+    }
+    /// Counts the occurrences of the pattern.
+    pub fn count(&self, pattern: &[u8]) -> f64 {
+        self.index.count(pattern) as f64
+    }
+    /// Locates the occurrences of the pattern, in ascending order.
+    pub fn locate(&self, pattern: &[u8]) -> Vec<f64> {
+        let mut locations = self.index.locate(pattern);
+        locations.sort_unstable();
+        locations.into_iter().map(|location| location as f64).collect()
+    }
+    /// [count](Self::count) of the UTF-8 bytes of the string.
+    #[wasm_bindgen(js_name = countString)]
+    pub fn count_string(&self, pattern: &str) -> f64 {
+        self.count(pattern.as_bytes())
+    }
+    /// [locate](Self::locate) of the UTF-8 bytes of the string.
+    #[wasm_bindgen(js_name = locateString)]
+    pub fn locate_string(&self, pattern: &str) -> Vec<f64> {
+        self.locate(pattern.as_bytes())
+    }
+}
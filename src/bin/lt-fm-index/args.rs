@@ -0,0 +1,71 @@
+use std::str::FromStr;
+
+/// Parsed command-line arguments of a subcommand.
+pub struct Args {
+    positionals: Vec<String>,
+    options: Vec<(String, String)>,
+    flags: Vec<String>,
+}
+
+impl Args {
+    /// Parses the arguments. The `options` take a value, and the `flags` do not.
+    pub fn parse<I: Iterator<Item = String>>(
+        tokens: I,
+        options: &[&str],
+        flags: &[&str],
+    ) -> Result<Self, String> {
+        let mut args = Self {
+            positionals: Vec::new(),
+            options: Vec::new(),
+            flags: Vec::new(),
+        };
+        let mut tokens = tokens.peekable();
+        while let Some(token) = tokens.next() {
+            let name = match token.as_str() {
+                "-o" => "output".to_string(),
+                _ => match token.strip_prefix("--") {
+                    Some(name) => name.to_string(),
+                    None => {
+                        args.positionals.push(token);
+                        continue;
+                    },
+                },
+            };
+            let (name, inline_value) = match name.split_once('=') {
+                Some((name, value)) => (name.to_string(), Some(value.to_string())),
+                None => (name, None),
+            };
+            if flags.contains(&name.as_str()) && inline_value.is_none() {
+                args.flags.push(name);
+            } else if options.contains(&name.as_str()) {
+                let value = match inline_value {
+                    Some(value) => value,
+                    None => tokens.next().ok_or_else(|| format!("Option --{} needs a value", name))?,
+                };
+                args.options.push((name, value));
+            } else {
+                return Err(format!("Unknown option --{}", name));
+            }
+        }
+        Ok(args)
+    }
+
+    pub fn positionals(&self) -> &[String] {
+        &self.positionals
+    }
+    pub fn positional(&self, idx: usize, name: &str) -> Result<&str, String> {
+        self.positionals.get(idx).map(String::as_str).ok_or_else(|| format!("Missing {}", name))
+    }
+    /// The last value of the option.
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.options.iter().rev().find(|(option, _)| option == name).map(|(_, value)| value.as_str())
+    }
+    pub fn parsed_value<T: FromStr>(&self, name: &str) -> Result<Option<T>, String> {
+        self.value(name).map(|value| {
+            value.parse().map_err(|_| format!("Invalid value of --{}: {}", name, value))
+        }).transpose()
+    }
+    pub fn flag(&self, name: &str) -> bool {
+        self.flags.iter().any(|flag| flag == name)
+    }
+}
@@ -0,0 +1,333 @@
+/*!
+Command-line tool to build and query the indexes (`cli` feature).
+
+Run `lt-fm-index help` for the usage.
+*/
+use std::collections::{BTreeSet, HashMap};
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use lt_fm_index::{
+    Alphabet, BuildOptions, LookupTable, UnindexedCharacters, SuffixArraySampling,
+    AnyLtFmIndex, PositionType, BackendType,
+};
+use lt_fm_index::bio_formats::{AnyMultiSequenceIndex, Coordinate, FastaReader, FastaRecord, FastqReader};
+
+mod args;
+use args::Args;
+mod output;
+use output::{Format, ResultWriter};
+
+const USAGE: &str = "\
+Usage: lt-fm-index <command> [arguments]
+
+Commands:
+  build <input> -o <index>   Builds the index of the text or FASTA file
+      --input-format <auto|fasta|text>   Format of the input (default: auto)
+      --alphabet <name>                  Preset of the characters: dna, dna-n, rna, iupac,
+                                         protein, or reduced-protein (default: dna)
+      --characters <chars>               Characters to index, instead of the preset
+      --position <u32|u64>               Type of the position (default: u32)
+      --backend <block2-block6|wavelet>  Backend of the BWT (default: smallest fitting block)
+      --kmer <k>                         K-mer size of the lookup table
+      --sampling <ratio>                 Sampling ratio of the suffix array (default: 2)
+      --lookup-table <dense|sparse>      Lookup table (default: dense)
+      --unindexed <same|separator>       Unindexed characters (default: same)
+      --sampling-by <row|text-position>  Suffix array sampling (default: row)
+  count <index> [patterns]   Counts the occurrences of the patterns
+  locate <index> [patterns]  Locates the occurrences of the patterns
+      --patterns <file>                  Patterns, one per line
+      --fastq <file>                     Patterns from the reads of the FASTQ file
+      --format <tsv|json>                Format of the results (default: tsv)
+      --one-based                        Offsets start at 1 (locate only)
+  info <index>               Prints the parameters and the sizes of the components
+  verify <index> <input>     Checks the index against a scan of the input it was built from
+      --samples <count>                  Count of sampled patterns (default: 1000)
+      --length <length>                  Length of the sampled patterns (default: 20)
+  help                       Prints this message
+";
+
+type CliResult = Result<(), Box<dyn std::error::Error>>;
+
+// Input of the build
+enum Input<R: BufRead> {
+    Fasta(R),
+    Text(FastaRecord),
+}
+
+fn main() {
+    let mut tokens = std::env::args().skip(1);
+    let command = tokens.next().unwrap_or_default();
+    let result = match command.as_str() {
+        "build" => build(tokens),
+        "count" => query(tokens, false),
+        "locate" => query(tokens, true),
+        "info" => info(tokens),
+        "verify" => verify(tokens),
+        "help" | "--help" | "-h" => {
+            print!("{}", USAGE);
+            Ok(())
+        },
+        _ => Err(format!("Unknown command: {:?}\n\n{}", command, USAGE).into()),
+    };
+    if let Err(error) = result {
+        eprintln!("Error: {}", error);
+        std::process::exit(1);
+    }
+}
+
+fn build(tokens: impl Iterator<Item = String>) -> CliResult {
+    let args = Args::parse(tokens, &[
+        "output", "input-format", "alphabet", "characters", "position", "backend",
+        "kmer", "sampling", "lookup-table", "unindexed", "sampling-by",
+    ], &[])?;
+    let input_path = args.positional(0, "input file")?;
+    let output_path = args.value("output").ok_or("Missing output file (-o)")?;
+
+    let characters_by_index = match args.value("characters") {
+        Some(characters) => characters.bytes().map(|chr| vec![chr]).collect(),
+        None => alphabet_preset(args.value("alphabet").unwrap_or("dna"))?,
+    };
+    let position = args.value("position").unwrap_or("u32");
+    let position = PositionType::from_name(position)
+        .ok_or_else(|| format!("Unknown position type: {} (u32 or u64)", position))?;
+    let backend = match args.value("backend") {
+        Some(backend) => BackendType::from_name(backend)
+            .ok_or_else(|| format!("Unknown backend: {} (block2-block6 or wavelet)", backend))?,
+        None => BackendType::fitting(characters_by_index.len()),
+    };
+    let kmer_size = args.parsed_value("kmer")?.unwrap_or_else(|| AnyLtFmIndex::default_kmer_size(characters_by_index.len()));
+    let sampling_ratio = args.parsed_value("sampling")?.unwrap_or(2);
+    let mut options = BuildOptions::new();
+    if let Some(lookup_table) = args.value("lookup-table") {
+        options = options.lookup_table(match lookup_table {
+            "dense" => LookupTable::Dense,
+            "sparse" => LookupTable::Sparse,
+            _ => return Err(format!("Unknown lookup table: {}", lookup_table).into()),
+        });
+    }
+    if let Some(unindexed) = args.value("unindexed") {
+        options = options.unindexed_characters(match unindexed {
+            "same" => UnindexedCharacters::Same,
+            "separator" => UnindexedCharacters::Separator,
+            _ => return Err(format!("Unknown unindexed characters: {}", unindexed).into()),
+        });
+    }
+    if let Some(sampling) = args.value("sampling-by") {
+        options = options.suffix_array_sampling(match sampling {
+            "row" => SuffixArraySampling::Row,
+            "text-position" => SuffixArraySampling::TextPosition,
+            _ => return Err(format!("Unknown suffix array sampling: {}", sampling).into()),
+        });
+    }
+
+    let index = match open_input(input_path, args.value("input-format").unwrap_or("auto"))? {
+        Input::Fasta(reader) => AnyMultiSequenceIndex::build_from_fasta(
+            reader, &characters_by_index, position, backend, sampling_ratio, kmer_size, &options,
+        )?,
+        Input::Text(record) => AnyMultiSequenceIndex::build_from_records(
+            [record], &characters_by_index, position, backend, sampling_ratio, kmer_size, &options,
+        )?,
+    };
+    let mut writer = BufWriter::new(File::create(output_path)?);
+    index.save_to(&mut writer)?;
+    writer.flush()?;
+    eprintln!(
+        "Indexed {} sequence(s) into {} ({} bytes)",
+        index.contigs().len(),
+        output_path,
+        index.encoded_len(),
+    );
+    Ok(())
+}
+
+fn query(tokens: impl Iterator<Item = String>, is_locate: bool) -> CliResult {
+    let args = Args::parse(tokens, &["patterns", "fastq", "format"], &["one-based"])?;
+    let index = load_index(args.positional(0, "index file")?)?;
+    let format = Format::parse(args.value("format").unwrap_or("tsv"))?;
+    let coordinate = if args.flag("one-based") { Coordinate::OneBased } else { Coordinate::ZeroBased };
+
+    // Queries as (name, pattern)
+    let mut queries: Vec<(String, Vec<u8>)> = args.positionals()[1..].iter().map(|pattern| {
+        (pattern.clone(), pattern.as_bytes().to_vec())
+    }).collect();
+    if let Some(path) = args.value("patterns") {
+        for line in BufReader::new(File::open(path)?).lines() {
+            let line = line?;
+            let pattern = line.trim_end_matches('\r');
+            if !pattern.is_empty() {
+                queries.push((pattern.to_string(), pattern.as_bytes().to_vec()));
+            }
+        }
+    }
+    if let Some(path) = args.value("fastq") {
+        for record in FastqReader::new(BufReader::new(File::open(path)?)) {
+            let record = record?;
+            queries.push((record.name, record.sequence));
+        }
+    }
+    if queries.is_empty() {
+        return Err("No patterns are given".into());
+    }
+
+    let mut writer = ResultWriter::new(BufWriter::new(std::io::stdout().lock()), format);
+    for (name, pattern) in queries.iter() {
+        if is_locate {
+            let mut locations = index.locate_in_contigs(pattern, coordinate);
+            locations.sort_unstable();
+            writer.write_locations(name, &locations)?;
+        } else {
+            writer.write_count(name, index.count(pattern))?;
+        }
+    }
+    writer.finish()?;
+    Ok(())
+}
+
+fn info(tokens: impl Iterator<Item = String>) -> CliResult {
+    let args = Args::parse(tokens, &[], &[])?;
+    let index = load_index(args.positional(0, "index file")?)?;
+    println!("position: {:?}", index.index().position_type());
+    println!("backend: {:?}", index.index().backend_type());
+    println!("{:#?}", index);
+    println!("contigs:");
+    index.contigs().iter().for_each(|contig| println!("  {}\t{}", contig.name, contig.len));
+    let sizes = index.index().component_sizes();
+    println!("component sizes (bytes):");
+    println!("  chr_idx_table: {}", sizes.chr_idx_table);
+    println!("  suffix_array: {}", sizes.suffix_array);
+    println!("  count_array: {}", sizes.count_array);
+    println!("  bwm: {}", sizes.bwm);
+    println!("  total: {}", index.encoded_len());
+    Ok(())
+}
+
+fn verify(tokens: impl Iterator<Item = String>) -> CliResult {
+    let args = Args::parse(tokens, &["samples", "length", "input-format"], &[])?;
+    let index = load_index(args.positional(0, "index file")?)?;
+    let input_path = args.positional(1, "input file")?;
+    let sample_count: u64 = args.parsed_value("samples")?.unwrap_or(1000);
+    let pattern_len: usize = args.parsed_value("length")?.unwrap_or(20);
+    if pattern_len == 0 {
+        return Err("The length of the sampled patterns must be positive".into());
+    }
+
+    let records: Vec<FastaRecord> = match open_input(input_path, args.value("input-format").unwrap_or("auto"))? {
+        Input::Fasta(reader) => FastaReader::new(reader).collect::<Result<_, _>>()?,
+        Input::Text(record) => vec![record],
+    };
+    let is_same_contigs = records.len() == index.contigs().len()
+        && records.iter().zip(index.contigs()).all(|(record, contig)| {
+            record.name == contig.name && record.sequence.len() as u64 == contig.len
+        });
+    if !is_same_contigs {
+        return Err("The names or lengths of the sequences are different from the input".into());
+    }
+
+    // Patterns at the evenly spaced positions, as (contig name, offset, pattern)
+    let total_len: u64 = index.contigs().iter().map(|contig| contig.len).sum();
+    let mut samples: Vec<(&str, u64, &[u8])> = Vec::new();
+    let mut contig_start = 0;
+    let mut remaining_records = records.iter().peekable();
+    for sample_idx in 0..sample_count.min(total_len) {
+        let location = sample_idx * total_len / sample_count.min(total_len);
+        while let Some(record) = remaining_records.next_if(|record| contig_start + record.sequence.len() as u64 <= location) {
+            contig_start += record.sequence.len() as u64;
+        }
+        let Some(record) = remaining_records.peek() else { break };
+        let offset = (location - contig_start) as usize;
+        let pattern = &record.sequence[offset..(offset + pattern_len).min(record.sequence.len())];
+        samples.push((record.name.as_str(), offset as u64, pattern));
+    }
+
+    let decoding_table = index.index().decoding_table();
+    let separator = match index.index().unindexed_characters() {
+        UnindexedCharacters::Same => None,
+        UnindexedCharacters::Separator => Some((index.index().index_count() - 1) as u8),
+    };
+    let patterns: Vec<&[u8]> = samples.iter().map(|(_, _, pattern)| *pattern).collect();
+    let expected_locations = scan_locations(&records, &patterns, decoding_table, separator);
+    let mut failed_count = 0;
+    for (name, offset, pattern) in samples.iter() {
+        let expected = encode_for_scan(pattern, decoding_table, separator)
+            .map(|encoded| expected_locations[&encoded].clone())
+            .unwrap_or_default();
+        let mut locations = index.locate_in_contigs(pattern, Coordinate::ZeroBased);
+        locations.sort_unstable();
+        if locations != expected || index.count(pattern) != expected.len() as u64 {
+            failed_count += 1;
+            eprintln!("Failed: {}\t{}\t{}", String::from_utf8_lossy(pattern), name, offset);
+        }
+    }
+    let checked_count = samples.len();
+    if failed_count == 0 {
+        println!("OK: {} patterns are verified", checked_count);
+        Ok(())
+    } else {
+        Err(format!("{} of {} patterns are failed", failed_count, checked_count).into())
+    }
+}
+
+// Locations of the patterns, found by comparing them with every position of the input
+fn scan_locations<'a>(
+    records: &'a [FastaRecord],
+    patterns: &[&[u8]],
+    decoding_table: &[u8; 256],
+    separator: Option<u8>,
+) -> HashMap<Vec<u8>, Vec<(&'a str, u64)>> {
+    let mut locations: HashMap<Vec<u8>, Vec<(&str, u64)>> = patterns.iter()
+        .filter_map(|pattern| encode_for_scan(pattern, decoding_table, separator))
+        .map(|encoded| (encoded, Vec::new()))
+        .collect();
+    let pattern_lens: BTreeSet<usize> = locations.keys().map(Vec::len).collect();
+    for record in records {
+        for &pattern_len in pattern_lens.iter() {
+            for (offset, window) in record.sequence.windows(pattern_len).enumerate() {
+                let Some(encoded) = encode_for_scan(window, decoding_table, separator) else { continue };
+                if let Some(pattern_locations) = locations.get_mut(&encoded) {
+                    pattern_locations.push((record.name.as_str(), offset as u64));
+                }
+            }
+        }
+    }
+    locations
+}
+
+// The indices of the characters, or `None` if the sequence has the separator
+fn encode_for_scan(sequence: &[u8], decoding_table: &[u8; 256], separator: Option<u8>) -> Option<Vec<u8>> {
+    sequence.iter().map(|&chr| {
+        let chridx = decoding_table[chr as usize];
+        (Some(chridx) != separator).then_some(chridx)
+    }).collect()
+}
+
+fn load_index(path: &str) -> Result<AnyMultiSequenceIndex, Box<dyn std::error::Error>> {
+    let file = File::open(path).map_err(|error| format!("{}: {}", path, error))?;
+    Ok(AnyMultiSequenceIndex::load_from(BufReader::new(file))?)
+}
+
+fn open_input(path: &str, input_format: &str) -> Result<Input<BufReader<File>>, Box<dyn std::error::Error>> {
+    let mut reader = BufReader::new(File::open(path).map_err(|error| format!("{}: {}", path, error))?);
+    let is_fasta = match input_format {
+        "fasta" => true,
+        "text" => false,
+        "auto" => reader.fill_buf()?.iter().find(|chr| !chr.is_ascii_whitespace()) == Some(&b'>'),
+        _ => return Err(format!("Unknown input format: {}", input_format).into()),
+    };
+    if is_fasta {
+        Ok(Input::Fasta(reader))
+    } else {
+        let mut sequence = Vec::new();
+        reader.read_to_end(&mut sequence)?;
+        let name = Path::new(path).file_stem().map(|stem| stem.to_string_lossy().into_owned()).unwrap_or_default();
+        Ok(Input::Text(FastaRecord { name, sequence }))
+    }
+}
+
+fn alphabet_preset(name: &str) -> Result<Vec<Vec<u8>>, String> {
+    match Alphabet::preset(name) {
+        Some(alphabet) => Ok(alphabet.groups().to_vec()),
+        None => Err(format!("Unknown alphabet: {} ({})", name, Alphabet::PRESET_NAMES.join(", "))),
+    }
+}
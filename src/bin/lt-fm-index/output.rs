@@ -0,0 +1,93 @@
+use std::io::Write;
+
+/// Format of the results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Tsv,
+    Json,
+}
+
+impl Format {
+    pub fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "tsv" => Ok(Self::Tsv),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("Unknown output format: {} (tsv or json)", value)),
+        }
+    }
+}
+
+/// Writes the results of the queries one by one.
+pub struct ResultWriter<W: Write> {
+    writer: W,
+    format: Format,
+    written_count: usize,
+}
+
+impl<W: Write> ResultWriter<W> {
+    pub fn new(writer: W, format: Format) -> Self {
+        Self {
+            writer,
+            format,
+            written_count: 0,
+        }
+    }
+    pub fn write_count(&mut self, query: &str, count: u64) -> std::io::Result<()> {
+        match self.format {
+            Format::Tsv => writeln!(self.writer, "{}\t{}", query, count),
+            Format::Json => {
+                self.write_separator()?;
+                write!(self.writer, "{{\"query\":{},\"count\":{}}}", json_string(query), count)
+            },
+        }
+    }
+    pub fn write_locations(&mut self, query: &str, locations: &[(&str, u64)]) -> std::io::Result<()> {
+        match self.format {
+            Format::Tsv => locations.iter().try_for_each(|(contig, offset)| {
+                writeln!(self.writer, "{}\t{}\t{}", query, contig, offset)
+            }),
+            Format::Json => {
+                self.write_separator()?;
+                write!(self.writer, "{{\"query\":{},\"locations\":[", json_string(query))?;
+                for (idx, (contig, offset)) in locations.iter().enumerate() {
+                    if idx != 0 {
+                        write!(self.writer, ",")?;
+                    }
+                    write!(self.writer, "{{\"contig\":{},\"offset\":{}}}", json_string(contig), offset)?;
+                }
+                write!(self.writer, "]}}")
+            },
+        }
+    }
+    pub fn finish(mut self) -> std::io::Result<()> {
+        if self.format == Format::Json {
+            if self.written_count == 0 {
+                write!(self.writer, "[")?;
+            }
+            writeln!(self.writer, "]")?;
+        }
+        self.writer.flush()
+    }
+    // Opens the array or separates the elements
+    fn write_separator(&mut self) -> std::io::Result<()> {
+        let separator = if self.written_count == 0 { "[" } else { "," };
+        self.written_count += 1;
+        write!(self.writer, "{}", separator)
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    value.chars().for_each(|chr| match chr {
+        '"' => escaped.push_str("\\\""),
+        '\\' => escaped.push_str("\\\\"),
+        '\n' => escaped.push_str("\\n"),
+        '\r' => escaped.push_str("\\r"),
+        '\t' => escaped.push_str("\\t"),
+        chr if (chr as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", chr as u32)),
+        chr => escaped.push(chr),
+    });
+    escaped.push('"');
+    escaped
+}
@@ -3,13 +3,57 @@ use thiserror::Error;
 /// Error type for building `LtFmIndex`.
 #[derive(Debug, Error)]
 pub enum BuildError {
+    /// The text is empty
+    #[error("Text must not be empty")]
+    EmptyText,
+    /// The length of the text can not be represented by the position
+    #[error("Text length {0} is too long for the position of {1} bits")]
+    TextTooLong(u64, u32),
+    /// No group of characters is given
+    #[error("At least one group of characters must be indexed")]
+    EmptyAlphabet,
+    /// More groups of characters than the index of `u8` can hold
+    #[error("At most 254 groups of characters can be indexed, but input is {0}")]
+    TooManyCharacterGroups(usize),
+    /// The character is given more than once
+    #[error("Character {0:?} is given more than once in the groups of characters")]
+    DuplicateCharacter(char),
+    /// The symbol is given more than once in the groups of the symbols
+    #[error("Symbol {0} is given more than once in the groups of symbols")]
+    DuplicateSymbol(u32),
     /// Index is over the maximum count of block
     #[error("Maximum index of block is {0}, but input is {1}.")]
     IndexCountOver(u32, u32),
     /// Invalid lookup table k-mer size
     #[error("Lookup table kmer size must be a positive integer")]
     LookupTableKmerSize,
+    /// The index of the lookup table overflows with the k-mer size
+    #[error("Lookup table kmer size {0} is too large for the count of characters")]
+    LookupTableOverflow(u32),
     /// Invalid suffix array sampling ratio
-    #[error("Suffix array sampling ratio must be a positive integer")]
+    #[error("Suffix array sampling ratio must be a positive integer that fits in the position")]
     SuffixArraySamplingRatio,
-}
\ No newline at end of file
+    /// Indexes to merge are built with the different characters or options
+    #[error("Indexes to merge must be built with the same characters and options")]
+    IncompatibleIndexes,
+    /// The build is cancelled by the `CancellationToken`
+    #[error("The build is cancelled")]
+    Cancelled,
+    /// Failed to read the text
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+/// Error type for the checked queries of `LtFmIndex`.
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum QueryError {
+    /// The chr index of the raw index is not less than the count of the characters
+    #[error("Chr index {0} is out of range for the count of characters {1}")]
+    ChrIdxOutOfRange(u8, u32),
+    /// The character of the pattern is not in the characters of the index
+    #[error("Character {0:?} is not in the characters of the index")]
+    UnindexedCharacter(char),
+    /// The encoded pattern is encoded with the other decoding table than the index
+    #[error("Pattern is encoded with the other decoding table than the index")]
+    IncompatibleAlphabet,
+}
@@ -75,7 +75,7 @@ impl Position for u32 {
     const BITS: u32 = Self::BITS;
     #[inline(always)]
     fn as_u32(self) -> u32 {
-        self as u32
+        self
     }
     #[inline(always)]
     fn from_u32(value: u32) -> Self {
@@ -120,7 +120,7 @@ impl Position for u64 {
     }
     #[inline(always)]
     fn as_u64(self) -> u64 {
-        self as u64
+        self
     }
     #[inline(always)]
     fn from_u64(value: u64) -> Self {
@@ -14,6 +14,28 @@
     - [Block] uses [blocks::Vector] as inner vectors.
         - Currently, [blocks::Vector] is implemented for: u32, u64, u128.
         - The shorter the vector, the faster the algorithm, but the larger the struct.
+    - [Block] is one kind of [BwmBackend]; the other backends are in [backends].
+        - e.g. [backends::WaveletTree] scales with the entropy of the text instead of the count of the characters, which suits large alphabets such as protein.
+
+The optional settings of the build are in [BuildOptions] (e.g. the [LookupTable::Sparse] lookup table for large k-mer sizes).
+
+Patterns with a set of characters at each position (e.g. IUPAC degenerate codes) are searched with [ClassPattern].
+
+[RlFmIndex] is the variant over the run-length encoded BWT (r-index), whose size is proportional to the count of runs in the BWT. It suits highly repetitive texts such as collections of genomes of the same species.
+
+[DynamicLtFmIndex] appends new texts without the full rebuild, by querying a set of static segments together.
+
+[AnyLtFmIndex] chooses the position and backend at runtime, and is saved with a header of them.
+
+With the `bio-formats` feature, `bio_formats` indexes FASTA files and locates the pattern by the names of the sequences.
+
+With the `server` feature, `server` answers the queries on the loaded indexes over a local socket.
+
+With the `capi` feature, `capi` is the C API with the generated header.
+
+With the `python` feature, `python` is the Python extension module.
+
+With the `wasm` feature, `wasm` is the WebAssembly bindings loading the serialized index.
 ### Example
 ```rust
 use lt_fm_index::LtFmIndex;
@@ -62,18 +84,55 @@ let loaded = LtFmIndex::load_from(&buffer[..]).unwrap();
 assert_eq!(lt_fm_index, loaded);
 ```
 */
+// The async functions return `impl Future + Send` to keep the futures `Send` in their signatures
+#![allow(clippy::manual_async_fn)]
 
 mod core;
 pub use crate::core::{
     Position,
-    errors::BuildError,
+    errors::{BuildError, QueryError},
 };
 mod algorithm;
 pub use algorithm::{
     LtFmIndex,
+    RlFmIndex,
+    DynamicLtFmIndex,
+    AnyLtFmIndex,
+    PositionType,
+    BackendType,
+    SymbolLtFmIndex,
+    Symbol,
     Block,
     blocks,
+    BwmBackend,
+    backends,
+    BuildOptions,
+    LookupTable,
+    UnindexedCharacters,
+    SuffixArraySampling,
+    BuildPhase,
+    BuildObserver,
+    CancellationToken,
+    Alphabet,
+    ClassPattern,
+    EncodedPattern,
+    ComponentSizes,
 };
+#[cfg(feature = "bio-formats")]
+#[cfg_attr(docsrs, doc(cfg(feature = "bio-formats")))]
+pub use algorithm::bio_formats;
+#[cfg(feature = "server")]
+#[cfg_attr(docsrs, doc(cfg(feature = "server")))]
+pub use algorithm::server;
+#[cfg(feature = "capi")]
+#[cfg_attr(docsrs, doc(cfg(feature = "capi")))]
+pub use algorithm::capi;
+#[cfg(feature = "python")]
+#[cfg_attr(docsrs, doc(cfg(feature = "python")))]
+pub use algorithm::python;
+#[cfg(feature = "wasm")]
+#[cfg_attr(docsrs, doc(cfg(feature = "wasm")))]
+pub use algorithm::wasm;
 
 #[cfg(test)]
 mod tests;
@@ -7,7 +7,7 @@ use crate::tests::random_data::{
 };
 
 fn assert_accurate_lt_fm_index_from_raw_index<P: Position, B: Block<P>>(
-    chr_list: &Vec<u8>,
+    chr_list: &[u8],
     text: Vec<u8>,
     patterns: &[Vec<u8>],
     ltks: u32,
@@ -17,7 +17,7 @@ fn assert_accurate_lt_fm_index_from_raw_index<P: Position, B: Block<P>>(
         println!("          pass");
         return;
     }
-    let characters_by_index = chr_list.chunks(1).map(|c| c).collect::<Vec<_>>();
+    let characters_by_index = chr_list.chunks(1).collect::<Vec<_>>();
     let lt_fm_index = LtFmIndex::<P, B>::build(
         text,
         &characters_by_index,
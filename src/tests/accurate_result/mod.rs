@@ -19,10 +19,10 @@ use crate::tests::{
 mod accurate_results_from_raw_index;
 
 fn assert_accurate_lt_fm_index<P: Position, B: Block<P>>(
-    chr_list: &Vec<u8>,
+    chr_list: &[u8],
     text: Vec<u8>,
-    patterns: &Vec<Vec<u8>>,
-    answers: &Vec<Vec<u64>>,
+    patterns: &[Vec<u8>],
+    answers: &[Vec<u64>],
     ltks: u32,
     sasr: u64,
 ) {
@@ -30,7 +30,7 @@ fn assert_accurate_lt_fm_index<P: Position, B: Block<P>>(
         println!("          pass");
         return;
     }
-    let characters_by_index = chr_list.chunks(1).map(|c| c).collect::<Vec<_>>();
+    let characters_by_index = chr_list.chunks(1).collect::<Vec<_>>();
     let lt_fm_index = LtFmIndex::<P, B>::build(
         text,
         &characters_by_index,
@@ -0,0 +1,89 @@
+use crate::{LtFmIndex, RlFmIndex, Position, BwmBackend, Alphabet, BuildError};
+use crate::blocks::{Block3, Block4, Block5};
+use crate::backends::WaveletTree;
+use crate::tests::random_data::{
+    gen_rand_chr_list,
+    gen_rand_text,
+    gen_rand_pattern,
+};
+
+fn presets() -> [Alphabet; 6] {
+    [
+        Alphabet::dna(),
+        Alphabet::dna_n(),
+        Alphabet::rna(),
+        Alphabet::iupac(),
+        Alphabet::protein(),
+        Alphabet::reduced_protein(),
+    ]
+}
+
+fn assert_alphabet_is_recovered<P: Position, B: BwmBackend<P>>(alphabet: &Alphabet) {
+    let chr_list: Vec<u8> = alphabet.groups().concat();
+    let text = gen_rand_text(&chr_list, 100, 300);
+    let index = LtFmIndex::<P, B>::build(text, alphabet, P::from_u64(2), 2).unwrap();
+    assert_eq!(&index.alphabet(), alphabet);
+
+    let mut buffer = Vec::new();
+    index.save_to(&mut buffer).unwrap();
+    let loaded = LtFmIndex::<P, B>::load_from(&buffer[..]).unwrap();
+    assert_eq!(&loaded.alphabet(), alphabet);
+}
+
+#[test]
+fn alphabet_is_recovered_from_index() {
+    for alphabet in presets().iter() {
+        assert_alphabet_is_recovered::<u32, Block5<u64>>(alphabet);
+        assert_alphabet_is_recovered::<u64, WaveletTree>(alphabet);
+    }
+    assert_alphabet_is_recovered::<u32, Block3<u32>>(&Alphabet::dna());
+    assert_alphabet_is_recovered::<u64, Block4<u64>>(&Alphabet::reduced_protein());
+
+    let rl_fm_index = RlFmIndex::<u32>::build(b"ACGUACGUNN".to_vec(), &Alphabet::rna(), 2).unwrap();
+    assert_eq!(rl_fm_index.alphabet(), Alphabet::rna());
+}
+
+#[test]
+fn alphabet_is_same_as_characters_by_index() {
+    let chr_list = gen_rand_chr_list(6);
+    let characters_by_index = vec![chr_list[3..5].to_vec(), vec![chr_list[0]], chr_list[1..3].to_vec()];
+    let alphabet = Alphabet::new(&characters_by_index).unwrap();
+    let text = gen_rand_text(&chr_list, 100, 300);
+    let index_of_alphabet = LtFmIndex::<u32, Block3<u64>>::build(text.clone(), &alphabet, 2, 3).unwrap();
+    let index_of_slices = LtFmIndex::<u32, Block3<u64>>::build(text.clone(), &characters_by_index, 2, 3).unwrap();
+    assert_eq!(index_of_alphabet, index_of_slices);
+    assert_eq!(index_of_slices.alphabet(), alphabet);
+    for _ in 0..20 {
+        let pattern = gen_rand_pattern(&text, 1, 8);
+        assert_eq!(index_of_alphabet.count(&pattern), index_of_slices.count(&pattern));
+    }
+}
+
+#[test]
+fn groups_and_representatives() {
+    let alphabet = Alphabet::new(&[b"ca".as_slice(), b"", b"G"]).unwrap();
+    assert_eq!(alphabet.groups(), &[b"ac".to_vec(), Vec::new(), b"G".to_vec()]);
+    assert_eq!(alphabet.chr_count(), 4);
+    assert_eq!(alphabet.representative(0), Some(b'a'));
+    assert_eq!(alphabet.representative(1), None);
+    assert_eq!(alphabet.representative(2), Some(b'G'));
+    assert_eq!(alphabet.representative(3), None);
+
+    let iupac = Alphabet::iupac();
+    assert_eq!(iupac.chr_count(), 16);
+    assert_eq!(iupac.groups()[3], b"TUtu");
+    assert_eq!(Alphabet::protein().chr_count(), 21);
+    assert_eq!(Alphabet::reduced_protein().representative(0), Some(b'I'));
+    assert_eq!(Alphabet::preset("dna-n"), Some(Alphabet::dna_n()));
+    assert_eq!(Alphabet::preset("Reduced-Protein"), Some(Alphabet::reduced_protein()));
+    assert_eq!(Alphabet::preset("unknown"), None);
+    for name in Alphabet::PRESET_NAMES {
+        assert!(Alphabet::preset(name).is_some());
+    }
+}
+
+#[test]
+fn invalid_alphabet_is_error() {
+    assert!(matches!(Alphabet::new::<&[u8]>(&[]), Err(BuildError::EmptyAlphabet)));
+    assert!(matches!(Alphabet::new(&[b"AC", b"CG"]), Err(BuildError::DuplicateCharacter('C'))));
+}
@@ -0,0 +1,129 @@
+use crate::{BuildError, AnyLtFmIndex, PositionType, BackendType, BuildOptions, UnindexedCharacters};
+use crate::tests::random_data::{
+    gen_rand_chr_list,
+    gen_rand_text,
+    gen_rand_pattern,
+};
+use std::io::ErrorKind;
+
+const POSITIONS: [PositionType; 2] = [PositionType::U32, PositionType::U64];
+const BACKENDS: [BackendType; 6] = [
+    BackendType::Block2, BackendType::Block3, BackendType::Block4,
+    BackendType::Block5, BackendType::Block6, BackendType::WaveletTree,
+];
+
+#[test]
+fn results_of_every_type_are_same() {
+    let chr_list = gen_rand_chr_list(3);
+    let text = gen_rand_text(&chr_list, 100, 300);
+    let characters_by_index: Vec<&[u8]> = chr_list.chunks(1).collect();
+    let options = BuildOptions::new().unindexed_characters(UnindexedCharacters::Separator);
+    let indexes: Vec<AnyLtFmIndex> = POSITIONS.into_iter().flat_map(|position| {
+        BACKENDS.into_iter().map(move |backend| (position, backend))
+    }).map(|(position, backend)| {
+        let index = AnyLtFmIndex::build(text.clone(), &characters_by_index, position, backend, 2, 3, &options).unwrap();
+        assert_eq!((index.position_type(), index.backend_type()), (position, backend));
+        assert_eq!(index.len_text(), text.len() as u64);
+        assert_eq!(index.unindexed_characters(), UnindexedCharacters::Separator);
+        index
+    }).collect();
+    for _ in 0..20 {
+        let pattern = gen_rand_pattern(&text, 1, 10);
+        let mut answer: Vec<u64> = (0..=text.len() - pattern.len())
+            .filter(|&start| text[start..].starts_with(&pattern))
+            .map(|start| start as u64)
+            .collect();
+        answer.sort_unstable();
+        for index in indexes.iter() {
+            assert_eq!(index.count(&pattern), answer.len() as u64);
+            let mut locations = index.locate(&pattern);
+            locations.sort_unstable();
+            assert_eq!(locations, answer);
+        }
+    }
+}
+
+#[test]
+fn saved_with_header_of_types() {
+    let text = gen_rand_text(b"ACGT", 100, 300);
+    for position in POSITIONS {
+        for backend in BACKENDS {
+            let index = AnyLtFmIndex::build(
+                text.clone(), &[b"A", b"C", b"G"], position, backend, 3, 4, &BuildOptions::default(),
+            ).unwrap();
+            let mut buffer = Vec::new();
+            index.save_to(&mut buffer).unwrap();
+            assert_eq!(buffer.len(), index.encoded_len());
+            assert_eq!(&buffer[..8], b"LTFMIDX1");
+            assert_eq!(buffer[8..10], [position.code(), backend.code()]);
+            let loaded = AnyLtFmIndex::load_from(&buffer[..]).unwrap();
+            assert_eq!(loaded, index);
+        }
+    }
+}
+
+#[test]
+fn typed_index_is_converted() {
+    use crate::LtFmIndex;
+    use crate::blocks::Block3;
+
+    let text = b"ACGTACGTTTACGT".to_vec();
+    let typed = LtFmIndex::<u64, Block3<u64>>::build(text, &[b"A", b"C", b"G", b"T"], 2, 3).unwrap();
+    let index = AnyLtFmIndex::from(typed.clone());
+    assert_eq!((index.position_type(), index.backend_type()), (PositionType::U64, BackendType::Block3));
+    assert_eq!(index.component_sizes(), typed.component_sizes());
+    assert_eq!(index.decoding_table(), typed.decoding_table());
+    assert_eq!(index, AnyLtFmIndex::U64Block3(typed));
+}
+
+#[test]
+fn invalid_header_is_error() {
+    let index = AnyLtFmIndex::build(
+        b"ACGTACGT".to_vec(), &[b"A", b"C", b"G", b"T"], PositionType::U32, BackendType::Block3, 2, 3, &BuildOptions::default(),
+    ).unwrap();
+    let mut buffer = Vec::new();
+    index.save_to(&mut buffer).unwrap();
+    for (idx, value) in [(0, b'X'), (8, 16), (9, 7)] {
+        let mut invalid = buffer.clone();
+        invalid[idx] = value;
+        assert_eq!(AnyLtFmIndex::load_from(&invalid[..]).unwrap_err().kind(), ErrorKind::InvalidData);
+    }
+    assert_eq!(AnyLtFmIndex::load_from(&buffer[..4]).unwrap_err().kind(), ErrorKind::InvalidData);
+    assert!(AnyLtFmIndex::load_from(&buffer[..buffer.len() / 2]).is_err());
+}
+
+#[test]
+fn types_by_names_and_codes() {
+    for position in POSITIONS {
+        assert_eq!(PositionType::from_name(position.name()), Some(position));
+        assert_eq!(PositionType::from_code(position.code()), Some(position));
+    }
+    for backend in BACKENDS {
+        assert_eq!(BackendType::from_name(backend.name()), Some(backend));
+        assert_eq!(BackendType::from_code(backend.code()), Some(backend));
+    }
+    assert_eq!(PositionType::from_name("u16"), None);
+    assert_eq!(BackendType::from_name("block7"), None);
+    assert_eq!(PositionType::fitting(u32::MAX as u64), PositionType::U32);
+    assert_eq!(PositionType::fitting(u32::MAX as u64 + 1), PositionType::U64);
+    assert_eq!(BackendType::fitting(4), BackendType::Block3);
+    assert_eq!(BackendType::fitting(64), BackendType::WaveletTree);
+}
+
+#[test]
+fn default_kmer_size_fits_lookup_table() {
+    // 5^8 = 390625 entries with `ACGT`
+    assert_eq!(AnyLtFmIndex::default_kmer_size(4), 8);
+    // 21^4 = 194481 entries with the amino acids
+    assert_eq!(AnyLtFmIndex::default_kmer_size(20), 4);
+    assert_eq!(AnyLtFmIndex::default_kmer_size(255), 2);
+}
+
+#[test]
+fn sampling_ratio_over_position_is_error() {
+    let result = AnyLtFmIndex::build(
+        b"ACGTACGT".to_vec(), &[b"A", b"C", b"G", b"T"], PositionType::U32, BackendType::Block3,
+        u32::MAX as u64 + 1, 2, &BuildOptions::default(),
+    );
+    assert!(matches!(result, Err(BuildError::SuffixArraySamplingRatio)));
+}
@@ -1,5 +1,6 @@
-use crate::{LtFmIndex, Position, Block};
+use crate::{LtFmIndex, RlFmIndex, Position, Block, BuildOptions, LookupTable, SuffixArraySampling};
 use crate::blocks::{Block2, Block3, Block4, Block5, Block6};
+use crate::backends::{WaveletTree, Interleaved, Superblock};
 use crate::tests::random_data::{
     gen_rand_chr_list,
     gen_rand_text,
@@ -7,7 +8,7 @@ use crate::tests::random_data::{
 use std::pin::Pin;
 
 async fn assert_serializing_is_success<P: Position, B: Block<P> + std::cmp::PartialEq>(
-    chr_list: &Vec<u8>,
+    chr_list: &[u8],
     text: Vec<u8>,
     ltks: u32,
     sasr: u64,
@@ -16,7 +17,7 @@ async fn assert_serializing_is_success<P: Position, B: Block<P> + std::cmp::Part
         println!("          pass");
         return;
     }
-    let characters_by_index = chr_list.chunks(1).map(|c| c).collect::<Vec<_>>();
+    let characters_by_index = chr_list.chunks(1).collect::<Vec<_>>();
     let lt_fm_index = LtFmIndex::<P, B>::build(
         text,
         &characters_by_index,
@@ -91,3 +92,123 @@ async fn async_save_and_load() {
         }
     }
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn async_save_and_load_of_wavelet_tree() {
+    let chr_list = gen_rand_chr_list(20);
+    let text = gen_rand_text(&chr_list, 100, 300);
+    let characters_by_index = chr_list.chunks(1).collect::<Vec<_>>();
+    let lt_fm_index = LtFmIndex::<u32, WaveletTree>::build(
+        text,
+        &characters_by_index,
+        2,
+        3,
+    ).unwrap();
+    let mut buffer = Vec::new();
+    lt_fm_index.async_save_to(Pin::new(&mut buffer)).await.unwrap();
+
+    let loaded: LtFmIndex::<u32, WaveletTree> = LtFmIndex::async_load_from(Pin::new(&mut &buffer[..])).await.unwrap();
+    assert_eq!(lt_fm_index, loaded);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn async_save_and_load_of_interleaved() {
+    let chr_list = gen_rand_chr_list(20);
+    let text = gen_rand_text(&chr_list, 100, 300);
+    let characters_by_index = chr_list.chunks(1).collect::<Vec<_>>();
+    let lt_fm_index = LtFmIndex::<u64, Interleaved<Block5<u32>>>::build(
+        text,
+        &characters_by_index,
+        2,
+        3,
+    ).unwrap();
+    let mut buffer = Vec::new();
+    lt_fm_index.async_save_to(Pin::new(&mut buffer)).await.unwrap();
+
+    let loaded: LtFmIndex::<u64, Interleaved<Block5<u32>>> = LtFmIndex::async_load_from(Pin::new(&mut &buffer[..])).await.unwrap();
+    assert_eq!(lt_fm_index, loaded);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn async_save_and_load_of_superblock() {
+    let chr_list = gen_rand_chr_list(20);
+    let text = gen_rand_text(&chr_list, 100, 300);
+    let characters_by_index = chr_list.chunks(1).collect::<Vec<_>>();
+    let lt_fm_index = LtFmIndex::<u64, Superblock<Block5<u32>, 4>>::build(
+        text,
+        &characters_by_index,
+        2,
+        3,
+    ).unwrap();
+    let mut buffer = Vec::new();
+    lt_fm_index.async_save_to(Pin::new(&mut buffer)).await.unwrap();
+
+    let loaded: LtFmIndex::<u64, Superblock<Block5<u32>, 4>> = LtFmIndex::async_load_from(Pin::new(&mut &buffer[..])).await.unwrap();
+    assert_eq!(lt_fm_index, loaded);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn async_save_and_load_of_rl_fm_index() {
+    let chr_list = gen_rand_chr_list(4);
+    let text = gen_rand_text(&chr_list, 100, 300);
+    let characters_by_index = chr_list.chunks(1).collect::<Vec<_>>();
+    let rl_fm_index = RlFmIndex::<u32>::build(
+        text,
+        &characters_by_index,
+        3,
+    ).unwrap();
+    let mut buffer = Vec::new();
+    rl_fm_index.async_save_to(Pin::new(&mut buffer)).await.unwrap();
+
+    let loaded = RlFmIndex::<u32>::async_load_from(Pin::new(&mut &buffer[..])).await.unwrap();
+    assert_eq!(rl_fm_index, loaded);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn async_save_and_load_of_sparse_lookup_table() {
+    let chr_list = gen_rand_chr_list(3);
+    let text = gen_rand_text(&chr_list, 100, 300);
+    let characters_by_index = chr_list.chunks(1).collect::<Vec<_>>();
+    let lt_fm_index = LtFmIndex::<u32, Block2<u64>>::build_with_options(
+        text,
+        &characters_by_index,
+        2,
+        10,
+        &BuildOptions::new().lookup_table(LookupTable::Sparse),
+    ).unwrap();
+    let mut buffer = Vec::new();
+    lt_fm_index.async_save_to(Pin::new(&mut buffer)).await.unwrap();
+
+    let loaded: LtFmIndex::<u32, Block2<u64>> = LtFmIndex::async_load_from(Pin::new(&mut &buffer[..])).await.unwrap();
+    assert_eq!(lt_fm_index, loaded);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn async_save_and_load_of_text_position_sampling() {
+    let chr_list = gen_rand_chr_list(3);
+    let text = gen_rand_text(&chr_list, 100, 300);
+    let characters_by_index = chr_list.chunks(1).collect::<Vec<_>>();
+    let lt_fm_index = LtFmIndex::<u32, Block2<u64>>::build_with_options(
+        text,
+        &characters_by_index,
+        4,
+        3,
+        &BuildOptions::new().suffix_array_sampling(SuffixArraySampling::TextPosition),
+    ).unwrap();
+    let mut buffer = Vec::new();
+    lt_fm_index.async_save_to(Pin::new(&mut buffer)).await.unwrap();
+
+    let loaded: LtFmIndex::<u32, Block2<u64>> = LtFmIndex::async_load_from(Pin::new(&mut &buffer[..])).await.unwrap();
+    assert_eq!(lt_fm_index, loaded);
+}
+
+#[cfg(target_endian = "little")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn async_load_of_index_of_0_7() {
+    let text = b"CTCCGTACACCTGTTTCGTATCGGAXXYYZZ".to_vec();
+    let lt_fm_index = LtFmIndex::<u32, Block2<u64>>::build(text, &[b"A", b"C", b"G"], 2, 3).unwrap();
+    let saved: &[u8] = include_bytes!("../save_and_load/index_of_0_7.bin");
+
+    let loaded: LtFmIndex::<u32, Block2<u64>> = LtFmIndex::async_load_from(Pin::new(&mut &saved[..])).await.unwrap();
+    assert_eq!(loaded, lt_fm_index);
+}
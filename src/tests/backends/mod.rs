@@ -0,0 +1,258 @@
+use crate::{LtFmIndex, Position, BwmBackend};
+use crate::blocks::{Block2, Block3, Block4, Block5, Block6};
+use crate::backends::{WaveletTree, Interleaved, Superblock};
+use crate::tests::{
+    random_data::{
+        gen_rand_chr_list,
+        gen_rand_text,
+        gen_rand_pattern,
+    },
+    result_answer::{
+        get_fmindex_of_other_crate,
+        get_sorted_locations,
+    },
+};
+use std::io::Cursor;
+
+fn assert_accurate_and_serializable<P: Position, B: BwmBackend<P> + std::cmp::PartialEq>(
+    chr_list: &[u8],
+    text: Vec<u8>,
+    patterns: &[Vec<u8>],
+    answers: &[Vec<u64>],
+    ltks: u32,
+    sasr: u64,
+) {
+    let characters_by_index = chr_list.chunks(1).collect::<Vec<_>>();
+    let lt_fm_index = LtFmIndex::<P, B>::build(
+        text,
+        &characters_by_index,
+        P::from_u64(sasr),
+        ltks,
+    ).unwrap();
+    patterns.iter().zip(answers.iter()).for_each(|(pattern, answer)| {
+        let mut result: Vec<u64> = lt_fm_index.locate(pattern).into_iter().map(|x| x.as_u64()).collect();
+        result.sort();
+        assert_eq!(&result, answer);
+        assert_eq!(lt_fm_index.count(pattern).as_u64(), answer.len() as u64);
+    });
+
+    let mut buffer = Vec::new();
+    lt_fm_index.save_to(&mut buffer).unwrap();
+    assert_eq!(lt_fm_index.encoded_len(), buffer.len());
+    let loaded: LtFmIndex<P, B> = LtFmIndex::load_from(Cursor::new(buffer)).unwrap();
+    assert_eq!(lt_fm_index, loaded);
+}
+
+#[test]
+fn wavelet_tree_result_is_accurate() {
+    let chr_counts = [3, 20, 63, 94];
+    let text_min_len = 100;
+    let text_max_len = 300;
+    let n_text = 2;
+    let pattern_min_len = 1;
+    let pattern_max_len = 10;
+    let n_pattern = 100;
+    let ltks = 2;
+    let sasr = 2;
+
+    for chr_count in chr_counts {
+        println!("- Chr count: {}", chr_count);
+        for i in 0..n_text {
+            println!("  - text: {}/{}", i+1, n_text);
+            // Leave out one character to also test the unindexed characters
+            let chr_list = gen_rand_chr_list(chr_count);
+            let text = gen_rand_text(&chr_list, text_min_len, text_max_len);
+            let indexed_chr_list = &chr_list[..chr_count - 1];
+
+            let patterns: Vec<Vec<u8>> = (0..n_pattern).map(|_| {
+                gen_rand_pattern(&text, pattern_min_len, pattern_max_len)
+            }).collect();
+            let answers: Vec<Vec<u64>> = {
+                let fm_index = get_fmindex_of_other_crate(&text);
+                patterns.iter().map(|pattern| {
+                    get_sorted_locations(&fm_index, pattern)
+                }).collect()
+            };
+            assert_accurate_and_serializable::<u32, WaveletTree>(
+                indexed_chr_list, text.clone(), &patterns, &answers, ltks, sasr,
+            );
+            assert_accurate_and_serializable::<u64, WaveletTree>(
+                indexed_chr_list, text.clone(), &patterns, &answers, ltks, sasr,
+            );
+        }
+    }
+}
+
+#[test]
+fn wavelet_tree_of_single_character_text() {
+    let text = b"AAAAAAAAAA".to_vec();
+    let characters_by_index: &[&[u8]] = &[b"A", b"C"];
+    let lt_fm_index = LtFmIndex::<u32, WaveletTree>::build(text, characters_by_index, 3, 2).unwrap();
+    assert_eq!(lt_fm_index.count(b"AAA"), 8);
+    assert_eq!(lt_fm_index.count(b"C"), 0);
+    assert_eq!(lt_fm_index.count(b"X"), 0);
+    let mut locations = lt_fm_index.locate(b"AAAAAAAAA");
+    locations.sort();
+    assert_eq!(locations, vec![0, 1]);
+}
+
+#[test]
+fn interleaved_result_is_accurate() {
+    let text_min_len = 100;
+    let text_max_len = 300;
+    let n_text = 2;
+    let pattern_min_len = 1;
+    let pattern_max_len = 10;
+    let n_pattern = 100;
+    let ltks = 2;
+    let sasr = 2;
+
+    macro_rules! for_vectors {
+        ( $chr_count: expr, $block: ident ) => {
+            for i in 0..n_text {
+                println!("- {}: text {}/{}", stringify!($block), i+1, n_text);
+                let chr_list = gen_rand_chr_list($chr_count);
+                let text = gen_rand_text(&chr_list, text_min_len, text_max_len);
+                let indexed_chr_list = &chr_list[..$chr_count - 1];
+
+                let patterns: Vec<Vec<u8>> = (0..n_pattern).map(|_| {
+                    gen_rand_pattern(&text, pattern_min_len, pattern_max_len)
+                }).collect();
+                let answers: Vec<Vec<u64>> = {
+                    let fm_index = get_fmindex_of_other_crate(&text);
+                    patterns.iter().map(|pattern| {
+                        get_sorted_locations(&fm_index, pattern)
+                    }).collect()
+                };
+                assert_accurate_and_serializable::<u32, Interleaved<$block<u32>>>(
+                    indexed_chr_list, text.clone(), &patterns, &answers, ltks, sasr,
+                );
+                assert_accurate_and_serializable::<u64, Interleaved<$block<u64>>>(
+                    indexed_chr_list, text.clone(), &patterns, &answers, ltks, sasr,
+                );
+                assert_accurate_and_serializable::<u32, Interleaved<$block<u128>>>(
+                    indexed_chr_list, text.clone(), &patterns, &answers, ltks, sasr,
+                );
+            }
+        };
+    }
+    for_vectors!(4, Block2);
+    for_vectors!(8, Block3);
+    for_vectors!(16, Block4);
+    for_vectors!(32, Block5);
+    for_vectors!(64, Block6);
+}
+
+#[test]
+fn interleaved_is_same_as_separated_at_block_boundaries() {
+    let chr_list = gen_rand_chr_list(4);
+    let characters_by_index = chr_list[..3].chunks(1).collect::<Vec<_>>();
+    // The BWT of the text fits to the blocks of 32 characters or has a remainder
+    for text_len in [30, 31, 32, 63, 64, 95] {
+        let text = gen_rand_text(&chr_list, text_len, text_len);
+        let separated = LtFmIndex::<u32, Block2<u32>>::build(text.clone(), &characters_by_index, 1, 2).unwrap();
+        let interleaved = LtFmIndex::<u32, Interleaved<Block2<u32>>>::build(text.clone(), &characters_by_index, 1, 2).unwrap();
+        for pattern_len in 1..=4 {
+            for start in 0..=text_len - pattern_len {
+                let pattern = &text[start..start + pattern_len];
+                assert_eq!(interleaved.count(pattern), separated.count(pattern));
+                let mut interleaved_locations = interleaved.locate(pattern);
+                let mut separated_locations = separated.locate(pattern);
+                interleaved_locations.sort();
+                separated_locations.sort();
+                assert_eq!(interleaved_locations, separated_locations);
+            }
+        }
+    }
+}
+
+#[test]
+fn superblock_result_is_accurate() {
+    let text_min_len = 100;
+    let text_max_len = 300;
+    let n_text = 2;
+    let pattern_min_len = 1;
+    let pattern_max_len = 10;
+    let n_pattern = 100;
+    let ltks = 2;
+    let sasr = 2;
+
+    macro_rules! for_intervals {
+        ( $chr_count: expr, $block: ident ) => {
+            for i in 0..n_text {
+                println!("- {}: text {}/{}", stringify!($block), i+1, n_text);
+                let chr_list = gen_rand_chr_list($chr_count);
+                let text = gen_rand_text(&chr_list, text_min_len, text_max_len);
+                let indexed_chr_list = &chr_list[..$chr_count - 1];
+
+                let patterns: Vec<Vec<u8>> = (0..n_pattern).map(|_| {
+                    gen_rand_pattern(&text, pattern_min_len, pattern_max_len)
+                }).collect();
+                let answers: Vec<Vec<u64>> = {
+                    let fm_index = get_fmindex_of_other_crate(&text);
+                    patterns.iter().map(|pattern| {
+                        get_sorted_locations(&fm_index, pattern)
+                    }).collect()
+                };
+                assert_accurate_and_serializable::<u32, Superblock<$block<u32>, 1>>(
+                    indexed_chr_list, text.clone(), &patterns, &answers, ltks, sasr,
+                );
+                assert_accurate_and_serializable::<u64, Superblock<$block<u64>, 3>>(
+                    indexed_chr_list, text.clone(), &patterns, &answers, ltks, sasr,
+                );
+                assert_accurate_and_serializable::<u32, Superblock<$block<u128>, 4>>(
+                    indexed_chr_list, text.clone(), &patterns, &answers, ltks, sasr,
+                );
+            }
+        };
+    }
+    for_intervals!(4, Block2);
+    for_intervals!(8, Block3);
+    for_intervals!(16, Block4);
+    for_intervals!(32, Block5);
+    for_intervals!(64, Block6);
+}
+
+#[test]
+fn superblock_is_same_as_single_level_across_superblocks() {
+    let chr_list = gen_rand_chr_list(4);
+    let characters_by_index = chr_list[..3].chunks(1).collect::<Vec<_>>();
+    // Longer than the several superblocks of 2^16 characters
+    let text = gen_rand_text(&chr_list, 200_000, 200_000);
+    let patterns: Vec<Vec<u8>> = (0..200).map(|_| gen_rand_pattern(&text, 1, 12)).collect();
+
+    let single_level = LtFmIndex::<u64, Block2<u32>>::build(text.clone(), &characters_by_index, 4, 3).unwrap();
+    let short_interval = LtFmIndex::<u64, Superblock<Block2<u32>, 1>>::build(text.clone(), &characters_by_index, 4, 3).unwrap();
+    let long_interval = LtFmIndex::<u64, Superblock<Block2<u32>, 16>>::build(text.clone(), &characters_by_index, 4, 3).unwrap();
+    let whole_superblock = LtFmIndex::<u64, Superblock<Block2<u128>, 512>>::build(text, &characters_by_index, 4, 3).unwrap();
+
+    for pattern in patterns.iter() {
+        let mut answer = single_level.locate(pattern);
+        answer.sort();
+        for (count, mut locations) in [
+            (short_interval.count(pattern), short_interval.locate(pattern)),
+            (long_interval.count(pattern), long_interval.locate(pattern)),
+            (whole_superblock.count(pattern), whole_superblock.locate(pattern)),
+        ] {
+            locations.sort();
+            assert_eq!(count, answer.len() as u64);
+            assert_eq!(locations, answer);
+        }
+    }
+    // The longer interval takes less memory
+    assert!(short_interval.encoded_len() < single_level.encoded_len());
+    assert!(long_interval.encoded_len() < short_interval.encoded_len());
+}
+
+#[test]
+fn superblock_is_not_loaded_with_other_interval() {
+    let chr_list = gen_rand_chr_list(4);
+    let text = gen_rand_text(&chr_list, 100, 300);
+    let characters_by_index = chr_list.chunks(1).collect::<Vec<_>>();
+    let lt_fm_index = LtFmIndex::<u32, Superblock<Block3<u64>, 2>>::build(text, &characters_by_index, 2, 2).unwrap();
+    let mut buffer = Vec::new();
+    lt_fm_index.save_to(&mut buffer).unwrap();
+
+    let error = LtFmIndex::<u32, Superblock<Block3<u64>, 4>>::load_from(Cursor::new(buffer)).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
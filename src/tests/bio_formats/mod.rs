@@ -0,0 +1,181 @@
+use crate::{BuildOptions, UnindexedCharacters, AnyLtFmIndex, PositionType, BackendType};
+use crate::bio_formats::{
+    MultiSequenceIndex, AnyMultiSequenceIndex, Coordinate, Contig, FastaReader, FastqReader, FastqRecord,
+};
+use crate::blocks::Block3;
+use crate::backends::WaveletTree;
+use crate::tests::random_data::{
+    gen_rand_text,
+    gen_rand_pattern,
+};
+use std::io::ErrorKind;
+
+fn to_fasta(contigs: &[(String, Vec<u8>)], line_len: usize, line_ending: &str) -> Vec<u8> {
+    let mut fasta = Vec::new();
+    contigs.iter().for_each(|(name, sequence)| {
+        fasta.extend(format!(">{} description{}", name, line_ending).bytes());
+        sequence.chunks(line_len).for_each(|line| {
+            fasta.extend_from_slice(line);
+            fasta.extend(line_ending.bytes());
+        });
+    });
+    fasta
+}
+
+fn locate_naively<'a>(contigs: &'a [(String, Vec<u8>)], pattern: &[u8]) -> Vec<(&'a str, u32)> {
+    let mut locations = Vec::new();
+    contigs.iter().for_each(|(name, sequence)| {
+        sequence.windows(pattern.len()).enumerate().for_each(|(offset, window)| {
+            if window == pattern {
+                locations.push((name.as_str(), offset as u32));
+            }
+        });
+    });
+    locations.sort();
+    locations
+}
+
+#[test]
+fn locate_in_contigs_of_fasta() {
+    let chr_list = b"ACGT";
+    let separated = BuildOptions::new().unindexed_characters(UnindexedCharacters::Separator);
+    for (line_len, line_ending, options) in [(60, "\n", BuildOptions::new()), (7, "\r\n", separated)] {
+        let contigs: Vec<(String, Vec<u8>)> = (0..5).map(|idx| {
+            (format!("contig{}", idx), gen_rand_text(chr_list, 30, 200))
+        }).collect();
+        let fasta = to_fasta(&contigs, line_len, line_ending);
+        let index = MultiSequenceIndex::<u32, Block3<u64>>::build_from_fasta(
+            &fasta[..], &[b"A", b"C", b"G", b"T"], 3, 3, &options,
+        ).unwrap();
+        assert_eq!(
+            index.contigs(),
+            &contigs.iter().map(|(name, sequence)| Contig {
+                name: name.clone(),
+                len: sequence.len() as u64,
+            }).collect::<Vec<_>>()[..],
+        );
+        for _ in 0..50 {
+            let pattern = gen_rand_pattern(&contigs[0].1, 1, 10);
+            let answer = locate_naively(&contigs, &pattern);
+            let mut locations = index.locate_in_contigs(&pattern, Coordinate::ZeroBased);
+            locations.sort();
+            assert_eq!(locations, answer);
+            assert_eq!(index.count(&pattern), answer.len() as u32);
+            let encoded = index.index().encode_pattern(&pattern);
+            let mut locations = index.try_locate_encoded_pattern_in_contigs(&encoded, Coordinate::ZeroBased).unwrap();
+            locations.sort();
+            assert_eq!(locations, answer);
+            assert_eq!(index.try_count_encoded_pattern(&encoded), Ok(answer.len() as u32));
+            let mut locations = index.locate_in_contigs(&pattern, Coordinate::OneBased);
+            locations.sort();
+            assert_eq!(locations, answer.iter().map(|&(name, offset)| (name, offset + 1)).collect::<Vec<_>>());
+        }
+    }
+}
+
+#[test]
+fn occurrences_across_contigs_are_excluded() {
+    let fasta = b">a\nAACG\n>empty\n>b\nTAAC\n";
+    let separated = BuildOptions::new().unindexed_characters(UnindexedCharacters::Separator);
+    for (options, count_across_in_index) in [(BuildOptions::new(), 1), (separated, 0)] {
+        let index = MultiSequenceIndex::<u32, Block3<u64>>::build_from_fasta(
+            &fasta[..], &[b"A", b"C", b"G", b"T"], 2, 2, &options,
+        ).unwrap();
+        assert_eq!(index.contigs()[1], Contig { name: "empty".to_string(), len: 0 });
+        assert_eq!(index.index().count(b"CGTA"), count_across_in_index);
+        assert_eq!(index.count(b"CGTA"), 0);
+        assert_eq!(index.count(b""), 8);
+        assert_eq!(index.locate_in_contigs(b"", Coordinate::ZeroBased).len(), 8);
+        let mut locations = index.locate_in_contigs(b"AAC", Coordinate::ZeroBased);
+        locations.sort();
+        assert_eq!(locations, vec![("a", 0), ("b", 1)]);
+    }
+}
+
+#[test]
+fn contigs_are_saved_with_index() {
+    let fasta = b">chr1\nACGTACGT\n>chr2 second\nGGTTAACC\n";
+    let separated = BuildOptions::new().unindexed_characters(UnindexedCharacters::Separator);
+    for options in [BuildOptions::new(), separated] {
+        let index = MultiSequenceIndex::<u32, Block3<u64>>::build_from_fasta(
+            &fasta[..], &[b"A", b"C", b"G", b"T"], 2, 3, &options,
+        ).unwrap();
+        let mut buffer = Vec::new();
+        index.save_to(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), index.encoded_len());
+        let loaded = MultiSequenceIndex::<u32, Block3<u64>>::load_from(&buffer[..]).unwrap();
+        assert_eq!(loaded, index);
+        assert_eq!(loaded.locate_in_contigs(b"TTA", Coordinate::OneBased), vec![("chr2", 3)]);
+        assert_eq!(loaded.count(b"GTGG"), 0);
+    }
+}
+
+#[test]
+fn sequence_without_header_is_error() {
+    let mut records = FastaReader::new(&b"\nACGT\n>chr1\nACGT\n"[..]);
+    assert_eq!(records.next().unwrap().unwrap_err().kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn any_index_is_same_as_typed_index() {
+    let fasta = b">a\nAACG\n>empty\n>b\nTAACGT\n";
+    let separated = BuildOptions::new().unindexed_characters(UnindexedCharacters::Separator);
+    for options in [BuildOptions::new(), separated] {
+        let typed = MultiSequenceIndex::<u64, WaveletTree>::build_from_fasta(
+            &fasta[..], &[b"A", b"C", b"G", b"T"], 2, 2, &options,
+        ).unwrap();
+        let index = AnyMultiSequenceIndex::build_from_fasta(
+            &fasta[..], &[b"A", b"C", b"G", b"T"], PositionType::U64, BackendType::WaveletTree, 2, 2, &options,
+        ).unwrap();
+        assert_eq!(index.contigs(), typed.contigs());
+        for pattern in [&b""[..], b"CGTA", b"AAC", b"ACGT", b"G"] {
+            assert_eq!(index.count(pattern), typed.count(pattern));
+            let mut locations = index.locate_in_contigs(pattern, Coordinate::OneBased);
+            locations.sort();
+            let mut answer = typed.locate_in_contigs(pattern, Coordinate::OneBased);
+            answer.sort();
+            assert_eq!(locations, answer);
+        }
+
+        // Saved as the typed index after the header of the types
+        let mut buffer = Vec::new();
+        index.save_to(&mut buffer).unwrap();
+        assert_eq!(buffer.len(), index.encoded_len());
+        let mut typed_buffer = Vec::new();
+        typed.save_to(&mut typed_buffer).unwrap();
+        assert_eq!(buffer[10..], typed_buffer[..]);
+        let loaded = AnyMultiSequenceIndex::load_from(&buffer[..]).unwrap();
+        assert_eq!(loaded, index);
+        // The index of the concatenated sequences
+        assert_eq!(&AnyLtFmIndex::load_from(&buffer[..]).unwrap(), index.index());
+    }
+}
+
+#[test]
+fn read_fastq() {
+    let fastq = b"@read1 desc\nACGT\n+\nIIII\n\n@read2\r\nGG\r\n+read2\r\n#I\r\n";
+    let records = FastqReader::new(&fastq[..]).collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(records, vec![
+        FastqRecord { name: "read1".to_string(), sequence: b"ACGT".to_vec(), quality: b"IIII".to_vec() },
+        FastqRecord { name: "read2".to_string(), sequence: b"GG".to_vec(), quality: b"#I".to_vec() },
+    ]);
+
+    for malformed in [&b">read1\nACGT\n+\nIIII\n"[..], b"@read1\nACGT\n+\nIII\n", b"@read1\nACGT\n"] {
+        let error = FastqReader::new(malformed).next().unwrap().unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+    }
+}
+
+#[cfg(feature = "async-io")]
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn contigs_are_async_saved_with_index() {
+    use std::pin::Pin;
+    let fasta = b">chr1\nACGTACGT\n>chr2 second\nGGTTAACC\n";
+    let index = MultiSequenceIndex::<u32, Block3<u64>>::build_from_fasta(
+        &fasta[..], &[b"A", b"C", b"G", b"T"], 2, 3, &BuildOptions::default(),
+    ).unwrap();
+    let mut buffer = Vec::new();
+    index.async_save_to(Pin::new(&mut buffer)).await.unwrap();
+    let loaded = MultiSequenceIndex::<u32, Block3<u64>>::async_load_from(Pin::new(&mut &buffer[..])).await.unwrap();
+    assert_eq!(loaded, index);
+}
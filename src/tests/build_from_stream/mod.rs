@@ -0,0 +1,85 @@
+use crate::{LtFmIndex, BuildOptions, LookupTable, BuildError};
+use crate::blocks::Block3;
+use crate::tests::random_data::{
+    gen_rand_chr_list,
+    gen_rand_text,
+};
+use std::io::{Read, Cursor};
+
+// Reader returning the text in small chunks of various sizes
+struct ChunkedReader {
+    text: Vec<u8>,
+    read_len: usize,
+    chunk_len: usize,
+}
+
+impl Read for ChunkedReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.chunk_len = self.chunk_len % 7 + 1;
+        let len = self.chunk_len.min(buf.len()).min(self.text.len() - self.read_len);
+        buf[..len].copy_from_slice(&self.text[self.read_len..self.read_len + len]);
+        self.read_len += len;
+        Ok(len)
+    }
+}
+
+#[test]
+fn streamed_index_is_same_as_built() {
+    let option_list = [
+        BuildOptions::new(),
+        BuildOptions::new().lookup_table(LookupTable::Sparse),
+    ];
+    for options in option_list.iter() {
+        for (min_len, max_len, ltks) in [(100, 300, 3), (1, 4, 5), (500, 1000, 8)] {
+            let chr_list = gen_rand_chr_list(5);
+            let characters_by_index = chr_list[..4].chunks(1).collect::<Vec<_>>();
+            let text = gen_rand_text(&chr_list, min_len, max_len);
+            let built = LtFmIndex::<u32, Block3<u64>>::build_with_options(
+                text.clone(), &characters_by_index, 2, ltks, options,
+            ).unwrap();
+
+            let from_iter = LtFmIndex::<u32, Block3<u64>>::build_from_iter(
+                text.iter().copied(), &characters_by_index, 2, ltks, options,
+            ).unwrap();
+            assert_eq!(from_iter, built);
+            let from_reader = LtFmIndex::<u32, Block3<u64>>::build_from_reader(
+                Cursor::new(text.clone()), &characters_by_index, 2, ltks, options,
+            ).unwrap();
+            assert_eq!(from_reader, built);
+            let reader = ChunkedReader { text, read_len: 0, chunk_len: 0 };
+            let from_chunks = LtFmIndex::<u32, Block3<u64>>::build_from_reader(
+                reader, &characters_by_index, 2, ltks, options,
+            ).unwrap();
+            assert_eq!(from_chunks, built);
+        }
+    }
+}
+
+#[test]
+fn reading_error_is_returned() {
+    struct FailingReader;
+    impl Read for FailingReader {
+        fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("failed"))
+        }
+    }
+    let result = LtFmIndex::<u32, Block3<u64>>::build_from_reader(
+        FailingReader, &[b"A", b"C"], 2, 3, &BuildOptions::default(),
+    );
+    assert!(matches!(result, Err(BuildError::Io(_))));
+}
+
+#[test]
+fn text_shorter_than_kmer_size() {
+    let text = b"ACGA".to_vec();
+    let lt_fm_index = LtFmIndex::<u32, Block3<u64>>::build_from_iter(
+        text, &[b"A", b"C", b"G"], 1, 6, &BuildOptions::default(),
+    ).unwrap();
+    assert_eq!(lt_fm_index.count(b"A"), 2);
+    assert_eq!(lt_fm_index.count(b"CGA"), 1);
+    assert_eq!(lt_fm_index.count(b"ACGA"), 1);
+    assert_eq!(lt_fm_index.count(b"GAA"), 0);
+    let mut locations = lt_fm_index.locate(b"A");
+    locations.sort();
+    assert_eq!(locations, vec![0, 3]);
+}
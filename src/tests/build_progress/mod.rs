@@ -0,0 +1,157 @@
+use crate::{LtFmIndex, Position, BwmBackend, BuildOptions, SuffixArraySampling, BuildError};
+use crate::{BuildPhase, BuildObserver, CancellationToken};
+use crate::blocks::{Block3, Block5};
+use crate::backends::WaveletTree;
+use crate::tests::random_data::{
+    gen_rand_chr_list,
+    gen_rand_text,
+};
+use std::time::Duration;
+
+const PHASES: [BuildPhase; 4] = [
+    BuildPhase::Encoding,
+    BuildPhase::SuffixSorting,
+    BuildPhase::Bwt,
+    BuildPhase::Vectorization,
+];
+
+// Records the reports, and cancels the token at the fraction of the phase
+#[derive(Default)]
+struct Recorder {
+    progresses: Vec<(BuildPhase, f64)>,
+    finished: Vec<(BuildPhase, Duration)>,
+    cancel_at: Option<(BuildPhase, f64, CancellationToken)>,
+}
+
+impl BuildObserver for Recorder {
+    fn on_progress(&mut self, phase: BuildPhase, fraction: f64) {
+        self.progresses.push((phase, fraction));
+        if let Some((cancel_phase, cancel_fraction, token)) = &self.cancel_at {
+            if phase == *cancel_phase && fraction >= *cancel_fraction {
+                token.cancel();
+            }
+        }
+    }
+    fn on_phase_finished(&mut self, phase: BuildPhase, elapsed: Duration) {
+        self.finished.push((phase, elapsed));
+    }
+}
+
+fn assert_progress_is_reported<P, B>(options: &BuildOptions) where
+    P: Position,
+    B: BwmBackend<P>,
+    LtFmIndex<P, B>: PartialEq + std::fmt::Debug,
+{
+    let chr_list = gen_rand_chr_list(5);
+    let characters_by_index = chr_list[..4].chunks(1).collect::<Vec<_>>();
+    let text = gen_rand_text(&chr_list, 1000, 3000);
+    let built = LtFmIndex::<P, B>::build_with_options(
+        text.clone(), &characters_by_index, P::from_u64(3), 4, options,
+    ).unwrap();
+
+    let mut recorder = Recorder::default();
+    let with_progress = LtFmIndex::<P, B>::build_with_progress(
+        text, &characters_by_index, P::from_u64(3), 4, options, &mut recorder, &CancellationToken::new(),
+    ).unwrap();
+    assert_eq!(with_progress, built);
+
+    // Each phase is finished once in order
+    let finished_phases: Vec<BuildPhase> = recorder.finished.iter().map(|(phase, _)| *phase).collect();
+    assert_eq!(finished_phases, PHASES);
+    // The fractions increase from 0.0 to 1.0 in each phase
+    for phase in PHASES {
+        let fractions: Vec<f64> = recorder.progresses.iter()
+            .filter(|(reported, _)| *reported == phase)
+            .map(|(_, fraction)| *fraction)
+            .collect();
+        assert_eq!(fractions.first(), Some(&0.0));
+        assert_eq!(fractions.last(), Some(&1.0));
+        assert!(fractions.windows(2).all(|pair| pair[0] <= pair[1]));
+    }
+    // The phases are reported in order
+    let phase_order = |phase: &BuildPhase| PHASES.iter().position(|x| x == phase).unwrap();
+    assert!(recorder.progresses.windows(2).all(|pair| phase_order(&pair[0].0) <= phase_order(&pair[1].0)));
+}
+
+#[test]
+fn progress_of_each_phase_is_reported() {
+    for options in [
+        BuildOptions::new(),
+        BuildOptions::new().suffix_array_sampling(SuffixArraySampling::TextPosition),
+    ].iter() {
+        assert_progress_is_reported::<u32, Block3<u64>>(options);
+        assert_progress_is_reported::<u64, Block5<u32>>(options);
+        assert_progress_is_reported::<u32, WaveletTree>(options);
+    }
+}
+
+#[test]
+fn progress_is_reported_inside_the_long_phases() {
+    let text = gen_rand_text(b"ACGT", 100_000, 100_000);
+    let mut recorder = Recorder::default();
+    LtFmIndex::<u32, Block3<u64>>::build_with_progress(
+        text, &[b"A", b"C", b"G", b"T"], 2, 4, &BuildOptions::new(), &mut recorder, &CancellationToken::new(),
+    ).unwrap();
+    for phase in PHASES {
+        if phase == BuildPhase::SuffixSorting && cfg!(feature = "fastbwt") {
+            continue;
+        }
+        let count = recorder.progresses.iter().filter(|(reported, _)| *reported == phase).count();
+        assert!(count > 50, "{:?} reported {} times", phase, count);
+    }
+}
+
+#[test]
+fn cancelled_before_build() {
+    let token = CancellationToken::new();
+    token.cancel();
+    let mut recorder = Recorder::default();
+    let result = LtFmIndex::<u32, Block3<u64>>::build_with_progress(
+        b"ACGTACGT".to_vec(), &[b"A", b"C", b"G", b"T"], 2, 4, &BuildOptions::new(), &mut recorder, &token,
+    );
+    assert!(matches!(result, Err(BuildError::Cancelled)));
+    assert!(recorder.progresses.is_empty());
+}
+
+#[test]
+fn cancelled_during_each_phase() {
+    let text = gen_rand_text(b"ACGT", 100_000, 100_000);
+    for (phase_idx, phase) in PHASES.into_iter().enumerate() {
+        // `libdivsufsort` sorts the suffixes in one call, checked at its start and end
+        let fraction = match phase {
+            BuildPhase::SuffixSorting if cfg!(feature = "fastbwt") => 0.0,
+            _ => 0.5,
+        };
+        let token = CancellationToken::new();
+        let mut recorder = Recorder {
+            cancel_at: Some((phase, fraction, token.clone())),
+            ..Default::default()
+        };
+        let result = LtFmIndex::<u32, Block3<u64>>::build_with_progress(
+            text.clone(), &[b"A", b"C", b"G", b"T"], 2, 4, &BuildOptions::new(), &mut recorder, &token,
+        );
+        assert!(matches!(result, Err(BuildError::Cancelled)));
+        // Cancelled in the phase
+        assert_eq!(recorder.finished.len(), phase_idx);
+        assert!(recorder.progresses.iter().all(|(reported, _)| PHASES[..=phase_idx].contains(reported)));
+        if fraction != 0.0 {
+            assert!(!recorder.progresses.contains(&(phase, 1.0)));
+        }
+    }
+}
+
+#[test]
+fn cancelled_during_vectorization_of_wavelet_tree() {
+    let text = gen_rand_text(b"ACGT", 100_000, 100_000);
+    let token = CancellationToken::new();
+    let mut recorder = Recorder {
+        cancel_at: Some((BuildPhase::Vectorization, 0.5, token.clone())),
+        ..Default::default()
+    };
+    let result = LtFmIndex::<u32, WaveletTree>::build_with_progress(
+        text, &[b"A", b"C", b"G", b"T"], 2, 4, &BuildOptions::new(), &mut recorder, &token,
+    );
+    assert!(matches!(result, Err(BuildError::Cancelled)));
+    assert_eq!(recorder.finished.len(), 3);
+    assert!(!recorder.progresses.contains(&(BuildPhase::Vectorization, 1.0)));
+}
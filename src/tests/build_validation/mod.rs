@@ -0,0 +1,68 @@
+use crate::{LtFmIndex, RlFmIndex, BuildOptions, BuildError};
+use crate::Block;
+use crate::blocks::{Block2, Block4};
+use crate::backends::WaveletTree;
+use crate::algorithm::check_text_len;
+
+fn build(text: &[u8], characters_by_index: &[&[u8]]) -> Result<LtFmIndex<u32, WaveletTree>, BuildError> {
+    LtFmIndex::build(text.to_vec(), characters_by_index, 2, 2)
+}
+
+#[test]
+fn empty_text_is_error() {
+    assert!(matches!(build(b"", &[b"A", b"C"]), Err(BuildError::EmptyText)));
+    let from_iter = LtFmIndex::<u32, Block2<u64>>::build_from_iter(
+        std::iter::empty(), &[b"A"], 2, 2, &BuildOptions::new(),
+    );
+    assert!(matches!(from_iter, Err(BuildError::EmptyText)));
+    let from_reader = LtFmIndex::<u32, Block2<u64>>::build_from_reader(
+        std::io::empty(), &[b"A"], 2, 2, &BuildOptions::new(),
+    );
+    assert!(matches!(from_reader, Err(BuildError::EmptyText)));
+    let rl_fm_index = RlFmIndex::<u32>::build(Vec::new(), &[b"A"], 2);
+    assert!(matches!(rl_fm_index, Err(BuildError::EmptyText)));
+}
+
+#[test]
+fn empty_alphabet_is_error() {
+    assert!(matches!(build(b"ACGT", &[]), Err(BuildError::EmptyAlphabet)));
+}
+
+#[test]
+fn too_many_character_groups_is_error() {
+    let characters: Vec<[u8; 1]> = (0..=u8::MAX).map(|chr| [chr]).collect();
+    let characters_by_index: Vec<&[u8]> = characters.iter().map(|chr| &chr[..]).collect();
+    assert!(build(b"ACGT", &characters_by_index[..254]).is_ok());
+    assert!(matches!(build(b"ACGT", &characters_by_index[..255]), Err(BuildError::TooManyCharacterGroups(255))));
+    assert!(matches!(build(b"ACGT", &characters_by_index), Err(BuildError::TooManyCharacterGroups(256))));
+}
+
+#[test]
+fn duplicate_character_is_error() {
+    // Overlapping groups
+    assert!(matches!(build(b"ACGT", &[b"Aa", b"Ca", b"G"]), Err(BuildError::DuplicateCharacter('a'))));
+    assert!(matches!(build(b"ACGT", &[b"A", b"C", b"A"]), Err(BuildError::DuplicateCharacter('A'))));
+    // Repeated in a group
+    assert!(matches!(build(b"ACGT", &[b"AA", b"C"]), Err(BuildError::DuplicateCharacter('A'))));
+    let rl_fm_index = RlFmIndex::<u32>::build(b"ACGT".to_vec(), &[b"A", b"A"], 2);
+    assert!(matches!(rl_fm_index, Err(BuildError::DuplicateCharacter('A'))));
+}
+
+#[test]
+fn text_too_long_for_position_is_error() {
+    assert!(check_text_len::<u32>(u32::MAX as usize).is_ok());
+    assert!(matches!(
+        check_text_len::<u32>(u32::MAX as usize + 1),
+        Err(BuildError::TextTooLong(length, 32)) if length == u32::MAX as u64 + 1,
+    ));
+    assert!(check_text_len::<u64>(usize::MAX).is_ok());
+}
+
+#[test]
+fn position_over_u32_is_split_into_block() {
+    // The texts longer than `u32::MAX` are accepted for the `u64` position
+    let pos = u32::MAX as u64 + 3;
+    assert!(check_text_len::<u64>(pos as usize).is_ok());
+    assert_eq!(<Block2<u64> as Block<u64>>::split_position(pos), ((pos / 64) as usize, 2));
+    assert_eq!(<Block4<u32> as Block<u64>>::split_position(pos), ((pos / 32) as usize, 2));
+}
@@ -0,0 +1,190 @@
+use crate::{RlFmIndex, ClassPattern, Position, BwmBackend, BuildOptions, UnindexedCharacters, QueryError};
+use crate::blocks::{Block2, Block3, Block5};
+use crate::backends::WaveletTree;
+use crate::tests::random_data::{
+    gen_rand_pattern,
+    option_list,
+    build_index,
+};
+use rand::Rng;
+
+fn assert_empty_pattern_matches_everywhere<P: Position, B: BwmBackend<P>>(options: &BuildOptions) {
+    let (_, text, index) = build_index::<P, B>(options);
+    let all_positions: Vec<u64> = (0..text.len() as u64).collect();
+    assert_eq!(index.count(b"").as_u64(), text.len() as u64);
+    let mut locations: Vec<u64> = index.locate(b"").into_iter().map(Position::as_u64).collect();
+    locations.sort();
+    assert_eq!(locations, all_positions);
+    let mut locations: Vec<u64> = index.locate_from_raw_index(std::iter::empty()).into_iter().map(Position::as_u64).collect();
+    locations.sort();
+    assert_eq!(locations, all_positions);
+    assert_eq!(index.count_class_pattern(&ClassPattern::from_chridx_sets(Vec::<Vec<u8>>::new())).as_u64(), text.len() as u64);
+}
+
+#[test]
+fn empty_pattern_matches_every_position() {
+    for options in option_list().iter() {
+        assert_empty_pattern_matches_everywhere::<u32, Block2<u64>>(options);
+        assert_empty_pattern_matches_everywhere::<u64, Block3<u32>>(options);
+        assert_empty_pattern_matches_everywhere::<u32, Block5<u128>>(options);
+        assert_empty_pattern_matches_everywhere::<u64, WaveletTree>(options);
+    }
+}
+
+fn assert_checked_is_same_as_unchecked<P: Position, B: BwmBackend<P>>(options: &BuildOptions) {
+    let (_, text, index) = build_index::<P, B>(options);
+    for _ in 0..50 {
+        let pattern = gen_rand_pattern(&text, 1, 10);
+        let raw_index_rev_iter = || pattern.iter().rev().map(|&chr| index.decoding_table()[chr as usize]);
+        assert_eq!(
+            index.try_count_from_raw_index(raw_index_rev_iter()),
+            Ok(index.count_from_raw_index(raw_index_rev_iter())),
+        );
+        assert_eq!(
+            index.try_locate_from_raw_index(raw_index_rev_iter()),
+            Ok(index.locate_from_raw_index(raw_index_rev_iter())),
+        );
+    }
+}
+
+#[test]
+fn checked_queries_are_same_as_unchecked() {
+    for options in option_list().iter() {
+        assert_checked_is_same_as_unchecked::<u32, Block2<u64>>(options);
+        assert_checked_is_same_as_unchecked::<u64, Block3<u32>>(options);
+        assert_checked_is_same_as_unchecked::<u32, WaveletTree>(options);
+    }
+}
+
+fn assert_random_raw_index_never_panics<P: Position, B: BwmBackend<P>>(options: &BuildOptions) {
+    let (chr_list, _, index) = build_index::<P, B>(options);
+    let mut rng = rand::rng();
+    for _ in 0..500 {
+        let raw_index_len = rng.random_range(0..12);
+        // Mostly valid, sometimes out of range
+        let raw_index_rev: Vec<u8> = (0..raw_index_len).map(|_| {
+            if rng.random_bool(0.9) { rng.random_range(0..4) } else { rng.random() }
+        }).collect();
+        let result = index.try_locate_from_raw_index(raw_index_rev.iter().copied());
+        match raw_index_rev.iter().find(|&&chridx| chridx >= 4) {
+            Some(&chridx) => assert_eq!(result, Err(QueryError::ChrIdxOutOfRange(chridx, 4))),
+            None => {
+                // Same as the pattern of the characters of the raw index
+                let pattern: Vec<u8> = raw_index_rev.iter().rev().map(|&chridx| chr_list[chridx as usize]).collect();
+                let mut locations = result.unwrap();
+                let mut expected = index.locate(&pattern);
+                locations.sort();
+                expected.sort();
+                assert_eq!(locations, expected);
+            },
+        }
+    }
+}
+
+fn assert_try_query_checks_characters<P: Position, B: BwmBackend<P>>(options: &BuildOptions) {
+    let (chr_list, text, index) = build_index::<P, B>(options);
+    for _ in 0..50 {
+        let pattern = gen_rand_pattern(&text, 0, 10);
+        // The last character of the list is not indexed
+        match pattern.iter().find(|&&chr| chr == chr_list[3]) {
+            Some(&chr) => {
+                assert_eq!(index.try_count(&pattern), Err(QueryError::UnindexedCharacter(chr as char)));
+                assert_eq!(index.try_locate(&pattern), Err(QueryError::UnindexedCharacter(chr as char)));
+            },
+            None => {
+                assert_eq!(index.try_count(&pattern), Ok(index.count(&pattern)));
+                assert_eq!(index.try_locate(&pattern), Ok(index.locate(&pattern)));
+            },
+        }
+    }
+    // The bytes out of the text are not indexed either
+    let unknown = (0..=u8::MAX).find(|chr| !chr_list.contains(chr)).unwrap();
+    let pattern = [chr_list[0], unknown];
+    assert_eq!(index.try_count(&pattern), Err(QueryError::UnindexedCharacter(unknown as char)));
+    assert_eq!(index.try_count(b""), Ok(index.count(b"")));
+}
+
+#[test]
+fn try_queries_return_error_for_unindexed_character() {
+    for options in option_list().iter() {
+        assert_try_query_checks_characters::<u32, Block2<u64>>(options);
+        assert_try_query_checks_characters::<u64, Block3<u32>>(options);
+        assert_try_query_checks_characters::<u32, WaveletTree>(options);
+    }
+}
+
+#[test]
+fn random_raw_index_never_panics() {
+    for options in option_list().iter() {
+        assert_random_raw_index_never_panics::<u32, Block2<u64>>(options);
+        assert_random_raw_index_never_panics::<u64, Block3<u32>>(options);
+        assert_random_raw_index_never_panics::<u32, WaveletTree>(options);
+    }
+}
+
+fn assert_random_pattern_of_rl_fm_index_never_panics<P: Position>(options: &BuildOptions) {
+    let (chr_list, text, lt_fm_index) = build_index::<P, Block2<u64>>(options);
+    let characters_by_index = chr_list[..3].chunks(1).collect::<Vec<_>>();
+    let rl_fm_index = RlFmIndex::<P>::build_with_options(text, &characters_by_index, 3, options).unwrap();
+    let mut rng = rand::rng();
+    for _ in 0..500 {
+        let pattern_len = rng.random_range(0..12);
+        // Mostly the characters of the text, sometimes any byte
+        let pattern: Vec<u8> = (0..pattern_len).map(|_| {
+            if rng.random_bool(0.9) { chr_list[rng.random_range(0..4)] } else { rng.random() }
+        }).collect();
+        let mut locations = rl_fm_index.locate(&pattern);
+        let mut expected = lt_fm_index.locate(&pattern);
+        locations.sort();
+        expected.sort();
+        assert_eq!(locations, expected);
+        assert_eq!(rl_fm_index.count(&pattern), lt_fm_index.count(&pattern));
+    }
+}
+
+#[test]
+fn random_pattern_of_rl_fm_index_never_panics() {
+    for options in option_list().iter() {
+        assert_random_pattern_of_rl_fm_index_never_panics::<u32>(options);
+        assert_random_pattern_of_rl_fm_index_never_panics::<u64>(options);
+    }
+}
+
+fn assert_random_class_pattern_never_panics<P: Position, B: BwmBackend<P>>(options: &BuildOptions) {
+    let (_, text, index) = build_index::<P, B>(options);
+    // The unindexed characters are of the last index
+    let is_separator = |chridx: u8| index.unindexed_characters() == UnindexedCharacters::Separator && chridx == 3;
+    let text_chridx: Vec<u8> = text.iter().map(|&chr| index.decoding_table()[chr as usize]).collect();
+    let mut rng = rand::rng();
+    for _ in 0..200 {
+        let pattern_len = rng.random_range(0..6);
+        // The sets can be empty, or have the indices out of the characters
+        let sets: Vec<Vec<u8>> = (0..pattern_len).map(|_| {
+            let set_size = rng.random_range(0..4);
+            (0..set_size).map(|_| if rng.random_bool(0.9) { rng.random_range(0..4) } else { rng.random() }).collect()
+        }).collect();
+        let class_pattern = ClassPattern::from_chridx_sets(sets.clone());
+        let expected: Vec<u64> = if sets.is_empty() {
+            (0..text.len() as u64).collect()
+        } else {
+            text_chridx.windows(sets.len()).enumerate().filter_map(|(location, window)| {
+                let matched = window.iter().zip(sets.iter())
+                    .all(|(&chridx, set)| set.contains(&chridx) && !is_separator(chridx));
+                matched.then_some(location as u64)
+            }).collect()
+        };
+        let mut locations: Vec<u64> = index.locate_class_pattern(&class_pattern).into_iter().map(Position::as_u64).collect();
+        locations.sort();
+        assert_eq!(locations, expected);
+        assert_eq!(index.count_class_pattern(&class_pattern).as_u64(), expected.len() as u64);
+    }
+}
+
+#[test]
+fn random_class_pattern_never_panics() {
+    for options in option_list().iter() {
+        assert_random_class_pattern_never_panics::<u32, Block2<u64>>(options);
+        assert_random_class_pattern_never_panics::<u64, Block3<u32>>(options);
+        assert_random_class_pattern_never_panics::<u32, WaveletTree>(options);
+    }
+}
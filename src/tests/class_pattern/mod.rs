@@ -0,0 +1,87 @@
+use crate::{LtFmIndex, ClassPattern, BuildOptions, UnindexedCharacters};
+use crate::blocks::{Block3, Block5};
+use crate::tests::random_data::{
+    gen_rand_chr_list,
+    gen_rand_text,
+};
+use rand::Rng;
+
+fn naive_locations(text: &[u8], sets: &[Vec<u8>]) -> Vec<u32> {
+    text.windows(sets.len()).enumerate().filter_map(|(location, window)| {
+        let matched = window.iter().zip(sets.iter()).all(|(chr, set)| set.contains(chr));
+        matched.then_some(location as u32)
+    }).collect()
+}
+
+#[test]
+fn iupac_pattern_result_is_accurate() {
+    let mut rng = rand::rng();
+    let characters_by_index: &[&[u8]] = &[b"A", b"C", b"G", b"T"];
+    let codes = b"ACGTURYSWKMBDHVNacgtn";
+    for _ in 0..3 {
+        let text = gen_rand_text(b"ACGT", 1000, 2000);
+        let lt_fm_index = LtFmIndex::<u32, Block3<u64>>::build(
+            text.clone(), characters_by_index, 2, 3,
+        ).unwrap();
+        for _ in 0..100 {
+            let pattern_len = rng.random_range(1..10);
+            let pattern: Vec<u8> = (0..pattern_len).map(|_| codes[rng.random_range(0..codes.len())]).collect();
+            let sets: Vec<Vec<u8>> = pattern.iter().map(|code| {
+                let bases: &[u8] = match code.to_ascii_uppercase() {
+                    b'A' => b"A", b'C' => b"C", b'G' => b"G", b'T' | b'U' => b"T",
+                    b'R' => b"AG", b'Y' => b"CT", b'S' => b"CG", b'W' => b"AT",
+                    b'K' => b"GT", b'M' => b"AC", b'B' => b"CGT", b'D' => b"AGT",
+                    b'H' => b"ACT", b'V' => b"ACG", _ => b"ACGT",
+                };
+                bases.to_vec()
+            }).collect();
+            let answer = naive_locations(&text, &sets);
+
+            let class_pattern = ClassPattern::iupac(&pattern, lt_fm_index.decoding_table());
+            let mut result = lt_fm_index.locate_class_pattern(&class_pattern);
+            result.sort();
+            assert_eq!(result, answer);
+            assert_eq!(lt_fm_index.count_class_pattern(&class_pattern), answer.len() as u32);
+        }
+    }
+}
+
+#[test]
+fn character_sets_pattern_result_is_accurate() {
+    let mut rng = rand::rng();
+    let chr_list = gen_rand_chr_list(16);
+    let characters_by_index = chr_list.chunks(1).collect::<Vec<_>>();
+    let text = gen_rand_text(&chr_list, 1000, 2000);
+    let lt_fm_index = LtFmIndex::<u64, Block5<u64>>::build(
+        text.clone(), &characters_by_index, 3, 2,
+    ).unwrap();
+    for _ in 0..100 {
+        let pattern_len = rng.random_range(1..6);
+        let sets: Vec<Vec<u8>> = (0..pattern_len).map(|_| {
+            let set_size = rng.random_range(1..5);
+            (0..set_size).map(|_| chr_list[rng.random_range(0..chr_list.len())]).collect()
+        }).collect();
+        let answer = naive_locations(&text, &sets);
+
+        let class_pattern = ClassPattern::from_character_sets(&sets, lt_fm_index.decoding_table());
+        let mut result: Vec<u32> = lt_fm_index.locate_class_pattern(&class_pattern)
+            .into_iter().map(|x| x as u32).collect();
+        result.sort();
+        assert_eq!(result, answer);
+    }
+}
+
+#[test]
+fn class_pattern_does_not_match_separator() {
+    let text = b"ACGTNNACGTAAGT".to_vec();
+    let characters_by_index: &[&[u8]] = &[b"A", b"C", b"G", b"T"];
+    let options = BuildOptions::new().unindexed_characters(UnindexedCharacters::Separator);
+    let lt_fm_index = LtFmIndex::<u32, Block3<u64>>::build_with_options(
+        text, characters_by_index, 2, 3, &options,
+    ).unwrap();
+    // `X` is the unindexed character
+    let class_pattern = ClassPattern::from_character_sets(&[b"T".to_vec(), b"AX".to_vec()], lt_fm_index.decoding_table());
+    assert_eq!(lt_fm_index.count_class_pattern(&class_pattern), 1);
+    // The empty pattern occurs at every position
+    assert_eq!(lt_fm_index.count_class_pattern(&ClassPattern::from_chridx_sets(Vec::<Vec<u8>>::new())), 14);
+}
@@ -0,0 +1,72 @@
+use crate::{LtFmIndex, DynamicLtFmIndex, BuildOptions, UnindexedCharacters, BuildError};
+use crate::blocks::Block3;
+use crate::tests::random_data::{
+    gen_rand_text,
+    gen_rand_pattern,
+};
+
+fn assert_same_as_fresh_build(
+    dynamic_index: &DynamicLtFmIndex<u32, Block3<u64>>,
+    text: &[u8],
+    options: &BuildOptions,
+) {
+    let characters_by_index: &[&[u8]] = &[b"A", b"C", b"G", b"T"];
+    let lt_fm_index = LtFmIndex::<u32, Block3<u64>>::build_with_options(
+        text.to_vec(), characters_by_index, 2, 3, options,
+    ).unwrap();
+    for _ in 0..100 {
+        let pattern = gen_rand_pattern(text, 1, 30.min(text.len() - 1));
+        let mut answer = lt_fm_index.locate(&pattern);
+        answer.sort();
+        let mut result = dynamic_index.locate(&pattern);
+        result.sort();
+        assert_eq!(result, answer);
+        assert_eq!(dynamic_index.count(&pattern), lt_fm_index.count(&pattern));
+    }
+}
+
+#[test]
+fn dynamic_index_is_same_as_fresh_build() {
+    let characters_by_index: &[&[u8]] = &[b"A", b"C", b"G", b"T"];
+    for unindexed_characters in [UnindexedCharacters::Same, UnindexedCharacters::Separator] {
+        let options = BuildOptions::new().unindexed_characters(unindexed_characters);
+        let mut dynamic_index = DynamicLtFmIndex::<u32, Block3<u64>>::new(
+            characters_by_index, 2, 3, options.clone(),
+        ).unwrap();
+        let mut text = Vec::new();
+        for _ in 0..20 {
+            // Short texts to have the patterns across many segments
+            let inserted = gen_rand_text(b"ACGTN", 5, 40);
+            dynamic_index.insert_text(&inserted).unwrap();
+            text.extend(inserted);
+            assert!(dynamic_index.segment_count() <= 2 * (text.len().ilog2() as usize + 1));
+            assert_same_as_fresh_build(&dynamic_index, &text, &options);
+        }
+        dynamic_index.compact().unwrap();
+        assert_eq!(dynamic_index.segment_count(), 1);
+        assert_eq!(dynamic_index.len_text(), text.len() as u32);
+        assert_same_as_fresh_build(&dynamic_index, &text, &options);
+    }
+}
+
+#[test]
+fn invalid_arguments_are_checked_by_new() {
+    let new = |characters_by_index: &[&[u8]], suffix_array_sampling_ratio, lookup_table_kmer_size| {
+        DynamicLtFmIndex::<u32, Block3<u64>>::new(
+            characters_by_index, suffix_array_sampling_ratio, lookup_table_kmer_size, BuildOptions::default(),
+        )
+    };
+    assert!(matches!(new(&[b"A", b"C", b"G", b"T"], 2, 0), Err(BuildError::LookupTableKmerSize)));
+    assert!(matches!(new(&[b"A", b"C", b"G", b"T"], 0, 3), Err(BuildError::SuffixArraySamplingRatio)));
+    assert!(matches!(new(&[], 2, 3), Err(BuildError::EmptyAlphabet)));
+    assert!(matches!(new(&[b"AC", b"CG"], 2, 3), Err(BuildError::DuplicateCharacter('C'))));
+    // Block3 indexes at most 7 characters
+    let characters_by_index: Vec<[u8; 1]> = (b'A'..=b'H').map(|chr| [chr]).collect();
+    let characters_by_index: Vec<&[u8]> = characters_by_index.iter().map(|chr| &chr[..]).collect();
+    assert!(matches!(new(&characters_by_index, 2, 3), Err(BuildError::IndexCountOver(7, 9))));
+
+    let mut dynamic_index = new(&[b"A", b"C", b"G", b"T"], 2, 3).unwrap();
+    dynamic_index.insert_text(b"").unwrap();
+    assert_eq!(dynamic_index.len_text(), 0);
+    assert_eq!(dynamic_index.count(b"A"), 0);
+}
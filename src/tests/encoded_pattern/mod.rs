@@ -0,0 +1,129 @@
+use crate::{LtFmIndex, RlFmIndex, DynamicLtFmIndex, Position, BwmBackend, BuildOptions, EncodedPattern, QueryError};
+use crate::blocks::{Block2, Block3, Block5};
+use crate::backends::WaveletTree;
+use crate::tests::random_data::{
+    gen_rand_chr_list,
+    gen_rand_text,
+    gen_rand_pattern,
+    option_list,
+    build_index,
+};
+
+fn sorted<P: Position>(mut locations: Vec<P>) -> Vec<P> {
+    locations.sort();
+    locations
+}
+
+fn assert_encoded_pattern_is_same_as_pattern<P: Position, B: BwmBackend<P>>(options: &BuildOptions) {
+    let (_, text, index) = build_index::<P, B>(options);
+    for _ in 0..50 {
+        let pattern = gen_rand_pattern(&text, 0, 10);
+        let encoded = index.encode_pattern(&pattern);
+        assert_eq!(encoded.len(), pattern.len());
+        assert_eq!(index.count_encoded_pattern(&encoded), index.count(&pattern));
+        assert_eq!(index.count_from_raw_index(encoded.raw_index_rev_iter()), index.count(&pattern));
+        assert_eq!(index.try_count_from_raw_index(encoded.raw_index_rev_iter()), Ok(index.count(&pattern)));
+        assert_eq!(sorted(index.locate_encoded_pattern(&encoded)), sorted(index.locate(&pattern)));
+        assert_eq!(index.try_count_encoded_pattern(&encoded), Ok(index.count(&pattern)));
+        assert_eq!(
+            index.try_locate_encoded_pattern(&encoded).map(sorted),
+            Ok(sorted(index.locate(&pattern))),
+        );
+    }
+}
+
+#[test]
+fn encoded_pattern_is_same_as_pattern() {
+    for options in option_list().iter() {
+        assert_encoded_pattern_is_same_as_pattern::<u32, Block2<u64>>(options);
+        assert_encoded_pattern_is_same_as_pattern::<u64, Block3<u32>>(options);
+        assert_encoded_pattern_is_same_as_pattern::<u32, Block5<u128>>(options);
+        assert_encoded_pattern_is_same_as_pattern::<u64, WaveletTree>(options);
+    }
+}
+
+#[test]
+fn encoded_pattern_is_reused_across_indexes() {
+    let chr_list = gen_rand_chr_list(4);
+    let characters_by_index = chr_list.chunks(1).collect::<Vec<_>>();
+    let texts: Vec<Vec<u8>> = (0..3).map(|_| gen_rand_text(&chr_list, 100, 300)).collect();
+    let block_shards: Vec<LtFmIndex<u32, Block3<u64>>> = texts.iter().map(|text| {
+        LtFmIndex::build(text.clone(), &characters_by_index, 2, 3).unwrap()
+    }).collect();
+    let wavelet_shards: Vec<LtFmIndex<u64, WaveletTree>> = texts.iter().map(|text| {
+        LtFmIndex::build(text.clone(), &characters_by_index, 4, 2).unwrap()
+    }).collect();
+    for _ in 0..50 {
+        let pattern = gen_rand_pattern(&texts[0], 1, 8);
+        let encoded = EncodedPattern::new(&pattern, block_shards[0].decoding_table());
+        block_shards.iter().zip(&wavelet_shards).for_each(|(block_shard, wavelet_shard)| {
+            assert_eq!(block_shard.try_count_encoded_pattern(&encoded), Ok(block_shard.count(&pattern)));
+            assert_eq!(wavelet_shard.try_count_encoded_pattern(&encoded), Ok(wavelet_shard.count(&pattern)));
+        });
+    }
+}
+
+#[test]
+fn encoded_pattern_of_other_alphabet_is_rejected() {
+    let text = b"ACGTACGTTTGCA".to_vec();
+    let index = LtFmIndex::<u32, Block3<u64>>::build(text.clone(), &[b"A", b"C", b"G", b"T"], 2, 3).unwrap();
+    let other_index = LtFmIndex::<u32, Block3<u64>>::build(text, &[b"T", b"G", b"C", b"A"], 2, 3).unwrap();
+    let encoded = other_index.encode_pattern(b"ACG");
+    assert!(!encoded.is_encoded_with(index.decoding_table()));
+    assert_eq!(index.try_count_encoded_pattern(&encoded), Err(QueryError::IncompatibleAlphabet));
+    assert_eq!(index.try_locate_encoded_pattern(&encoded), Err(QueryError::IncompatibleAlphabet));
+    assert_eq!(other_index.try_count_encoded_pattern(&encoded), Ok(2));
+}
+
+#[test]
+fn encoded_pattern_of_other_indexes_is_same_as_pattern() {
+    for options in option_list().iter() {
+        let chr_list = gen_rand_chr_list(4);
+        let characters_by_index = chr_list[..3].chunks(1).collect::<Vec<_>>();
+        let texts: Vec<Vec<u8>> = (0..3).map(|_| gen_rand_text(&chr_list, 50, 100)).collect();
+        let whole_text = texts.concat();
+        let rl_fm_index = RlFmIndex::<u32>::build_with_options(whole_text.clone(), &characters_by_index, 3, options).unwrap();
+        let mut dynamic_index = DynamicLtFmIndex::<u32, Block2<u64>>::new(&characters_by_index, 3, 3, options.clone()).unwrap();
+        texts.iter().for_each(|text| dynamic_index.insert_text(text).unwrap());
+        for _ in 0..50 {
+            let pattern = gen_rand_pattern(&whole_text, 0, 10);
+            let encoded = rl_fm_index.encode_pattern(&pattern);
+            assert_eq!(rl_fm_index.try_count_encoded_pattern(&encoded), Ok(rl_fm_index.count(&pattern)));
+            assert_eq!(
+                rl_fm_index.try_locate_encoded_pattern(&encoded).map(sorted),
+                Ok(sorted(rl_fm_index.locate(&pattern))),
+            );
+            assert_eq!(dynamic_index.try_count_encoded_pattern(&encoded), Ok(dynamic_index.count(&pattern)));
+            assert_eq!(
+                dynamic_index.try_locate_encoded_pattern(&encoded).map(sorted),
+                Ok(sorted(dynamic_index.locate(&pattern))),
+            );
+        }
+    }
+}
+
+#[test]
+fn encoded_patterns_are_searched_in_batch() {
+    let chr_list = gen_rand_chr_list(4);
+    let characters_by_index = chr_list.chunks(1).collect::<Vec<_>>();
+    let text = gen_rand_text(&chr_list, 100, 300);
+    let index = LtFmIndex::<u32, Block3<u64>>::build(text.clone(), &characters_by_index, 2, 3).unwrap();
+    let patterns: Vec<Vec<u8>> = (0..20).map(|_| gen_rand_pattern(&text, 1, 8)).collect();
+    let encoded: Vec<EncodedPattern> = patterns.iter().map(|pattern| index.encode_pattern(pattern)).collect();
+    assert_eq!(
+        index.try_count_encoded_patterns(&encoded),
+        Ok(patterns.iter().map(|pattern| index.count(pattern)).collect()),
+    );
+    assert_eq!(
+        index.try_locate_encoded_patterns(&encoded).map(|locations| locations.into_iter().map(sorted).collect()),
+        Ok(patterns.iter().map(|pattern| sorted(index.locate(pattern))).collect::<Vec<_>>()),
+    );
+    // Rejected if any pattern is of the other characters
+    let mut other_characters_by_index = characters_by_index.clone();
+    other_characters_by_index.reverse();
+    let other_index = LtFmIndex::<u32, Block3<u64>>::build(text, &other_characters_by_index, 2, 3).unwrap();
+    let mut mixed = encoded.clone();
+    mixed.push(other_index.encode_pattern(&patterns[0]));
+    assert_eq!(index.try_count_encoded_patterns(&mixed), Err(QueryError::IncompatibleAlphabet));
+    assert_eq!(index.try_locate_encoded_patterns(&mixed), Err(QueryError::IncompatibleAlphabet));
+}
@@ -0,0 +1,108 @@
+use crate::{LtFmIndex, RlFmIndex, Position, BuildOptions, LookupTable, BuildError};
+use crate::blocks::{Block2, Block5};
+use crate::tests::random_data::{
+    gen_rand_chr_list,
+    gen_rand_text,
+    gen_rand_pattern,
+};
+use std::io::Cursor;
+
+fn assert_sparse_is_same_as_dense<P: Position>(
+    chr_list: &[u8],
+    text: Vec<u8>,
+    patterns: &[Vec<u8>],
+    dense_ltks: u32,
+    sparse_ltks: u32,
+) {
+    let characters_by_index = chr_list.chunks(1).collect::<Vec<_>>();
+    let sparse_options = BuildOptions::new().lookup_table(LookupTable::Sparse);
+    let dense = LtFmIndex::<P, Block5<u64>>::build(
+        text.clone(),
+        &characters_by_index,
+        P::from_u64(2),
+        dense_ltks,
+    ).unwrap();
+    let sparse = LtFmIndex::<P, Block5<u64>>::build_with_options(
+        text.clone(),
+        &characters_by_index,
+        P::from_u64(2),
+        sparse_ltks,
+        &sparse_options,
+    ).unwrap();
+    let rl_sparse = RlFmIndex::<P>::build_with_options(
+        text,
+        &characters_by_index,
+        sparse_ltks,
+        &sparse_options,
+    ).unwrap();
+    assert_eq!(sparse.lookup_table(), LookupTable::Sparse);
+
+    for pattern in patterns {
+        let mut answer = dense.locate(pattern);
+        answer.sort();
+        let mut result = sparse.locate(pattern);
+        result.sort();
+        assert_eq!(result, answer);
+        let mut result = rl_sparse.locate(pattern);
+        result.sort();
+        assert_eq!(result, answer);
+        assert_eq!(sparse.count(pattern), dense.count(pattern));
+
+        let raw_index_rev_iter = || pattern.iter().rev().map(|chr| sparse.decoding_table()[*chr as usize]);
+        let mut result = sparse.locate_from_raw_index(raw_index_rev_iter());
+        result.sort();
+        assert_eq!(result, answer);
+    }
+
+    let mut buffer = Vec::new();
+    sparse.save_to(&mut buffer).unwrap();
+    assert_eq!(sparse.encoded_len(), buffer.len());
+    let loaded = LtFmIndex::<P, Block5<u64>>::load_from(Cursor::new(buffer)).unwrap();
+    assert_eq!(sparse, loaded);
+
+    let mut buffer = Vec::new();
+    rl_sparse.save_to(&mut buffer).unwrap();
+    assert_eq!(rl_sparse.encoded_len(), buffer.len());
+    let loaded = RlFmIndex::<P>::load_from(Cursor::new(buffer)).unwrap();
+    assert_eq!(rl_sparse, loaded);
+}
+
+#[test]
+fn sparse_lookup_table_is_same_as_dense() {
+    let chr_counts = [4, 20];
+    let text_min_len = 500;
+    let text_max_len = 1000;
+    let n_text = 3;
+    let pattern_min_len = 1;
+    let pattern_max_len = 16;
+    let n_pattern = 100;
+
+    for chr_count in chr_counts {
+        // Large k-mer size, for which the dense table can not be made
+        let sparse_ltks = if chr_count == 4 { 12 } else { 6 };
+        for _ in 0..n_text {
+            let chr_list = gen_rand_chr_list(chr_count);
+            let text = gen_rand_text(&chr_list, text_min_len, text_max_len);
+            // Leave out one character to also test the unindexed characters
+            let indexed_chr_list = &chr_list[..chr_count - 1];
+            let patterns: Vec<Vec<u8>> = (0..n_pattern).map(|_| {
+                gen_rand_pattern(&text, pattern_min_len, pattern_max_len)
+            }).collect();
+            assert_sparse_is_same_as_dense::<u32>(indexed_chr_list, text.clone(), &patterns, 2, sparse_ltks);
+            assert_sparse_is_same_as_dense::<u64>(indexed_chr_list, text, &patterns, 2, 2);
+        }
+    }
+}
+
+#[test]
+fn too_large_kmer_size_is_error() {
+    let options = BuildOptions::new().lookup_table(LookupTable::Sparse);
+    let result = LtFmIndex::<u32, Block2<u64>>::build_with_options(
+        b"ACGT".to_vec(),
+        &[b"A", b"C", b"G"],
+        1,
+        64,
+        &options,
+    );
+    assert!(matches!(result, Err(BuildError::LookupTableOverflow(64))));
+}
@@ -0,0 +1,80 @@
+use crate::{LtFmIndex, Position, BwmBackend, BuildOptions, BuildError, LookupTable, SuffixArraySampling, UnindexedCharacters};
+use crate::blocks::{Block2, Block4};
+use crate::backends::WaveletTree;
+use crate::tests::random_data::{
+    gen_rand_chr_list,
+    gen_rand_text,
+};
+
+fn assert_merged_is_same_as_built<P: Position, B: BwmBackend<P> + PartialEq>(
+    chr_list: &[u8],
+    text_a: Vec<u8>,
+    text_b: Vec<u8>,
+    options: &BuildOptions,
+) {
+    let characters_by_index = chr_list.chunks(1).collect::<Vec<_>>();
+    let build = |text: Vec<u8>| LtFmIndex::<P, B>::build_with_options(
+        text,
+        &characters_by_index,
+        P::from_u64(3),
+        3,
+        options,
+    ).unwrap();
+    let concatenated: Vec<u8> = text_a.iter().chain(text_b.iter()).copied().collect();
+    let merged = LtFmIndex::merge(&build(text_a), &build(text_b)).unwrap();
+    assert_eq!(merged, build(concatenated));
+}
+
+#[test]
+fn merged_index_is_same_as_built_on_concatenation() {
+    let option_list = [
+        BuildOptions::new(),
+        BuildOptions::new().lookup_table(LookupTable::Sparse),
+        BuildOptions::new().suffix_array_sampling(SuffixArraySampling::TextPosition),
+        BuildOptions::new().unindexed_characters(UnindexedCharacters::Separator),
+    ];
+    for options in option_list.iter() {
+        for _ in 0..10 {
+            let chr_list = gen_rand_chr_list(4);
+            let indexed_chr_list = &chr_list[..3];
+            let text_a = gen_rand_text(&chr_list, 50, 300);
+            let text_b = gen_rand_text(&chr_list, 50, 300);
+            assert_merged_is_same_as_built::<u32, Block2<u64>>(indexed_chr_list, text_a.clone(), text_b.clone(), options);
+            assert_merged_is_same_as_built::<u64, Block4<u32>>(indexed_chr_list, text_a.clone(), text_b.clone(), options);
+            assert_merged_is_same_as_built::<u32, WaveletTree>(indexed_chr_list, text_a, text_b, options);
+        }
+    }
+}
+
+#[test]
+fn merge_repetitive_texts() {
+    // The suffixes of `a` being the prefix of each other
+    let chr_list = b"AC";
+    let option_list = [
+        BuildOptions::new(),
+        BuildOptions::new().lookup_table(LookupTable::Sparse),
+        BuildOptions::new().suffix_array_sampling(SuffixArraySampling::TextPosition),
+    ];
+    for options in option_list.iter() {
+        for (text_a, text_b) in [
+            (b"AAAAAAAAAA".to_vec(), b"AAAA".to_vec()),
+            (b"AAAAAAAAAA".to_vec(), b"C".to_vec()),
+            (b"ACACACACAC".to_vec(), b"ACA".to_vec()),
+            (b"CACACAC".to_vec(), b"A".to_vec()),
+            (b"A".to_vec(), b"AAAAAAA".to_vec()),
+            (b"A".to_vec(), b"C".to_vec()),
+            (b"CA".to_vec(), b"A".to_vec()),
+            (vec![b'A'; 5000], vec![b'A'; 3000]),
+        ] {
+            assert_merged_is_same_as_built::<u32, Block2<u64>>(chr_list, text_a, text_b, options);
+        }
+    }
+}
+
+#[test]
+fn merging_incompatible_indexes_is_error() {
+    let text = b"ACGTACGGA".to_vec();
+    let a = LtFmIndex::<u32, Block2<u64>>::build(text.clone(), &[b"A", b"C", b"G"], 2, 3).unwrap();
+    let b = LtFmIndex::<u32, Block2<u64>>::build(text, &[b"A", b"C", b"G"], 4, 3).unwrap();
+    assert!(matches!(LtFmIndex::merge(&a, &b), Err(BuildError::IncompatibleIndexes)));
+}
@@ -4,5 +4,32 @@ pub mod result_answer;
 mod readme;
 mod accurate_result;
 mod save_and_load;
+mod backends;
+mod simd_rank;
+mod rl_fm_index;
+mod lookup_table;
+mod unindexed_characters;
+mod alphabet;
+mod class_pattern;
+mod suffix_array_sampling;
+mod dynamic_index;
+mod any_index;
+mod symbol_index;
+mod merge;
+mod build_from_stream;
+mod build_progress;
+mod build_validation;
+mod checked_query;
+mod encoded_pattern;
+#[cfg(any(feature = "serde", feature = "rkyv"))]
+mod serde_and_rkyv;
+#[cfg(feature = "bio-formats")]
+mod bio_formats;
+#[cfg(feature = "server")]
+mod server;
+#[cfg(feature = "python")]
+mod python;
+#[cfg(feature = "wasm")]
+mod wasm;
 #[cfg(feature = "async-io")]
-mod async_save_and_load;
\ No newline at end of file
+mod async_save_and_load;
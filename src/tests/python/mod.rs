@@ -0,0 +1,92 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+
+use crate::python::python_module;
+
+// Runs the script with the module imported as `lt_fm_index`
+fn run_script(script: &std::ffi::CStr) {
+    Python::initialize();
+    Python::attach(|py| {
+        let module = pyo3::wrap_pymodule!(python_module)(py);
+        py.import("sys").unwrap().getattr("modules").unwrap().set_item("lt_fm_index", module).unwrap();
+        let path = std::env::temp_dir().join(format!("lt-fm-index-python-test-{}", std::process::id()));
+        let globals = PyDict::new(py);
+        globals.set_item("path", path.to_str().unwrap()).unwrap();
+        let result = py.run(script, Some(&globals), None);
+        let _ = std::fs::remove_file(&path);
+        if let Err(err) = result {
+            err.print(py);
+            panic!("Python script failed");
+        }
+    });
+}
+
+#[test]
+fn build_and_query() {
+    run_script(cr#"
+from lt_fm_index import LtFmIndex
+
+text = b"CTCCGTACACCTGTTTCGTATCGGAXXYYZZ"
+index = LtFmIndex(text, characters=[b"Aa", "Cc", b"Gg"], sampling_ratio=2, kmer_size=4)
+assert (index.position, index.backend) == ("u32", "block2")
+assert len(index) == len(text)
+assert index.count(b"TA") == 2
+assert index.count("UNDEF") == 2
+assert index.locate(b"TA") == [5, 18]
+assert index.locate("ACGT") == []
+locations = index.locate_array(b"TA")
+assert locations.typecode == "Q" and list(locations) == [5, 18]
+assert memoryview(locations).format == "Q"
+
+for position in ["u32", "u64"]:
+    for backend in ["block3", "block4", "block5", "block6", "wavelet"]:
+        index = LtFmIndex(text, alphabet="dna", position=position, backend=backend, sampling_ratio=3)
+        assert (index.position, index.backend) == (position, backend)
+        assert sorted(index.locate(b"CG")) == [i for i in range(len(text)) if text.startswith(b"CG", i)]
+"#);
+}
+
+#[test]
+fn default_types_by_alphabet() {
+    run_script(cr#"
+from lt_fm_index import LtFmIndex
+
+assert LtFmIndex(b"ACGU", alphabet="rna").backend == "block3"
+assert LtFmIndex(b"MKVL", alphabet="protein").backend == "block5"
+"#);
+}
+
+#[test]
+fn save_and_load() {
+    run_script(cr#"
+from lt_fm_index import LtFmIndex
+
+text = "acgtacgtnnACGTACGT"
+index = LtFmIndex(text, alphabet="dna-n", position="u64", backend="wavelet")
+index.save(path)
+loaded = LtFmIndex.load(path)
+assert (loaded.position, loaded.backend) == ("u64", "wavelet")
+assert loaded.locate(b"ACGT") == index.locate(b"ACGT") == [0, 4, 10, 14]
+"#);
+}
+
+#[test]
+fn invalid_arguments() {
+    run_script(cr#"
+from lt_fm_index import LtFmIndex
+
+def raises(error, function):
+    try:
+        function()
+    except error:
+        return
+    raise AssertionError("not raised")
+
+raises(ValueError, lambda: LtFmIndex(b"ACGT", alphabet="unknown"))
+raises(ValueError, lambda: LtFmIndex(b"ACGT", backend="block7"))
+raises(ValueError, lambda: LtFmIndex(b"ACGT", sampling_ratio=0))
+raises(TypeError, lambda: LtFmIndex([1, 2, 3]))
+raises(TypeError, lambda: LtFmIndex(b"ACGT").count(3))
+raises(OSError, lambda: LtFmIndex.load("/nonexistent/lt_fm_index"))
+"#);
+}
@@ -1,5 +1,6 @@
 use std::ops::Range;
 use rand::{Rng, seq::SliceRandom};
+use crate::{LtFmIndex, Position, BwmBackend, BuildOptions, LookupTable, SuffixArraySampling, UnindexedCharacters};
 
 const ASCII_RANGE: Range<u8> = 33..127;
 
@@ -35,3 +36,21 @@ pub fn gen_rand_pattern(text: &[u8], min_len: usize, max_len: usize) -> Vec<u8>
     let end = start + pattern_len;
     text[start..end].to_vec()
 }
+
+// The default options, and each option changed from them
+pub fn option_list() -> [BuildOptions; 4] {
+    [
+        BuildOptions::new(),
+        BuildOptions::new().lookup_table(LookupTable::Sparse),
+        BuildOptions::new().suffix_array_sampling(SuffixArraySampling::TextPosition),
+        BuildOptions::new().unindexed_characters(UnindexedCharacters::Separator),
+    ]
+}
+// The index of the random text with 4 characters, 3 of them indexed. Returns the characters, text, and index.
+pub fn build_index<P: Position, B: BwmBackend<P>>(options: &BuildOptions) -> (Vec<u8>, Vec<u8>, LtFmIndex<P, B>) {
+    let chr_list = gen_rand_chr_list(4);
+    let text = gen_rand_text(&chr_list, 100, 300);
+    let characters_by_index = chr_list[..3].chunks(1).collect::<Vec<_>>();
+    let index = LtFmIndex::build_with_options(text.clone(), &characters_by_index, P::from_u64(3), 3, options).unwrap();
+    (chr_list, text, index)
+}
@@ -1,5 +1,5 @@
 #[test]
-#[allow(unused_variables)]
+#[allow(unused_variables, clippy::byte_char_slices)]
 fn example() {
 
 use crate::LtFmIndex;
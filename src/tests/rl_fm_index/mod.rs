@@ -0,0 +1,97 @@
+use crate::{LtFmIndex, RlFmIndex, Position};
+use crate::blocks::Block4;
+use crate::tests::random_data::{
+    gen_rand_chr_list,
+    gen_rand_text,
+    gen_rand_pattern,
+};
+use rand::Rng;
+use std::io::Cursor;
+
+fn assert_same_with_lt_fm_index<P: Position>(
+    chr_list: &[u8],
+    text: Vec<u8>,
+    patterns: &[Vec<u8>],
+    ltks: u32,
+) -> RlFmIndex<P> {
+    let characters_by_index = chr_list.chunks(1).collect::<Vec<_>>();
+    let lt_fm_index = LtFmIndex::<P, Block4<u64>>::build(
+        text.clone(),
+        &characters_by_index,
+        P::ONE,
+        ltks,
+    ).unwrap();
+    let rl_fm_index = RlFmIndex::<P>::build(
+        text,
+        &characters_by_index,
+        ltks,
+    ).unwrap();
+    for pattern in patterns {
+        let mut answer = lt_fm_index.locate(pattern);
+        answer.sort();
+        let mut result = rl_fm_index.locate(pattern);
+        result.sort();
+        assert_eq!(result, answer);
+        assert_eq!(rl_fm_index.count(pattern), lt_fm_index.count(pattern));
+    }
+
+    let mut buffer = Vec::new();
+    rl_fm_index.save_to(&mut buffer).unwrap();
+    assert_eq!(rl_fm_index.encoded_len(), buffer.len());
+    let loaded = RlFmIndex::<P>::load_from(Cursor::new(buffer)).unwrap();
+    assert_eq!(rl_fm_index, loaded);
+    rl_fm_index
+}
+
+// Copies of a text with few mutations
+fn gen_repetitive_text(chr_list: &[u8], unit_len: usize, n_copy: usize) -> Vec<u8> {
+    let mut rng = rand::rng();
+    let unit = gen_rand_text(chr_list, unit_len, unit_len);
+    let mut text = Vec::with_capacity(unit_len * n_copy);
+    for _ in 0..n_copy {
+        let mut copied = unit.clone();
+        let mutated = rng.random_range(0..unit_len);
+        copied[mutated] = chr_list[rng.random_range(0..chr_list.len())];
+        text.extend(copied);
+    }
+    text
+}
+
+#[test]
+fn rl_fm_index_result_is_accurate() {
+    let range_chr_count = 2..6;
+    let n_text = 2;
+    let n_pattern = 100;
+    let ltks = 3;
+
+    for chr_count in range_chr_count {
+        println!("- Chr count: {}", chr_count);
+        for i in 0..n_text {
+            println!("  - text: {}/{}", i+1, n_text);
+            let chr_list = gen_rand_chr_list(chr_count);
+            // Leave out one character to also test the unindexed characters
+            let indexed_chr_list = &chr_list[..chr_count - 1];
+            for text in [
+                gen_rand_text(&chr_list, 100, 300),
+                gen_repetitive_text(&chr_list, 100, 10),
+            ] {
+                let patterns: Vec<Vec<u8>> = (0..n_pattern).map(|_| {
+                    gen_rand_pattern(&text, 1, 20)
+                }).collect();
+                assert_same_with_lt_fm_index::<u32>(indexed_chr_list, text.clone(), &patterns, ltks);
+                assert_same_with_lt_fm_index::<u64>(indexed_chr_list, text, &patterns, ltks);
+            }
+        }
+    }
+}
+
+#[test]
+fn run_count_of_repetitive_text_is_small() {
+    let chr_list = b"ACGT";
+    let text = gen_repetitive_text(chr_list, 1000, 50);
+    let patterns: Vec<Vec<u8>> = (0..100).map(|_| {
+        gen_rand_pattern(&text, 10, 50)
+    }).collect();
+    let rl_fm_index = assert_same_with_lt_fm_index::<u32>(chr_list, text.clone(), &patterns, 4);
+    assert!(rl_fm_index.run_count() < text.len() / 5);
+}
@@ -7,7 +7,7 @@ use crate::tests::random_data::{
 use std::io::Cursor;
 
 fn assert_serializing_and_estimating_size_are_success<P: Position, B: Block<P> + std::cmp::PartialEq>(
-    chr_list: &Vec<u8>,
+    chr_list: &[u8],
     text: Vec<u8>,
     ltks: u32,
     sasr: u64,
@@ -16,7 +16,7 @@ fn assert_serializing_and_estimating_size_are_success<P: Position, B: Block<P> +
         println!("          pass");
         return;
     }
-    let characters_by_index = chr_list.chunks(1).map(|c| c).collect::<Vec<_>>();
+    let characters_by_index = chr_list.chunks(1).collect::<Vec<_>>();
     let lt_fm_index = LtFmIndex::<P, B>::build(
         text,
         &characters_by_index,
@@ -92,3 +92,29 @@ fn save_and_load() {
         }
     }
 }
+
+#[test]
+fn index_of_newer_format_version_is_not_loaded() {
+    let text = b"ACGTACGTTGCAACGT".to_vec();
+    let lt_fm_index = LtFmIndex::<u32, Block2<u64>>::build(text, &[b"A", b"C", b"G"], 2, 2).unwrap();
+    let mut buffer = Vec::new();
+    lt_fm_index.save_to(&mut buffer).unwrap();
+    // The version follows the magic of 8 bytes
+    buffer[8..12].copy_from_slice(&u32::MAX.to_ne_bytes());
+
+    let error = LtFmIndex::<u32, Block2<u64>>::load_from(Cursor::new(buffer)).unwrap_err();
+    assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+}
+
+// Saved by 0.7.1 on the little-endian machine, without the header of the format version
+#[cfg(target_endian = "little")]
+#[test]
+fn index_of_0_7_is_loaded() {
+    let text = b"CTCCGTACACCTGTTTCGTATCGGAXXYYZZ".to_vec();
+    let lt_fm_index = LtFmIndex::<u32, Block2<u64>>::build(text, &[b"A", b"C", b"G"], 2, 3).unwrap();
+    let saved: &[u8] = include_bytes!("index_of_0_7.bin");
+
+    let loaded = LtFmIndex::<u32, Block2<u64>>::load_from(saved).unwrap();
+    assert_eq!(loaded, lt_fm_index);
+    assert_eq!(loaded.count(b"CG"), 3);
+}
@@ -0,0 +1,77 @@
+use crate::{LtFmIndex, Position, BwmBackend, BuildOptions};
+use crate::blocks::{Block2, Block3, Block4, Block5, Block6};
+use crate::backends::{WaveletTree, Interleaved, Superblock};
+use crate::tests::random_data::{
+    option_list,
+    build_index,
+};
+
+#[cfg(feature = "serde")]
+fn assert_serde_round_trip<P, B>(options: &BuildOptions) where
+    P: Position,
+    B: BwmBackend<P>,
+    LtFmIndex<P, B>: serde::Serialize + serde::de::DeserializeOwned + PartialEq,
+{
+    let (_, _, index) = build_index::<P, B>(options);
+    let json = serde_json::to_vec(&index).unwrap();
+    let deserialized: LtFmIndex<P, B> = serde_json::from_slice(&json).unwrap();
+    assert_eq!(deserialized, index);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_round_trip() {
+    for options in option_list().iter() {
+        assert_serde_round_trip::<u32, Block2<u32>>(options);
+        assert_serde_round_trip::<u32, Block3<u64>>(options);
+        assert_serde_round_trip::<u64, Block4<u128>>(options);
+        assert_serde_round_trip::<u64, Block5<u64>>(options);
+        assert_serde_round_trip::<u32, Block6<u32>>(options);
+        assert_serde_round_trip::<u32, WaveletTree>(options);
+        assert_serde_round_trip::<u64, Interleaved<Block3<u128>>>(options);
+        assert_serde_round_trip::<u64, Superblock<Block4<u32>, 4>>(options);
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_embedded_in_other_structure() {
+    #[derive(serde::Serialize, serde::Deserialize)]
+    struct Reference {
+        name: String,
+        index: LtFmIndex<u32, Block3<u64>>,
+    }
+    let reference = Reference {
+        name: "reference".to_string(),
+        index: build_index(&BuildOptions::new()).2,
+    };
+    let json = serde_json::to_string(&reference).unwrap();
+    let deserialized: Reference = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized.name, reference.name);
+    assert_eq!(deserialized.index, reference.index);
+}
+
+#[cfg(feature = "rkyv")]
+macro_rules! assert_rkyv_round_trip {
+    ($position:ty, $backend:ty, $options:expr) => {
+        let (_, _, index) = build_index::<$position, $backend>($options);
+        let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&index).unwrap();
+        let deserialized = rkyv::from_bytes::<LtFmIndex<$position, $backend>, rkyv::rancor::Error>(&bytes).unwrap();
+        assert_eq!(deserialized, index);
+    };
+}
+
+#[cfg(feature = "rkyv")]
+#[test]
+fn rkyv_round_trip() {
+    for options in option_list().iter() {
+        assert_rkyv_round_trip!(u32, Block2<u32>, options);
+        assert_rkyv_round_trip!(u32, Block3<u64>, options);
+        assert_rkyv_round_trip!(u64, Block4<u128>, options);
+        assert_rkyv_round_trip!(u64, Block5<u64>, options);
+        assert_rkyv_round_trip!(u32, Block6<u32>, options);
+        assert_rkyv_round_trip!(u32, WaveletTree, options);
+        assert_rkyv_round_trip!(u64, Interleaved<Block3<u128>>, options);
+        assert_rkyv_round_trip!(u64, Superblock<Block4<u32>, 4>, options);
+    }
+}
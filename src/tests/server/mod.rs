@@ -0,0 +1,137 @@
+use crate::{LtFmIndex, AnyLtFmIndex, BuildOptions, PositionType, BackendType};
+use crate::bio_formats::{AnyMultiSequenceIndex, Coordinate};
+use crate::blocks::Block3;
+use crate::server::{IndexServer, IndexClient};
+use crate::tests::random_data::{
+    gen_rand_text,
+    gen_rand_pattern,
+};
+use std::io::ErrorKind;
+
+fn build_index(text: Vec<u8>) -> LtFmIndex<u32, Block3<u64>> {
+    LtFmIndex::build(text, &[b"A", b"C", b"G", b"T"], 2, 3).unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn query_over_tcp() {
+    let texts = [gen_rand_text(b"ACGT", 100, 300), gen_rand_text(b"ACGT", 100, 300)];
+    let indexes = texts.clone().map(build_index);
+    let mut server = IndexServer::new();
+    server.add_index("first", indexes[0].clone());
+    server.add_index("second", indexes[1].clone());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let serving = tokio::spawn(server.serve_tcp(listener));
+
+    let mut client = IndexClient::connect_tcp(address).await.unwrap();
+    assert_eq!(client.list().await.unwrap(), vec!["first".to_string(), "second".to_string()]);
+    for (name, (text, index)) in ["first", "second"].into_iter().zip(texts.iter().zip(indexes.iter())) {
+        for _ in 0..20 {
+            let pattern = gen_rand_pattern(text, 1, 10);
+            assert_eq!(client.count(name, &pattern).await.unwrap(), index.count(&pattern) as u64);
+            let mut locations: Vec<u64> = index.locate(&pattern).into_iter().map(u64::from).collect();
+            locations.sort();
+            assert_eq!(client.locate(name, &pattern).await.unwrap(), locations);
+        }
+    }
+    // Pattern with a space
+    assert_eq!(client.count("first", b"AC GT").await.unwrap(), 0);
+    // Errors of the server do not close the connection
+    let error = client.count("third", b"ACGT").await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Other);
+    assert!(error.to_string().contains("Unknown index"));
+    let error = client.count("first", b"AC\nGT").await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::InvalidData);
+    assert_eq!(client.count("first", b"ACGT").await.unwrap(), indexes[0].count(b"ACGT") as u64);
+    serving.abort();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn long_request_is_skipped() {
+    let mut server = IndexServer::new();
+    server.add_index("index", build_index(b"ACGTACGTTTACGT".to_vec()));
+    server.set_max_request_len(16);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let serving = tokio::spawn(server.serve_tcp(listener));
+
+    let mut client = IndexClient::connect_tcp(address).await.unwrap();
+    // "COUNT index " and the pattern of 4 bytes is 16 bytes
+    assert_eq!(client.count("index", b"ACGT").await.unwrap(), 3);
+    let long_pattern = vec![b'A'; 10_000];
+    let error = client.count("index", &long_pattern).await.unwrap_err();
+    assert_eq!(error.kind(), ErrorKind::Other);
+    assert!(error.to_string().contains("longer than 16 bytes"));
+    let error = client.locate("index", b"ACGTA").await.unwrap_err();
+    assert!(error.to_string().contains("longer than 16 bytes"));
+    // The connection is kept after the long requests
+    assert_eq!(client.locate("index", b"TTA").await.unwrap(), vec![8]);
+    serving.abort();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn query_loaded_files() {
+    // The file of an index, and of the sequences as the command-line tool writes
+    let text_index = AnyLtFmIndex::from(build_index(b"ACGTACGTTTACGT".to_vec()));
+    let mut text_file = Vec::new();
+    text_index.save_to(&mut text_file).unwrap();
+    let fasta = b">chr1\nACGTACGTAC\n>chr2\nGTTACGTAA\n";
+    let sequences_index = AnyMultiSequenceIndex::build_from_fasta(
+        &fasta[..], &[b"A", b"C", b"G", b"T"], PositionType::U32, BackendType::Block3, 2, 3, &BuildOptions::default(),
+    ).unwrap();
+    let mut sequences_file = Vec::new();
+    sequences_index.save_to(&mut sequences_file).unwrap();
+
+    let mut server = IndexServer::new();
+    server.load_index("text", &text_file[..]).unwrap();
+    server.load_index("genome", &sequences_file[..]).unwrap();
+    assert!(server.load_index("invalid", &b"ACGT"[..]).is_err());
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap();
+    let serving = tokio::spawn(server.serve_tcp(listener));
+
+    let mut client = IndexClient::connect_tcp(address).await.unwrap();
+    assert_eq!(client.list().await.unwrap(), vec!["genome".to_string(), "text".to_string()]);
+    assert_eq!(client.count("text", b"ACGT").await.unwrap(), 3);
+    assert_eq!(client.locate("text", b"ACGT").await.unwrap(), vec![0, 4, 10]);
+    // "ACGTAC" is across the sequences in the concatenated text
+    for pattern in [&b"ACGT"[..], b"ACGTAC", b"TTAC"] {
+        assert_eq!(client.count("genome", pattern).await.unwrap(), sequences_index.count(pattern));
+        let mut locations: Vec<(String, u64)> = sequences_index.locate_in_contigs(pattern, Coordinate::ZeroBased)
+            .into_iter().map(|(contig, offset)| (contig.to_string(), offset)).collect();
+        locations.sort();
+        assert_eq!(client.locate_in_contigs("genome", pattern).await.unwrap(), locations);
+    }
+    assert_eq!(
+        client.locate_in_contigs("genome", b"ACGT").await.unwrap(),
+        vec![("chr1".to_string(), 0), ("chr1".to_string(), 4), ("chr2".to_string(), 3)],
+    );
+    // The locate of the other kind of index is the error
+    let error = client.locate("genome", b"ACGT").await.unwrap_err();
+    assert!(error.to_string().contains("LOCATE_IN_CONTIGS"));
+    let error = client.locate_in_contigs("text", b"ACGT").await.unwrap_err();
+    assert!(error.to_string().contains("no contigs"));
+    serving.abort();
+}
+
+#[cfg(unix)]
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn query_over_unix_socket() {
+    let path = std::env::temp_dir().join(format!("lt-fm-index-test-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let mut server = IndexServer::new();
+    server.add_index("index", build_index(b"ACGTACGTTTACGT".to_vec()));
+    let listener = tokio::net::UnixListener::bind(&path).unwrap();
+    let serving = tokio::spawn(server.serve_unix(listener));
+
+    let mut clients = [
+        IndexClient::connect_unix(&path).await.unwrap(),
+        IndexClient::connect_unix(&path).await.unwrap(),
+    ];
+    for client in clients.iter_mut() {
+        assert_eq!(client.count("index", b"ACGT").await.unwrap(), 3);
+        assert_eq!(client.locate("index", b"ACGT").await.unwrap(), vec![0, 4, 10]);
+    }
+    serving.abort();
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,56 @@
+use crate::algorithm::blocks::simd_rank::{
+    Kernel,
+    count_pair_scalar,
+    count_pair_u64_with,
+    count_pair_u128_with,
+};
+use rand::Rng;
+
+fn assert_kernels_are_same_as_scalar<const N: usize>() {
+    let mut rng = rand::rng();
+    for _ in 0..2000 {
+        let vectors_u64: [[u64; N]; 2] = [[0; N]; 2].map(|vectors| vectors.map(|_| rng.random()));
+        let vectors_u128: [[u128; N]; 2] = [[0; N]; 2].map(|vectors| vectors.map(|_| rng.random()));
+        let rems_u64 = [rng.random_range(0..64), rng.random_range(0..64)];
+        let rems_u128 = [rng.random_range(0..128), rng.random_range(0..128)];
+        let chridx = rng.random_range(0..(1 << N)) as u8;
+        let expected_u64 = count_pair_scalar([&vectors_u64[0], &vectors_u64[1]], rems_u64, chridx);
+        let expected_u128 = count_pair_scalar([&vectors_u128[0], &vectors_u128[1]], rems_u128, chridx);
+        for kernel in Kernel::supported() {
+            assert_eq!(
+                count_pair_u64_with(kernel, [&vectors_u64[0], &vectors_u64[1]], rems_u64, chridx),
+                expected_u64,
+                "{:?}", kernel,
+            );
+            assert_eq!(
+                count_pair_u128_with(kernel, [&vectors_u128[0], &vectors_u128[1]], rems_u128, chridx),
+                expected_u128,
+                "{:?}", kernel,
+            );
+        }
+    }
+}
+
+#[test]
+fn kernels_are_same_as_scalar() {
+    assert_kernels_are_same_as_scalar::<5>();
+    assert_kernels_are_same_as_scalar::<6>();
+}
+
+#[test]
+fn scalar_is_same_as_bit_by_bit_count() {
+    let mut rng = rand::rng();
+    for _ in 0..500 {
+        let vectors: [u64; 5] = [0; 5].map(|_| rng.random());
+        let rem = rng.random_range(0..64);
+        let chridx = rng.random_range(0..32);
+        // The first bit is the most significant bit
+        let expected = (0..rem).filter(|&offset| {
+            let chridx_at = (0..5).fold(0, |chridx_at, bit| {
+                chridx_at | ((vectors[bit] >> (63 - offset)) as u8 & 1) << bit
+            });
+            chridx_at == chridx
+        }).count() as u32;
+        assert_eq!(count_pair_scalar([&vectors, &vectors], [rem, 0], chridx), [expected, 0]);
+    }
+}
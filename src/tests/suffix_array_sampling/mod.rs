@@ -0,0 +1,68 @@
+use crate::{LtFmIndex, Position, BuildOptions, SuffixArraySampling};
+use crate::blocks::Block4;
+use crate::tests::{
+    random_data::{
+        gen_rand_chr_list,
+        gen_rand_text,
+        gen_rand_pattern,
+    },
+    result_answer::{
+        get_fmindex_of_other_crate,
+        get_sorted_locations,
+    },
+};
+use std::io::Cursor;
+
+fn assert_accurate_with_text_position_sampling<P: Position>(
+    chr_list: &[u8],
+    text: Vec<u8>,
+    patterns: &[Vec<u8>],
+    answers: &[Vec<u64>],
+    sasr: u64,
+) {
+    let characters_by_index = chr_list.chunks(1).collect::<Vec<_>>();
+    let options = BuildOptions::new().suffix_array_sampling(SuffixArraySampling::TextPosition);
+    let lt_fm_index = LtFmIndex::<P, Block4<u64>>::build_with_options(
+        text,
+        &characters_by_index,
+        P::from_u64(sasr),
+        3,
+        &options,
+    ).unwrap();
+    assert_eq!(lt_fm_index.suffix_array_sampling(), SuffixArraySampling::TextPosition);
+    patterns.iter().zip(answers.iter()).for_each(|(pattern, answer)| {
+        let mut result: Vec<u64> = lt_fm_index.locate(pattern).into_iter().map(|x| x.as_u64()).collect();
+        result.sort();
+        assert_eq!(&result, answer);
+    });
+
+    let mut buffer = Vec::new();
+    lt_fm_index.save_to(&mut buffer).unwrap();
+    assert_eq!(lt_fm_index.encoded_len(), buffer.len());
+    let loaded = LtFmIndex::<P, Block4<u64>>::load_from(Cursor::new(buffer)).unwrap();
+    assert_eq!(lt_fm_index, loaded);
+}
+
+#[test]
+fn text_position_sampling_result_is_accurate() {
+    let n_text = 3;
+    let n_pattern = 100;
+    for sasr in [1, 2, 5, 16] {
+        for _ in 0..n_text {
+            let chr_list = gen_rand_chr_list(8);
+            let text = gen_rand_text(&chr_list, 300, 600);
+            let indexed_chr_list = &chr_list[..7];
+            let patterns: Vec<Vec<u8>> = (0..n_pattern).map(|_| {
+                gen_rand_pattern(&text, 1, 10)
+            }).collect();
+            let answers: Vec<Vec<u64>> = {
+                let fm_index = get_fmindex_of_other_crate(&text);
+                patterns.iter().map(|pattern| {
+                    get_sorted_locations(&fm_index, pattern)
+                }).collect()
+            };
+            assert_accurate_with_text_position_sampling::<u32>(indexed_chr_list, text.clone(), &patterns, &answers, sasr);
+            assert_accurate_with_text_position_sampling::<u64>(indexed_chr_list, text, &patterns, &answers, sasr);
+        }
+    }
+}
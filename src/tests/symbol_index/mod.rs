@@ -0,0 +1,99 @@
+use crate::{SymbolLtFmIndex, Symbol, Position, BwmBackend, BuildOptions, UnindexedCharacters, BuildError};
+use crate::blocks::{Block3, Block5};
+use crate::backends::WaveletTree;
+use rand::Rng;
+
+// Random symbols, the last of them unindexed
+fn gen_rand_symbol_list<S: Symbol>(symbol_count: usize, max: u32) -> Vec<S> {
+    let mut rng = rand::rng();
+    let mut symbol_list = Vec::with_capacity(symbol_count);
+    while symbol_list.len() < symbol_count {
+        let symbol = S::from_u32(rng.random_range(0..=max));
+        if !symbol_list.contains(&symbol) {
+            symbol_list.push(symbol);
+        }
+    }
+    symbol_list
+}
+fn gen_rand_symbol_text<S: Symbol>(symbol_list: &[S], len: usize) -> Vec<S> {
+    let mut rng = rand::rng();
+    (0..len).map(|_| symbol_list[rng.random_range(0..symbol_list.len())]).collect()
+}
+// Sorted locations of the pattern of which the symbols are the same group
+fn naive_locations<S: Symbol>(text: &[S], pattern: &[S], chridx_of: impl Fn(S) -> u8, separator: Option<u8>) -> Vec<u64> {
+    if pattern.iter().any(|&symbol| Some(chridx_of(symbol)) == separator) {
+        return Vec::new();
+    }
+    text.windows(pattern.len()).enumerate().filter(|(_, window)| {
+        window.iter().zip(pattern).all(|(&a, &b)| chridx_of(a) == chridx_of(b))
+    }).map(|(location, _)| location as u64).collect()
+}
+
+fn assert_symbol_index_is_accurate<S: Symbol, P: Position, B: BwmBackend<P>>(
+    group_count: usize,
+    max: u32,
+    options: &BuildOptions,
+) {
+    let symbol_list: Vec<S> = gen_rand_symbol_list(group_count * 2 + 1, max);
+    // Two symbols in each group
+    let symbols_by_index: Vec<&[S]> = symbol_list[..group_count * 2].chunks(2).collect();
+    let text = gen_rand_symbol_text(&symbol_list, 500);
+    let index = SymbolLtFmIndex::<S, P, B>::build_with_options(
+        text.clone(), &symbols_by_index, P::from_u64(3), 2, options,
+    ).unwrap();
+    assert_eq!(index.len_text().as_usize(), text.len());
+    assert_eq!(index.symbol_groups().len(), group_count);
+    let separator = match options.unindexed_characters {
+        UnindexedCharacters::Same => None,
+        UnindexedCharacters::Separator => Some(group_count as u8),
+    };
+    let mut rng = rand::rng();
+    for _ in 0..50 {
+        let start = rng.random_range(0..text.len() - 8);
+        let pattern = &text[start..start + rng.random_range(1..8)];
+        let expected = naive_locations(&text, pattern, |symbol| index.chridx_of(symbol), separator);
+        let mut locations: Vec<u64> = index.locate(pattern).into_iter().map(Position::as_u64).collect();
+        locations.sort();
+        assert_eq!(locations, expected);
+        assert_eq!(index.count(pattern).as_usize(), expected.len());
+        assert_eq!(index.index().count_encoded_pattern(&index.encode_pattern(pattern)).as_usize(), expected.len());
+    }
+}
+
+#[test]
+fn symbol_index_is_accurate() {
+    for options in [BuildOptions::new(), BuildOptions::new().unindexed_characters(UnindexedCharacters::Separator)].iter() {
+        assert_symbol_index_is_accurate::<u16, u32, Block3<u64>>(3, u16::MAX as u32, options);
+        assert_symbol_index_is_accurate::<u32, u64, Block5<u64>>(20, u32::MAX, options);
+        assert_symbol_index_is_accurate::<u32, u32, WaveletTree>(100, 1_000_000, options);
+        assert_symbol_index_is_accurate::<u8, u32, Block3<u32>>(3, u8::MAX as u32, options);
+    }
+}
+
+#[test]
+fn symbol_index_is_saved_and_loaded() {
+    let symbol_list: Vec<u32> = gen_rand_symbol_list(11, u32::MAX);
+    let symbols_by_index: Vec<&[u32]> = symbol_list[..10].chunks(1).collect();
+    let text = gen_rand_symbol_text(&symbol_list, 300);
+    let index = SymbolLtFmIndex::<u32, u32, Block5<u64>>::build(text.clone(), &symbols_by_index, 2, 2).unwrap();
+    let mut buffer = Vec::new();
+    index.save_to(&mut buffer).unwrap();
+    assert_eq!(buffer.len(), index.encoded_len());
+    let loaded = SymbolLtFmIndex::<u32, u32, Block5<u64>>::load_from(&buffer[..]).unwrap();
+    assert_eq!(loaded, index);
+    assert_eq!(loaded.count(&text[10..13]), index.count(&text[10..13]));
+
+    // Symbols wider than the symbol type
+    assert!(SymbolLtFmIndex::<u8, u32, Block5<u64>>::load_from(&buffer[..]).is_err());
+}
+
+#[test]
+fn invalid_symbol_groups_are_error() {
+    let result = SymbolLtFmIndex::<u16, u32, Block3<u64>>::build(vec![1, 2, 3], &[[1, 2], [2, 3]], 2, 2);
+    assert!(matches!(result, Err(BuildError::DuplicateSymbol(2))));
+    let result = SymbolLtFmIndex::<u16, u32, Block3<u64>>::build::<[u16; 1]>(vec![1, 2, 3], &[], 2, 2);
+    assert!(matches!(result, Err(BuildError::EmptyAlphabet)));
+    let groups: Vec<[u16; 1]> = (0..255).map(|symbol| [symbol]).collect();
+    let result = SymbolLtFmIndex::<u16, u32, WaveletTree>::build(vec![1, 2, 3], &groups, 2, 2);
+    assert!(matches!(result, Err(BuildError::TooManyCharacterGroups(255))));
+}
@@ -0,0 +1,73 @@
+use crate::{LtFmIndex, RlFmIndex, BuildOptions, UnindexedCharacters};
+use crate::blocks::Block3;
+use crate::tests::random_data::gen_rand_pattern;
+use rand::Rng;
+use std::io::Cursor;
+
+// Nucleotide text with the gaps of `N`
+fn gen_text_with_gaps(len: usize) -> Vec<u8> {
+    let mut rng = rand::rng();
+    (0..len).map(|_| {
+        if rng.random_bool(0.1) { b'N' } else { b"ACGT"[rng.random_range(0..4)] }
+    }).collect()
+}
+
+fn naive_locations(text: &[u8], pattern: &[u8]) -> Vec<u32> {
+    text.windows(pattern.len()).enumerate().filter_map(|(location, window)| {
+        let matched = window == pattern && !pattern.contains(&b'N');
+        matched.then_some(location as u32)
+    }).collect()
+}
+
+#[test]
+fn separators_are_never_matched() {
+    let characters_by_index: &[&[u8]] = &[b"A", b"C", b"G", b"T"];
+    let options = BuildOptions::new().unindexed_characters(UnindexedCharacters::Separator);
+    for _ in 0..3 {
+        let text = gen_text_with_gaps(1000);
+        let lt_fm_index = LtFmIndex::<u32, Block3<u64>>::build_with_options(
+            text.clone(), characters_by_index, 2, 3, &options,
+        ).unwrap();
+        let rl_fm_index = RlFmIndex::<u32>::build_with_options(
+            text.clone(), characters_by_index, 3, &options,
+        ).unwrap();
+        assert_eq!(lt_fm_index.unindexed_characters(), UnindexedCharacters::Separator);
+
+        for _ in 0..200 {
+            let pattern = gen_rand_pattern(&text, 1, 8);
+            let answer = naive_locations(&text, &pattern);
+
+            let mut result = lt_fm_index.locate(&pattern);
+            result.sort();
+            assert_eq!(result, answer);
+            assert_eq!(lt_fm_index.count(&pattern), answer.len() as u32);
+            let mut result = rl_fm_index.locate(&pattern);
+            result.sort();
+            assert_eq!(result, answer);
+            let decoding_table = lt_fm_index.decoding_table();
+            let mut result = lt_fm_index.locate_from_raw_index(
+                pattern.iter().rev().map(|chr| decoding_table[*chr as usize]),
+            );
+            result.sort();
+            assert_eq!(result, answer);
+        }
+
+        let mut buffer = Vec::new();
+        lt_fm_index.save_to(&mut buffer).unwrap();
+        assert_eq!(lt_fm_index.encoded_len(), buffer.len());
+        let loaded = LtFmIndex::<u32, Block3<u64>>::load_from(Cursor::new(buffer)).unwrap();
+        assert_eq!(lt_fm_index, loaded);
+    }
+}
+
+#[test]
+fn unindexed_characters_are_same_by_default() {
+    let text = b"ACGTNNNNACGTXXXX".to_vec();
+    let characters_by_index: &[&[u8]] = &[b"A", b"C", b"G", b"T"];
+    let lt_fm_index = LtFmIndex::<u32, Block3<u64>>::build(
+        text, characters_by_index, 2, 3,
+    ).unwrap();
+    assert_eq!(lt_fm_index.unindexed_characters(), UnindexedCharacters::Same);
+    assert_eq!(lt_fm_index.count(b"TNN"), 2);
+    assert_eq!(lt_fm_index.count(b"NNNN"), 2);
+}
@@ -0,0 +1,77 @@
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+use crate::{LtFmIndex, AnyLtFmIndex, Position, BwmBackend};
+use crate::blocks::{Block2, Block3, Block4, Block5, Block6};
+use crate::backends::WaveletTree;
+use crate::wasm::WasmLtFmIndex;
+use crate::tests::random_data::{
+    gen_rand_text,
+    gen_rand_pattern,
+};
+
+fn serialized_index<P: Position, B: BwmBackend<P>>(text: &[u8]) -> (LtFmIndex<P, B>, Vec<u8>) where
+    AnyLtFmIndex: From<LtFmIndex<P, B>>,
+{
+    let index = LtFmIndex::<P, B>::build(text.to_vec(), &[b"Aa", b"Cc", b"Gg"], P::from_u64(2), 3).unwrap();
+    let mut bytes = Vec::new();
+    AnyLtFmIndex::from(index.clone()).save_to(&mut bytes).unwrap();
+    (index, bytes)
+}
+
+fn assert_same_results<P: Position, B: BwmBackend<P>>(position: &str, backend: &str) where
+    AnyLtFmIndex: From<LtFmIndex<P, B>>,
+{
+    let text = gen_rand_text(b"ACGT", 100, 300);
+    let (index, bytes) = serialized_index::<P, B>(&text);
+    let loaded = WasmLtFmIndex::from_bytes(&bytes).unwrap();
+    assert_eq!((loaded.position().as_str(), loaded.backend().as_str()), (position, backend));
+    assert_eq!(loaded.text_length(), text.len() as f64);
+    for _ in 0..20 {
+        let pattern = gen_rand_pattern(&text, 1, 10);
+        assert_eq!(loaded.count(&pattern), index.count(&pattern).as_u64() as f64);
+        let mut locations = index.locate(&pattern).into_iter().map(|x| x.as_u64() as f64).collect::<Vec<_>>();
+        locations.sort_by(f64::total_cmp);
+        assert_eq!(loaded.locate(&pattern), locations);
+    }
+}
+
+#[test]
+fn load_from_bytes() {
+    assert_same_results::<u32, Block2<u64>>("u32", "block2");
+    assert_same_results::<u32, Block3<u64>>("u32", "block3");
+    assert_same_results::<u32, Block4<u64>>("u32", "block4");
+    assert_same_results::<u32, Block5<u64>>("u32", "block5");
+    assert_same_results::<u32, Block6<u64>>("u32", "block6");
+    assert_same_results::<u32, WaveletTree>("u32", "wavelet");
+    assert_same_results::<u64, Block2<u64>>("u64", "block2");
+    assert_same_results::<u64, Block3<u64>>("u64", "block3");
+    assert_same_results::<u64, Block4<u64>>("u64", "block4");
+    assert_same_results::<u64, Block5<u64>>("u64", "block5");
+    assert_same_results::<u64, Block6<u64>>("u64", "block6");
+    assert_same_results::<u64, WaveletTree>("u64", "wavelet");
+}
+
+#[test]
+fn string_patterns() {
+    let (_, bytes) = serialized_index::<u32, Block2<u64>>(b"CTCCGTACACCTGTTTCGTATCGGA");
+    let loaded = WasmLtFmIndex::from_bytes(&bytes).unwrap();
+    assert_eq!(loaded.count_string("TA"), 2.0);
+    assert_eq!(loaded.locate_string("TA"), vec![5.0, 18.0]);
+    assert_eq!(loaded.locate_string("ta"), vec![5.0, 18.0]);
+}
+
+// `JsError` is only created in the wasm runtime
+#[cfg(target_arch = "wasm32")]
+#[test]
+fn invalid_bytes() {
+    let (index, bytes) = serialized_index::<u32, Block2<u64>>(b"ACGTACGT");
+    let mut unknown_position = bytes.clone();
+    unknown_position[8] = 16;
+    assert!(WasmLtFmIndex::from_bytes(&unknown_position).is_err());
+    assert!(WasmLtFmIndex::from_bytes(&bytes[..bytes.len() / 2]).is_err());
+    // Without the header of the types
+    let mut index_bytes = Vec::new();
+    index.save_to(&mut index_bytes).unwrap();
+    assert!(WasmLtFmIndex::from_bytes(&index_bytes).is_err());
+}
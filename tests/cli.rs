@@ -0,0 +1,125 @@
+// Runs the commands of the built `lt-fm-index` binary (`cli` feature)
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+const FASTA: &str = ">chr1 first\nACGTACGTAC\nGTNACG\n>chr2\nTTACGTAA\n";
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_lt-fm-index")).args(args).output().unwrap()
+}
+
+fn stdout_of(args: &[&str]) -> String {
+    let output = run(args);
+    assert!(output.status.success(), "{:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+    String::from_utf8(output.stdout).unwrap()
+}
+
+// Writes the FASTA to the directory of the test, and returns the paths of the FASTA and the index
+fn write_fasta(test_name: &str, fasta: &str) -> (String, String) {
+    let dir = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("cli").join(test_name);
+    fs::create_dir_all(&dir).unwrap();
+    let fasta_path = dir.join("input.fa");
+    fs::write(&fasta_path, fasta).unwrap();
+    let index_path = dir.join("input.ltfm");
+    (fasta_path.to_str().unwrap().to_string(), index_path.to_str().unwrap().to_string())
+}
+
+fn build(fasta_path: &str, index_path: &str, extra_args: &[&str]) {
+    let mut args = vec!["build", fasta_path, "-o", index_path, "--kmer", "2"];
+    args.extend_from_slice(extra_args);
+    stdout_of(&args);
+}
+
+#[test]
+fn count_and_locate_in_tsv() {
+    let (fasta_path, index_path) = write_fasta("count_and_locate_in_tsv", FASTA);
+    build(&fasta_path, &index_path, &[]);
+
+    // "CGTT" is across the sequences
+    assert_eq!(
+        stdout_of(&["count", &index_path, "ACGT", "CGTT", "NACG"]),
+        "ACGT\t4\nCGTT\t0\nNACG\t1\n",
+    );
+    assert_eq!(
+        stdout_of(&["locate", &index_path, "ACGT", "NACG", "--one-based"]),
+        "ACGT\tchr1\t1\nACGT\tchr1\t5\nACGT\tchr1\t9\nACGT\tchr2\t3\nNACG\tchr1\t13\n",
+    );
+
+    let patterns_path = fasta_path.replace("input.fa", "patterns.txt");
+    fs::write(&patterns_path, "GTAA\r\n\nTTAC\n").unwrap();
+    assert_eq!(
+        stdout_of(&["count", &index_path, "--patterns", &patterns_path]),
+        "GTAA\t1\nTTAC\t1\n",
+    );
+}
+
+#[test]
+fn count_and_locate_in_json() {
+    let (fasta_path, index_path) = write_fasta("count_and_locate_in_json", FASTA);
+    build(&fasta_path, &index_path, &["--position", "u64", "--backend", "wavelet"]);
+
+    let counts: serde_json::Value = serde_json::from_str(
+        &stdout_of(&["count", &index_path, "ACGT", "CGTT", "--format", "json"]),
+    ).unwrap();
+    assert_eq!(counts, serde_json::json!([
+        {"query": "ACGT", "count": 4},
+        {"query": "CGTT", "count": 0},
+    ]));
+    let locations: serde_json::Value = serde_json::from_str(
+        &stdout_of(&["locate", &index_path, "TAC", "GGG", "--format", "json"]),
+    ).unwrap();
+    assert_eq!(locations, serde_json::json!([
+        {"query": "TAC", "locations": [
+            {"contig": "chr1", "offset": 3},
+            {"contig": "chr1", "offset": 7},
+            {"contig": "chr2", "offset": 1},
+        ]},
+        {"query": "GGG", "locations": []},
+    ]));
+}
+
+#[test]
+fn info_prints_types_and_contigs() {
+    let (fasta_path, index_path) = write_fasta("info_prints_types_and_contigs", FASTA);
+    build(&fasta_path, &index_path, &["--position", "u64", "--backend", "block4"]);
+
+    let info = stdout_of(&["info", &index_path]);
+    assert!(info.contains("position: U64\n"), "{}", info);
+    assert!(info.contains("backend: Block4\n"), "{}", info);
+    assert!(info.contains("contigs:\n  chr1\t16\n  chr2\t8\n"), "{}", info);
+    let file_len = fs::metadata(&index_path).unwrap().len();
+    assert!(info.contains(&format!("  total: {}\n", file_len)), "{}", info);
+}
+
+#[test]
+fn verify_index_against_input() {
+    let (fasta_path, index_path) = write_fasta("verify_index_against_input", FASTA);
+    for unindexed in ["same", "separator"] {
+        build(&fasta_path, &index_path, &["--unindexed", unindexed]);
+        let verified = stdout_of(&["verify", &index_path, &fasta_path, "--samples", "24", "--length", "4"]);
+        assert_eq!(verified, "OK: 24 patterns are verified\n");
+    }
+
+    // The sequences of the same names and lengths, but of the other characters
+    let other_fasta_path = fasta_path.replace("input.fa", "other.fa");
+    fs::write(&other_fasta_path, FASTA.replace("TTACGTAA", "TTACCTAA")).unwrap();
+    let output = run(&["verify", &index_path, &other_fasta_path, "--samples", "24", "--length", "4"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert!(stderr.contains("Failed: ACCT\tchr2\t2\n"), "{}", stderr);
+    assert!(stderr.contains("patterns are failed"), "{}", stderr);
+}
+
+#[test]
+fn index_file_is_loaded_by_library() {
+    let (fasta_path, index_path) = write_fasta("index_file_is_loaded_by_library", FASTA);
+    build(&fasta_path, &index_path, &["--position", "u32", "--backend", "block3"]);
+
+    // The index of the concatenated sequences, after the header of the types
+    let index = lt_fm_index::AnyLtFmIndex::load_from(fs::File::open(&index_path).unwrap()).unwrap();
+    assert_eq!(index.position_type(), lt_fm_index::PositionType::U32);
+    assert_eq!(index.backend_type(), lt_fm_index::BackendType::Block3);
+    // One more than the count in the sequences, across them in the concatenated text
+    assert_eq!(index.count(b"ACGT"), 5);
+}